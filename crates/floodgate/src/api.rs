@@ -104,22 +104,50 @@ pub enum EventData<'a> {
     Record {
         #[serde(borrow)]
         record: RecordEventData<'a>,
+        /// The original JSON text of the event, if the channel retains it.
+        #[serde(skip)]
+        raw: Option<String>,
     },
     Identity {
         #[serde(borrow)]
         identity: IdentityEventData<'a>,
+        /// The original JSON text of the event, if the channel retains it.
+        #[serde(skip)]
+        raw: Option<String>,
     },
 }
 
+impl EventData<'_> {
+    /// The original JSON text the event was parsed from.
+    ///
+    /// Only present when the channel was built with
+    /// [`retain_raw_json`](crate::channel::ChannelBuilder::retain_raw_json),
+    /// so handlers can record the exact bytes of an event - for a dead-letter
+    /// table, say - without re-serializing the parsed representation.
+    pub fn raw(&self) -> Option<&str> {
+        match self {
+            EventData::Record { raw, .. } | EventData::Identity { raw, .. } => raw.as_deref(),
+        }
+    }
+
+    pub(crate) fn set_raw(&mut self, text: String) {
+        match self {
+            EventData::Record { raw, .. } | EventData::Identity { raw, .. } => *raw = Some(text),
+        }
+    }
+}
+
 impl IntoStatic for EventData<'_> {
     type Output = EventData<'static>;
     fn into_static(self) -> Self::Output {
         match self {
-            EventData::Record { record } => EventData::Record {
+            EventData::Record { record, raw } => EventData::Record {
                 record: record.into_static(),
+                raw,
             },
-            EventData::Identity { identity } => EventData::Identity {
+            EventData::Identity { identity, raw } => EventData::Identity {
                 identity: identity.into_static(),
+                raw,
             },
         }
     }
@@ -1,9 +1,22 @@
-use crate::api::{Event, EventData};
+use crate::{
+    api::{Event, EventData},
+    cursor::CursorStore,
+    metrics::ChannelMetrics,
+};
 use futures_util::{SinkExt, StreamExt};
-use jacquard_common::IntoStatic;
+use jacquard_common::{IntoStatic, types::nsid::Nsid};
+use rand::Rng;
 use reqwest::header::{AUTHORIZATION, HeaderValue, USER_AGENT};
 use serde::Serialize;
-use std::{num::NonZero, sync::Arc};
+use std::{
+    collections::HashMap,
+    num::NonZero,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU32, AtomicUsize, Ordering},
+    },
+    time::{Duration, Instant},
+};
 use tokio::{
     sync::{Semaphore, mpsc},
     task::JoinSet,
@@ -12,6 +25,7 @@ use tokio_tungstenite::{
     connect_async,
     tungstenite::{Message, client::IntoClientRequest},
 };
+use tokio_util::sync::CancellationToken;
 use url::Url;
 
 #[derive(Debug)]
@@ -23,8 +37,39 @@ pub struct ChannelConnectionHandle {
             tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>,
         >,
     >,
-    ack_tx: mpsc::UnboundedSender<u64>,
+    ack_tx: mpsc::UnboundedSender<WriterMessage>,
     semaphore: Arc<Semaphore>,
+    collection_semaphores: Arc<HashMap<String, Arc<Semaphore>>>,
+    max_concurrent: Arc<AtomicUsize>,
+    metrics: Option<Arc<dyn ChannelMetrics>>,
+    idle_timeout: Duration,
+    retain_raw_json: bool,
+}
+
+/// Messages forwarded to the writer task, which owns the websocket sink.
+enum WriterMessage {
+    Ack(u64),
+    Pong(tokio_tungstenite::tungstenite::Bytes),
+}
+
+/// How successfully handled events are acknowledged back to the server.
+///
+/// The default is [`Immediate`](AckStrategy::Immediate), one ack frame per
+/// event. The other strategies coalesce ids into `{"type": "ack", "ids":
+/// [...]}` frames, which cuts the write overhead on high-throughput streams
+/// but depends on the server accepting batched acks - they are opt-in for
+/// that reason.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum AckStrategy {
+    /// One `{"type": "ack", "id": N}` frame per event, sent as soon as the
+    /// event's handler succeeds.
+    Immediate,
+    /// Accumulate up to `max_ids` event ids, then send them in a single
+    /// frame.
+    Batched { max_ids: NonZero<usize> },
+    /// Flush whatever ids accumulated every `window`.
+    Windowed { window: Duration },
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -36,14 +81,34 @@ pub enum ConnectionError {
     WebSocketHttpResponseFailure(tokio_tungstenite::tungstenite::handshake::client::Response),
 }
 
+/// How often the writer task persists the highest acked event id to the cursor store.
+const CURSOR_SAVE_INTERVAL: Duration = Duration::from_secs(5);
+/// How many acks may be sent before the cursor store is persisted regardless of time.
+const CURSOR_SAVE_ACK_COUNT: u64 = 100;
+
 impl ChannelConnectionHandle {
-    async fn connect(
-        mut url: Url,
-        auth_header: Option<HeaderValue>,
-        max_concurrent: NonZero<usize>,
-    ) -> Result<Self, ConnectionError> {
+    async fn connect(channel: &Channel) -> Result<Self, ConnectionError> {
+        let mut url = channel.base_url.clone();
         url.set_path("/channel");
 
+        // Ask the server to only forward records from the given collections. Servers
+        // without filtering support ignore this and forward everything as usual.
+        if !channel.collections.is_empty() {
+            let mut query_pairs = url.query_pairs_mut();
+            for collection in &channel.collections {
+                query_pairs.append_pair("collections", collection.as_str());
+            }
+        }
+
+        // Resume from the last persisted cursor position if one is available.
+        if let Some(store) = &channel.cursor_store
+            && let Some(cursor) = store.load()
+        {
+            log::debug!("resuming channel from cursor {cursor}");
+            url.query_pairs_mut()
+                .append_pair("cursor", &cursor.to_string());
+        }
+
         // Create websocket request with appropriate headers.
         let mut request = url.as_str().into_client_request()?;
         request.headers_mut().insert(
@@ -54,7 +119,7 @@ impl ChannelConnectionHandle {
                 env!("CARGO_PKG_VERSION")
             )),
         );
-        if let Some(auth_header) = auth_header {
+        if let Some(auth_header) = channel.auth_header.clone() {
             request.headers_mut().insert(AUTHORIZATION, auth_header);
         }
 
@@ -68,76 +133,235 @@ impl ChannelConnectionHandle {
         // Open appropriate channels for communicating via websocket.
         log::debug!("opened websocket connection to {url}");
         let (write, read) = websocket_stream.split();
-        let (ack_tx, ack_rx) = mpsc::unbounded_channel::<u64>();
-        let semaphore = Arc::new(Semaphore::new(max_concurrent.get()));
+        let (ack_tx, ack_rx) = mpsc::unbounded_channel::<WriterMessage>();
+        let semaphore = Arc::new(Semaphore::new(channel.max_concurrent.get()));
 
         log::trace!("spawning handler writer task");
+        let cursor_store = channel.cursor_store.clone();
+        let ping_interval = channel.ping_interval;
+        let ack_strategy = channel.ack_strategy;
+        let metrics = channel.metrics.clone();
         tokio::spawn(async move {
-            Self::writer_task(write, ack_rx).await;
+            Self::writer_task(
+                write,
+                ack_rx,
+                cursor_store,
+                ping_interval,
+                ack_strategy,
+                metrics,
+            )
+            .await;
         });
 
         Ok(Self {
             read,
             ack_tx,
             semaphore,
+            collection_semaphores: Arc::new(
+                channel
+                    .collection_limits
+                    .iter()
+                    .map(|(nsid, limit)| (nsid.clone(), Arc::new(Semaphore::new(limit.get()))))
+                    .collect(),
+            ),
+            max_concurrent: Arc::new(AtomicUsize::new(channel.max_concurrent.get())),
+            metrics: channel.metrics.clone(),
+            idle_timeout: channel.idle_timeout,
+            retain_raw_json: channel.retain_raw_json,
         })
     }
 
+    /// Permits not currently held by an in-flight handler task.
+    pub fn available_permits(&self) -> usize {
+        self.semaphore.available_permits()
+    }
+
+    /// Handler tasks currently holding a permit.
+    pub fn in_flight(&self) -> usize {
+        self.max_concurrent
+            .load(Ordering::Relaxed)
+            .saturating_sub(self.semaphore.available_permits())
+    }
+
+    /// Obtain a clonable control for observing and resizing the handler
+    /// concurrency while [`handler`](Self::handler) is running.
+    pub fn concurrency_control(&self) -> ConcurrencyControl {
+        ConcurrencyControl {
+            semaphore: self.semaphore.clone(),
+            max_concurrent: self.max_concurrent.clone(),
+        }
+    }
+
     pub async fn handler<
         Handler: Fn(EventData<'static>) -> HandlerResult + Send + Sync + 'static,
         // Should eventually add the Error bound once handler functions in the gifdex ingester work with it.
         HandlerErr: std::fmt::Debug, /* + Error */
         HandlerResult: std::future::Future<Output = Result<(), HandlerErr>> + Send,
+    >(
+        self,
+        handler: Handler,
+    ) {
+        self.handler_with_shutdown(handler, CancellationToken::new())
+            .await
+    }
+
+    /// Like [`handler`](Self::handler), but stops pulling new messages once the given
+    /// token is cancelled.
+    ///
+    /// In-flight handler tasks are drained to completion, their pending acks flushed,
+    /// and the websocket closed with a proper close frame before this returns.
+    pub async fn handler_with_shutdown<
+        Handler: Fn(EventData<'static>) -> HandlerResult + Send + Sync + 'static,
+        HandlerErr: std::fmt::Debug,
+        HandlerResult: std::future::Future<Output = Result<(), HandlerErr>> + Send,
     >(
         mut self,
         handler: Handler,
+        shutdown: CancellationToken,
     ) {
         let handler = Arc::new(handler);
         let mut tasks = JoinSet::new();
         loop {
             // Remove any finished tasks in the set and log a panic if needed.
             while let Some(result) = tasks.try_join_next() {
-                if let Err(err) = result {
-                    if err.is_panic() {
-                        log::error!("handler task panicked: {err:?}");
+                if let Err(err) = result
+                    && err.is_panic()
+                {
+                    log::error!("handler task panicked: {err:?}");
+                    if let Some(metrics) = &self.metrics {
+                        metrics.on_handler_error();
                     }
                 }
             }
-            let permit = match self.semaphore.clone().acquire_owned().await {
-                Ok(p) => p,
-                Err(_) => break,
+            // Log when the handler pool is saturated so operators can see
+            // whether ingest lag is concurrency-bound.
+            if self.semaphore.available_permits() == 0 {
+                log::debug!("handler concurrency saturated - waiting for a free permit");
+            }
+            let permit = tokio::select! {
+                _ = shutdown.cancelled() => {
+                    log::info!("shutdown requested - no longer pulling channel messages");
+                    break;
+                }
+                permit = self.semaphore.clone().acquire_owned() => match permit {
+                    Ok(p) => p,
+                    Err(_) => break,
+                },
             };
-            let message = match self.read.next().await {
-                Some(msg) => msg,
-                None => {
+            let message = tokio::select! {
+                _ = shutdown.cancelled() => {
+                    log::info!("shutdown requested - no longer pulling channel messages");
                     drop(permit);
                     break;
                 }
+                // The writer task pings on an interval, so a healthy
+                // connection always has traffic - a silent one is dead even
+                // if the read stream never returns None.
+                _ = tokio::time::sleep(self.idle_timeout) => {
+                    log::warn!(
+                        "no traffic received within {:?} - closing connection",
+                        self.idle_timeout
+                    );
+                    drop(permit);
+                    break;
+                }
+                message = self.read.next() => match message {
+                    Some(msg) => msg,
+                    None => {
+                        drop(permit);
+                        break;
+                    }
+                },
             };
             match message {
                 Ok(Message::Text(text)) => {
                     let ack_tx = self.ack_tx.clone();
                     let handler = handler.clone();
+                    let metrics = self.metrics.clone();
+                    let retain_raw_json = self.retain_raw_json;
+                    let collection_semaphores = self.collection_semaphores.clone();
+                    let pool = self.semaphore.clone();
+                    if let Some(metrics) = &metrics {
+                        metrics.on_event_received();
+                    }
                     tasks.spawn(async move {
-                        let event = match serde_json::from_str::<Event>(&text) {
+                        let mut event = match serde_json::from_str::<Event>(&text) {
                             Ok(e) => e.into_static(),
                             Err(err) => {
                                 log::warn!("failed to parse event: {err:?}");
+                                if let Some(metrics) = &metrics {
+                                    metrics.on_parse_error(&text);
+                                }
                                 drop(permit);
                                 return;
                             }
                         };
+                        if retain_raw_json {
+                            event.data.set_raw(text.to_string());
+                        }
+                        // Gate the handler on its collection's own permit when
+                        // a limit is configured, bounding how much of the pool
+                        // one collection's slow handlers can occupy. A task
+                        // that has to wait hands its pool permit back first,
+                        // so a backlog for one collection queues without
+                        // draining the pool and starving the others.
+                        let mut permit = Some(permit);
+                        let _collection_permit = match &event.data {
+                            EventData::Record { record, .. } => {
+                                match collection_semaphores.get(record.collection.as_str()) {
+                                    Some(semaphore) => match semaphore.clone().try_acquire_owned() {
+                                        Ok(collection_permit) => Some(collection_permit),
+                                        Err(_) => {
+                                            log::debug!(
+                                                "collection {} concurrency saturated - queueing without a pool permit",
+                                                record.collection.as_str()
+                                            );
+                                            permit = None;
+                                            match semaphore.clone().acquire_owned().await {
+                                                Ok(collection_permit) => Some(collection_permit),
+                                                Err(_) => return,
+                                            }
+                                        }
+                                    },
+                                    None => None,
+                                }
+                            }
+                            _ => None,
+                        };
+                        // Re-take a pool permit if it was handed back while
+                        // queued - the pool still bounds running handlers.
+                        let permit = match permit {
+                            Some(permit) => permit,
+                            None => match pool.acquire_owned().await {
+                                Ok(permit) => permit,
+                                Err(_) => return,
+                            },
+                        };
                         let result = handler(event.data).await;
                         if result.is_ok() {
-                            if let Err(err) = ack_tx.send(event.id) {
+                            if let Err(err) = ack_tx.send(WriterMessage::Ack(event.id)) {
                                 log::warn!("failed to queue ack for event {}: {err:?}", event.id);
+                            } else if let Some(metrics) = &metrics {
+                                metrics.on_event_acked();
                             }
                         } else if let Err(err) = result {
                             log::warn!("event {} handler failed: {err:?}", event.id);
+                            if let Some(metrics) = &metrics {
+                                metrics.on_handler_error();
+                            }
                         }
                         drop(permit);
                     });
                 }
+                Ok(Message::Ping(payload)) => {
+                    // Tungstenite queues an automatic pong, but it is only
+                    // flushed alongside the next write - respond explicitly so
+                    // liveness doesn't depend on ack traffic.
+                    if self.ack_tx.send(WriterMessage::Pong(payload)).is_err() {
+                        log::warn!("failed to queue pong response");
+                    }
+                    drop(permit);
+                }
                 Ok(Message::Close(_)) => {
                     log::info!("websocket closed");
                     drop(permit);
@@ -156,9 +380,12 @@ impl ChannelConnectionHandle {
 
         // Wait for all currently running handler tasks to run to completion.
         while let Some(result) = tasks.join_next().await {
-            if let Err(err) = result {
-                if err.is_panic() {
-                    log::error!("handler task panicked: {err:?}");
+            if let Err(err) = result
+                && err.is_panic()
+            {
+                log::error!("handler task panicked: {err:?}");
+                if let Some(metrics) = &self.metrics {
+                    metrics.on_handler_error();
                 }
             }
         }
@@ -171,32 +398,216 @@ impl ChannelConnectionHandle {
             >,
             Message,
         >,
-        mut ack_rx: mpsc::UnboundedReceiver<u64>,
+        mut ack_rx: mpsc::UnboundedReceiver<WriterMessage>,
+        cursor_store: Option<Arc<dyn CursorStore>>,
+        ping_interval: Duration,
+        ack_strategy: AckStrategy,
+        metrics: Option<Arc<dyn ChannelMetrics>>,
     ) {
+        let mut pending: Vec<u64> = Vec::new();
+        let mut highest_acked: Option<u64> = None;
+        let mut acks_since_save = 0u64;
+        let mut last_save = Instant::now();
+        let mut ping_timer = tokio::time::interval_at(
+            tokio::time::Instant::now() + ping_interval,
+            ping_interval,
+        );
+        // Only the windowed strategy flushes on a timer. The interval still
+        // needs some period for the select arm to exist, so the others get a
+        // long one and the arm is guarded off.
+        let flush_window = match ack_strategy {
+            AckStrategy::Windowed { window } => window,
+            _ => Duration::from_secs(3600),
+        };
+        let mut flush_timer = tokio::time::interval_at(
+            tokio::time::Instant::now() + flush_window,
+            flush_window,
+        );
+        loop {
+            let flush = tokio::select! {
+                message = ack_rx.recv() => match message {
+                    Some(WriterMessage::Ack(id)) => {
+                        pending.push(id);
+                        match ack_strategy {
+                            AckStrategy::Immediate => true,
+                            AckStrategy::Batched { max_ids } => pending.len() >= max_ids.get(),
+                            AckStrategy::Windowed { .. } => false,
+                        }
+                    }
+                    Some(WriterMessage::Pong(payload)) => {
+                        if let Err(err) = write.send(Message::Pong(payload)).await {
+                            log::warn!("failed to send pong: {err:?}");
+                            break;
+                        }
+                        continue;
+                    }
+                    None => break,
+                },
+                // Proactively ping on an interval so a silently dead
+                // connection trips the reader's idle timeout instead of
+                // hanging indefinitely.
+                _ = ping_timer.tick() => {
+                    if let Err(err) = write.send(Message::Ping(Default::default())).await {
+                        log::warn!("failed to send keepalive ping: {err:?}");
+                        break;
+                    }
+                    continue;
+                }
+                _ = flush_timer.tick(),
+                    if matches!(ack_strategy, AckStrategy::Windowed { .. }) =>
+                {
+                    !pending.is_empty()
+                }
+            };
+            if !flush {
+                continue;
+            }
+            if !Self::flush_acks(&mut write, &pending, ack_strategy, &metrics).await {
+                break;
+            }
+
+            // Periodically persist the highest acked event id.
+            if let Some(store) = &cursor_store {
+                highest_acked = Some(
+                    pending
+                        .iter()
+                        .copied()
+                        .fold(highest_acked.unwrap_or(0), u64::max),
+                );
+                acks_since_save += pending.len() as u64;
+                if acks_since_save >= CURSOR_SAVE_ACK_COUNT
+                    || last_save.elapsed() >= CURSOR_SAVE_INTERVAL
+                {
+                    store.save(highest_acked.unwrap());
+                    acks_since_save = 0;
+                    last_save = Instant::now();
+                }
+            }
+            pending.clear();
+        }
+
+        // Flush whatever a coalescing strategy still had accumulated, so a
+        // clean shutdown never drops acks for handled events.
+        if !pending.is_empty() {
+            highest_acked = Some(
+                pending
+                    .iter()
+                    .copied()
+                    .fold(highest_acked.unwrap_or(0), u64::max),
+            );
+            Self::flush_acks(&mut write, &pending, ack_strategy, &metrics).await;
+        }
+
+        // Persist the final cursor position before the task exits.
+        if let Some(store) = &cursor_store
+            && let Some(id) = highest_acked
+        {
+            store.save(id);
+        }
+
+        // All pending acks have been flushed - close the socket cleanly.
+        if let Err(err) = write.send(Message::Close(None)).await {
+            log::debug!("failed to send websocket close frame: {err:?}");
+        }
+    }
+
+    /// Send the pending ack ids in the wire format the strategy calls for.
+    /// Returns `false` when the write failed and the task should stop.
+    async fn flush_acks(
+        write: &mut futures_util::stream::SplitSink<
+            tokio_tungstenite::WebSocketStream<
+                tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>,
+            >,
+            Message,
+        >,
+        pending: &[u64],
+        ack_strategy: AckStrategy,
+        metrics: &Option<Arc<dyn ChannelMetrics>>,
+    ) -> bool {
         #[derive(Serialize)]
         struct Ack {
             #[serde(rename = "type")]
             type_: &'static str,
             id: u64,
         }
-        while let Some(id) = ack_rx.recv().await {
-            let msg = Ack { type_: "ack", id };
-            let json = match serde_json::to_string(&msg) {
-                Ok(json) => json,
-                Err(err) => {
-                    log::warn!("failed to serialize ack: {err:?}");
-                    continue;
-                }
-            };
-
-            if let Err(err) = write.send(Message::Text(json.into())).await {
-                log::warn!("failed to send ack: {err:?}");
-                break;
+        #[derive(Serialize)]
+        struct BatchedAck<'a> {
+            #[serde(rename = "type")]
+            type_: &'static str,
+            ids: &'a [u64],
+        }
+        let json = match ack_strategy {
+            AckStrategy::Immediate => serde_json::to_string(&Ack {
+                type_: "ack",
+                id: pending[0],
+            }),
+            AckStrategy::Batched { .. } | AckStrategy::Windowed { .. } => {
+                serde_json::to_string(&BatchedAck {
+                    type_: "ack",
+                    ids: pending,
+                })
+            }
+        };
+        let json = match json {
+            Ok(json) => json,
+            Err(err) => {
+                log::warn!("failed to serialize ack: {err:?}");
+                return true;
             }
+        };
+        if let Err(err) = write.send(Message::Text(json.into())).await {
+            log::warn!("failed to send ack: {err:?}");
+            return false;
+        }
+        if let Some(metrics) = metrics {
+            metrics.on_ack_frame_sent(pending.len());
+        }
+        true
+    }
+}
+
+/// A clonable handle for observing and resizing a connection's handler
+/// concurrency at runtime.
+#[derive(Debug, Clone)]
+pub struct ConcurrencyControl {
+    semaphore: Arc<Semaphore>,
+    max_concurrent: Arc<AtomicUsize>,
+}
+
+impl ConcurrencyControl {
+    /// Permits not currently held by an in-flight handler task.
+    pub fn available_permits(&self) -> usize {
+        self.semaphore.available_permits()
+    }
+
+    /// Handler tasks currently holding a permit.
+    pub fn in_flight(&self) -> usize {
+        self.max_concurrent
+            .load(Ordering::Relaxed)
+            .saturating_sub(self.semaphore.available_permits())
+    }
+
+    /// Resize the concurrency limit.
+    ///
+    /// Growing takes effect immediately. Shrinking is best-effort: permits
+    /// held by in-flight handler tasks are only retired as they become
+    /// available again.
+    pub fn resize(&self, max_concurrent: NonZero<usize>) {
+        let old = self
+            .max_concurrent
+            .swap(max_concurrent.get(), Ordering::Relaxed);
+        let new = max_concurrent.get();
+        if new > old {
+            self.semaphore.add_permits(new - old);
+        } else {
+            self.semaphore.forget_permits(old - new);
         }
     }
 }
 
+/// How long a connection must stay up before the reconnect failure counter is reset.
+const BACKOFF_RESET_THRESHOLD: Duration = Duration::from_secs(60);
+
 /// Configuration for a channel connection
 #[derive(Debug, Clone)]
 #[non_exhaustive]
@@ -205,6 +616,18 @@ pub struct Channel {
     base_url: Url,
     auth_header: Option<HeaderValue>,
     max_concurrent: NonZero<usize>,
+    backoff_base: Duration,
+    backoff_max: Duration,
+    cursor_store: Option<Arc<dyn CursorStore>>,
+    metrics: Option<Arc<dyn ChannelMetrics>>,
+    collections: Vec<Nsid<'static>>,
+    collection_limits: HashMap<String, NonZero<usize>>,
+    ping_interval: Duration,
+    idle_timeout: Duration,
+    retain_raw_json: bool,
+    ack_strategy: AckStrategy,
+    reconnect_attempts: Arc<AtomicU32>,
+    last_connected: Arc<Mutex<Option<Instant>>>,
 }
 
 impl Channel {
@@ -213,14 +636,65 @@ impl Channel {
         ChannelBuilder::new(base_url)
     }
 
+    /// Attach a metrics hook that is notified of channel connection activity
+    pub fn with_metrics<M: ChannelMetrics + 'static>(mut self, metrics: M) -> Self {
+        self.metrics = Some(Arc::new(metrics));
+        self
+    }
+
     /// Connect to the channel and return a ChannelReceiver
     pub async fn connect(&self) -> Result<ChannelConnectionHandle, ConnectionError> {
-        ChannelConnectionHandle::connect(
-            self.base_url.clone(),
-            self.auth_header.clone(),
-            self.max_concurrent,
-        )
-        .await
+        ChannelConnectionHandle::connect(self).await
+    }
+
+    /// Connect to the channel, retrying with exponential backoff and jitter until a
+    /// connection is established.
+    ///
+    /// The failure counter persists across calls so that repeated short-lived connections
+    /// keep backing off; it is reset once a connection has stayed up for a while.
+    pub async fn connect_with_retry(&self) -> ChannelConnectionHandle {
+        // Reset the failure counter if the previous connection stayed up long enough.
+        if let Some(connected_at) = *self.last_connected.lock().unwrap()
+            && connected_at.elapsed() >= BACKOFF_RESET_THRESHOLD
+        {
+            self.reconnect_attempts.store(0, Ordering::Relaxed);
+        }
+
+        loop {
+            match self.connect().await {
+                Ok(handle) => {
+                    *self.last_connected.lock().unwrap() = Some(Instant::now());
+                    return handle;
+                }
+                Err(err) => {
+                    if let Some(metrics) = &self.metrics {
+                        metrics.on_reconnect();
+                    }
+                    let attempt = self.reconnect_attempts.fetch_add(1, Ordering::Relaxed);
+                    let delay = self.backoff_delay(attempt);
+                    log::warn!(
+                        "failed to connect to channel (attempt {}): {err:?} - retrying in {delay:?}",
+                        attempt + 1
+                    );
+                    tokio::time::sleep(delay).await;
+                }
+            }
+        }
+    }
+
+    /// The number of consecutive failed connection attempts so far.
+    pub fn reconnect_attempts(&self) -> u32 {
+        self.reconnect_attempts.load(Ordering::Relaxed)
+    }
+
+    /// Compute the delay before the given (zero-indexed) reconnection attempt as
+    /// `min(max, base * 2^attempt)` plus a random jitter of up to 25%.
+    fn backoff_delay(&self, attempt: u32) -> Duration {
+        let exponential = self
+            .backoff_base
+            .saturating_mul(2u32.saturating_pow(attempt.min(16)));
+        let delay = exponential.min(self.backoff_max);
+        delay + delay.mul_f64(rand::rng().random_range(0.0..0.25))
     }
 }
 
@@ -232,6 +706,15 @@ pub struct ChannelBuilder {
     base_url: Url,
     password: Option<String>,
     max_concurrent: NonZero<usize>,
+    backoff_base: Duration,
+    backoff_max: Duration,
+    cursor_store: Option<Arc<dyn CursorStore>>,
+    collections: Vec<Nsid<'static>>,
+    collection_limits: HashMap<String, NonZero<usize>>,
+    ping_interval: Duration,
+    idle_timeout: Duration,
+    retain_raw_json: bool,
+    ack_strategy: AckStrategy,
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -249,6 +732,15 @@ impl ChannelBuilder {
             base_url,
             password: None,
             max_concurrent: NonZero::new(100).unwrap(),
+            backoff_base: Duration::from_secs(5),
+            backoff_max: Duration::from_secs(300),
+            cursor_store: None,
+            collections: Vec::new(),
+            collection_limits: HashMap::new(),
+            ping_interval: Duration::from_secs(30),
+            idle_timeout: Duration::from_secs(90),
+            retain_raw_json: false,
+            ack_strategy: AckStrategy::Immediate,
         }
     }
 
@@ -264,6 +756,72 @@ impl ChannelBuilder {
         self
     }
 
+    /// Limit how many events from a single collection may be handled
+    /// concurrently, on top of the overall [`max_concurrent`](Self::max_concurrent)
+    /// pool.
+    ///
+    /// A burst of events for a collection with a slow handler can otherwise
+    /// occupy the whole pool and starve cheap events from other collections.
+    /// Events over the limit queue without holding a pool permit, so a
+    /// backlog for one collection never blocks the others.
+    pub fn collection_concurrency(mut self, collection: &Nsid<'_>, max: NonZero<usize>) -> Self {
+        self.collection_limits
+            .insert(collection.as_str().to_owned(), max);
+        self
+    }
+
+    /// Set the base and maximum delay used when reconnecting with [`Channel::connect_with_retry`]
+    pub fn reconnect_backoff(mut self, base: Duration, max: Duration) -> Self {
+        self.backoff_base = base;
+        self.backoff_max = max;
+        self
+    }
+
+    /// Set how often keepalive pings are sent and how long a connection may go
+    /// without any traffic before it is closed and reconnected.
+    pub fn keepalive(mut self, ping_interval: Duration, idle_timeout: Duration) -> Self {
+        self.ping_interval = ping_interval;
+        self.idle_timeout = idle_timeout;
+        self
+    }
+
+    /// Set how successful events are acknowledged back to the server.
+    ///
+    /// The coalescing strategies only work against servers that accept
+    /// batched `{"type": "ack", "ids": [...]}` frames - they are opt-in for
+    /// that reason.
+    pub fn ack_strategy(mut self, strategy: AckStrategy) -> Self {
+        self.ack_strategy = strategy;
+        self
+    }
+
+    /// Keep the original JSON text of each event alongside the parsed data,
+    /// available to handlers through [`EventData::raw`]. Off by default since
+    /// it copies every event's text.
+    pub fn retain_raw_json(mut self, retain: bool) -> Self {
+        self.retain_raw_json = retain;
+        self
+    }
+
+    /// Set a store used to persist and resume the channel cursor position across restarts
+    pub fn cursor_store<S: CursorStore + 'static>(mut self, store: S) -> Self {
+        self.cursor_store = Some(Arc::new(store));
+        self
+    }
+
+    /// Restrict the channel to records from the given collections.
+    ///
+    /// The NSIDs are sent to the server as part of the `/channel` request so filtering
+    /// happens server-side. Servers without filtering support will still forward all
+    /// records from their tracked repositories.
+    pub fn collections(mut self, collections: &[Nsid<'_>]) -> Self {
+        self.collections = collections
+            .iter()
+            .map(|nsid| nsid.clone().into_static())
+            .collect();
+        self
+    }
+
     /// Build and validate the channel configuration
     pub fn build(self) -> Result<Channel, ChannelBuildError> {
         // Validate the URL scheme
@@ -291,6 +849,18 @@ impl ChannelBuilder {
             base_url: self.base_url,
             auth_header,
             max_concurrent: self.max_concurrent,
+            backoff_base: self.backoff_base,
+            backoff_max: self.backoff_max,
+            cursor_store: self.cursor_store,
+            metrics: None,
+            collections: self.collections,
+            collection_limits: self.collection_limits,
+            ping_interval: self.ping_interval,
+            idle_timeout: self.idle_timeout,
+            retain_raw_json: self.retain_raw_json,
+            ack_strategy: self.ack_strategy,
+            reconnect_attempts: Arc::new(AtomicU32::new(0)),
+            last_connected: Arc::new(Mutex::new(None)),
         })
     }
 }
@@ -6,6 +6,7 @@ use crate::{
     channel::{Channel, ChannelBuilder},
 };
 use base64::Engine;
+use futures_util::{StreamExt, stream};
 use jacquard_common::{
     IntoStatic,
     types::{did::Did, did_doc::DidDocument},
@@ -14,8 +15,13 @@ use reqwest::{
     Response,
     header::{AUTHORIZATION, HeaderMap, HeaderValue, InvalidHeaderValue},
 };
-use serde::Serialize;
-use std::time::Duration;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    num::NonZero,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
 use url::Url;
 
 #[derive(Debug, Clone)]
@@ -24,6 +30,49 @@ pub struct TapClient {
     http_client: reqwest::Client,
     base_url: Url,
     password: Option<String>,
+    resolve_concurrency: NonZero<usize>,
+    resolve_cache: Option<Arc<ResolveCache>>,
+}
+
+/// An in-memory LRU cache of resolved DID documents with a TTL.
+struct ResolveCache {
+    entries: Mutex<lru::LruCache<Did<'static>, (Instant, DidDocument<'static>)>>,
+    ttl: Duration,
+}
+
+impl ResolveCache {
+    fn new(capacity: NonZero<usize>, ttl: Duration) -> Self {
+        Self {
+            entries: Mutex::new(lru::LruCache::new(capacity)),
+            ttl,
+        }
+    }
+
+    fn get(&self, did: &Did<'_>) -> Option<DidDocument<'static>> {
+        let did = did.clone().into_static();
+        let mut entries = self.entries.lock().unwrap();
+        match entries.get(&did) {
+            Some((resolved_at, document)) if resolved_at.elapsed() < self.ttl => {
+                Some(document.clone())
+            }
+            // Stale entries are left in place to be overwritten by the refresh -
+            // evicting them here would just churn the LRU order.
+            _ => None,
+        }
+    }
+
+    fn insert(&self, did: Did<'static>, document: DidDocument<'static>) {
+        let mut entries = self.entries.lock().unwrap();
+        entries.put(did, (Instant::now(), document));
+    }
+}
+
+impl std::fmt::Debug for ResolveCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ResolveCache")
+            .field("ttl", &self.ttl)
+            .finish_non_exhaustive()
+    }
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -31,12 +80,58 @@ pub struct TapClient {
 pub enum TapRequestError {
     #[error("network request failed due to: {0}")]
     NetworkError(#[from] reqwest::Error),
-    #[error("tap responded with an error: {0:?}")]
-    ErrorResponse(Response),
+    #[error("tap responded with an error: {0}")]
+    ErrorResponse(TapErrorResponse),
     #[error("server responded with an invalid response. failed to deserialise due to {0}")]
     InvalidResponseBody(#[from] serde_json::Error),
 }
 
+/// A structured error response from the tap server with the body eagerly read.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct TapErrorResponse {
+    pub status: reqwest::StatusCode,
+    pub body: String,
+    /// The `error` field if the body was a JSON error envelope.
+    pub error: Option<String>,
+    /// The `message` field if the body was a JSON error envelope.
+    pub message: Option<String>,
+}
+
+impl TapErrorResponse {
+    async fn from_response(response: Response) -> Self {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        #[derive(Deserialize)]
+        struct ErrorEnvelope {
+            error: Option<String>,
+            message: Option<String>,
+        }
+        let (error, message) = serde_json::from_str::<ErrorEnvelope>(&body)
+            .map(|envelope| (envelope.error, envelope.message))
+            .unwrap_or((None, None));
+        Self {
+            status,
+            body,
+            error,
+            message,
+        }
+    }
+}
+
+impl std::fmt::Display for TapErrorResponse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.status)?;
+        match (&self.error, &self.message) {
+            (Some(error), Some(message)) => write!(f, " {error}: {message}"),
+            (Some(error), None) => write!(f, " {error}"),
+            (None, Some(message)) => write!(f, " {message}"),
+            (None, None) if !self.body.is_empty() => write!(f, " {}", self.body),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
 impl TapClient {
     pub fn new(base_url: Url) -> Result<Self, TapClientBuildError> {
         Self::builder(base_url).build()
@@ -46,6 +141,8 @@ impl TapClient {
         TapClientBuilder {
             base_url,
             password: None,
+            resolve_concurrency: NonZero::new(8).unwrap(),
+            resolve_cache: None,
         }
     }
 
@@ -63,7 +160,9 @@ impl TapClient {
             .send()
             .await?;
         if !response.status().is_success() {
-            return Err(TapRequestError::ErrorResponse(response));
+            return Err(TapRequestError::ErrorResponse(
+                TapErrorResponse::from_response(response).await,
+            ));
         }
         Ok(())
     }
@@ -72,6 +171,12 @@ impl TapClient {
         &self,
         did: &Did<'_>,
     ) -> Result<DidDocument<'static>, TapRequestError> {
+        if let Some(ref cache) = self.resolve_cache
+            && let Some(document) = cache.get(did)
+        {
+            log::debug!("resolved {did} from cache");
+            return Ok(document);
+        }
         log::debug!("resolving {did}");
         let response = self
             .http_client
@@ -81,11 +186,39 @@ impl TapClient {
             .send()
             .await?;
         if !response.status().is_success() {
-            return Err(TapRequestError::ErrorResponse(response));
+            return Err(TapRequestError::ErrorResponse(
+                TapErrorResponse::from_response(response).await,
+            ));
         }
         let bytes = response.bytes().await?;
         let data: DidDocument = serde_json::from_slice(&bytes)?;
-        Ok(data.into_static())
+        let document = data.into_static();
+        if let Some(ref cache) = self.resolve_cache {
+            cache.insert(did.clone().into_static(), document.clone());
+        }
+        Ok(document)
+    }
+
+    /// Resolve a batch of DIDs concurrently, returning a per-DID result.
+    ///
+    /// Resolution is fanned out with a bounded concurrency (configurable via
+    /// [`TapClientBuilder::resolve_concurrency`]) so a single failing DID doesn't
+    /// sink the whole batch.
+    pub async fn resolve_dids(
+        &self,
+        dids: &[Did<'_>],
+    ) -> HashMap<Did<'static>, Result<DidDocument<'static>, TapRequestError>> {
+        log::debug!("resolving {} dids", dids.len());
+        stream::iter(dids.iter().map(|did| {
+            let did = did.clone().into_static();
+            async move {
+                let result = self.resolve_did(&did).await;
+                (did, result)
+            }
+        }))
+        .buffer_unordered(self.resolve_concurrency.get())
+        .collect()
+        .await
     }
 
     pub async fn repo_info(&self, did: &Did<'_>) -> Result<RepoInfo<'static>, TapRequestError> {
@@ -98,7 +231,9 @@ impl TapClient {
             .send()
             .await?;
         if !response.status().is_success() {
-            return Err(TapRequestError::ErrorResponse(response));
+            return Err(TapRequestError::ErrorResponse(
+                TapErrorResponse::from_response(response).await,
+            ));
         }
         let bytes = response.bytes().await?;
         let data: RepoInfo = serde_json::from_slice(&bytes)?;
@@ -121,7 +256,9 @@ impl TapClient {
             .send()
             .await?;
         if !response.status().is_success() {
-            return Err(TapRequestError::ErrorResponse(response));
+            return Err(TapRequestError::ErrorResponse(
+                TapErrorResponse::from_response(response).await,
+            ));
         }
         Ok(())
     }
@@ -142,7 +279,34 @@ impl TapClient {
             .send()
             .await?;
         if !response.status().is_success() {
-            return Err(TapRequestError::ErrorResponse(response));
+            return Err(TapRequestError::ErrorResponse(
+                TapErrorResponse::from_response(response).await,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Ask tap to resync the given repositories, replaying every record they
+    /// already contain as fresh events on the channel.
+    pub async fn resync_repos(&self, dids: &[Did<'_>]) -> Result<(), TapRequestError> {
+        log::debug!("requesting resync of {dids:?}");
+        #[derive(Serialize)]
+        struct Payload<'a> {
+            dids: &'a [Did<'a>],
+        }
+        let payload = Payload { dids };
+        let response = self
+            .http_client
+            .post(self.base_url.join("/repos/resync").expect(
+                "constructing the endpoint url from the base url should always be possible",
+            ))
+            .json(&payload)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(TapRequestError::ErrorResponse(
+                TapErrorResponse::from_response(response).await,
+            ));
         }
         Ok(())
     }
@@ -157,7 +321,9 @@ impl TapClient {
             .send()
             .await?;
         if !response.status().is_success() {
-            return Err(TapRequestError::ErrorResponse(response));
+            return Err(TapRequestError::ErrorResponse(
+                TapErrorResponse::from_response(response).await,
+            ));
         }
         let data = response.json::<RepoCountResponse>().await?;
         Ok(data)
@@ -173,7 +339,9 @@ impl TapClient {
             .send()
             .await?;
         if !response.status().is_success() {
-            return Err(TapRequestError::ErrorResponse(response));
+            return Err(TapRequestError::ErrorResponse(
+                TapErrorResponse::from_response(response).await,
+            ));
         }
         let data = response.json::<RecordCountResponse>().await?;
         Ok(data)
@@ -189,7 +357,9 @@ impl TapClient {
             .send()
             .await?;
         if !response.status().is_success() {
-            return Err(TapRequestError::ErrorResponse(response));
+            return Err(TapRequestError::ErrorResponse(
+                TapErrorResponse::from_response(response).await,
+            ));
         }
         let data = response.json::<OutboxBufferResponse>().await?;
         Ok(data)
@@ -205,7 +375,9 @@ impl TapClient {
             .send()
             .await?;
         if !response.status().is_success() {
-            return Err(TapRequestError::ErrorResponse(response));
+            return Err(TapRequestError::ErrorResponse(
+                TapErrorResponse::from_response(response).await,
+            ));
         }
         let data = response.json::<ResyncBufferResponse>().await?;
         Ok(data)
@@ -219,12 +391,36 @@ impl TapClient {
             .expect("constructing the endpoint url from the base url should always be possible");
         let response = self.http_client.get(url).send().await?;
         if !response.status().is_success() {
-            return Err(TapRequestError::ErrorResponse(response));
+            return Err(TapRequestError::ErrorResponse(
+                TapErrorResponse::from_response(response).await,
+            ));
         }
         let data = response.json::<CursorsResponse>().await?;
         Ok(data)
     }
 
+    /// Fetch all of tap's stats endpoints concurrently.
+    ///
+    /// Each sub-request's result is returned individually so a single failure
+    /// doesn't sink the whole poll - callers can use whichever succeeded.
+    pub async fn stats(&self) -> TapStats {
+        log::debug!("fetching all tap stats");
+        let (repo_count, record_count, outbox_buffer, resync_buffer, cursors) = tokio::join!(
+            self.repo_count(),
+            self.record_count(),
+            self.outbox_buffer(),
+            self.resync_buffer(),
+            self.cursors(),
+        );
+        TapStats {
+            repo_count,
+            record_count,
+            outbox_buffer,
+            resync_buffer,
+            cursors,
+        }
+    }
+
     /// Create a channel for connecting to the event stream
     ///
     /// Note: This carries the password from the TapClient if one was set.
@@ -233,7 +429,7 @@ impl TapClient {
         match url.scheme() {
             "http" => url.set_scheme("ws").unwrap(),
             "https" => url.set_scheme("wss").unwrap(),
-            scheme @ _ => unreachable!("invalid scheme {scheme} in channel call."),
+            scheme => unreachable!("invalid scheme {scheme} in channel call."),
         }
         let mut builder = Channel::builder(url);
         if let Some(ref password) = self.password {
@@ -243,12 +439,25 @@ impl TapClient {
     }
 }
 
+/// Aggregated results of all of tap's stats endpoints, as returned by
+/// [`TapClient::stats`].
+#[derive(Debug)]
+pub struct TapStats {
+    pub repo_count: Result<RepoCountResponse, TapRequestError>,
+    pub record_count: Result<RecordCountResponse, TapRequestError>,
+    pub outbox_buffer: Result<OutboxBufferResponse, TapRequestError>,
+    pub resync_buffer: Result<ResyncBufferResponse, TapRequestError>,
+    pub cursors: Result<CursorsResponse, TapRequestError>,
+}
+
 #[derive(Debug, Clone)]
 #[non_exhaustive]
 #[must_use]
 pub struct TapClientBuilder {
     base_url: Url,
     password: Option<String>,
+    resolve_concurrency: NonZero<usize>,
+    resolve_cache: Option<Arc<ResolveCache>>,
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -267,6 +476,22 @@ impl TapClientBuilder {
         self
     }
 
+    /// Set the maximum number of concurrent requests made by [`TapClient::resolve_dids`]
+    pub fn resolve_concurrency(mut self, max: NonZero<usize>) -> Self {
+        self.resolve_concurrency = max;
+        self
+    }
+
+    /// Cache resolved DID documents in memory, keyed by DID.
+    ///
+    /// A cache hit skips the network entirely; entries older than `ttl` are
+    /// refreshed lazily on the next lookup. The cache holds at most `capacity`
+    /// documents and evicts the least recently used entry when full.
+    pub fn resolve_cache(mut self, capacity: NonZero<usize>, ttl: Duration) -> Self {
+        self.resolve_cache = Some(Arc::new(ResolveCache::new(capacity, ttl)));
+        self
+    }
+
     pub fn build(self) -> Result<TapClient, TapClientBuildError> {
         if !matches!(self.base_url.scheme(), "http" | "https") {
             return Err(TapClientBuildError::InvalidUrlScheme(
@@ -281,7 +506,7 @@ impl TapClientBuilder {
             let encoded =
                 base64::engine::general_purpose::STANDARD.encode(format!("admin:{password}"));
             let auth_value = HeaderValue::from_str(&format!("Basic {encoded}"))
-                .map_err(|err| TapClientBuildError::InvalidPassword(err))?;
+                .map_err(TapClientBuildError::InvalidPassword)?;
             headers.insert(AUTHORIZATION, auth_value);
         }
 
@@ -299,6 +524,8 @@ impl TapClientBuilder {
             http_client,
             base_url: self.base_url,
             password: self.password,
+            resolve_concurrency: self.resolve_concurrency,
+            resolve_cache: self.resolve_cache,
         })
     }
 }
@@ -0,0 +1,66 @@
+use std::path::PathBuf;
+
+/// Persistent storage for the last acknowledged channel event id.
+///
+/// When attached to a channel via
+/// [`ChannelBuilder::cursor_store`](crate::channel::ChannelBuilder::cursor_store) the stored
+/// cursor is sent as a query parameter when the websocket is opened, letting the server resume
+/// the stream from where the consumer left off.
+pub trait CursorStore: std::fmt::Debug + Send + Sync {
+    /// Load the last persisted cursor position, if any.
+    fn load(&self) -> Option<u64>;
+
+    /// Persist the given cursor position.
+    fn save(&self, id: u64);
+}
+
+/// A [`CursorStore`] that persists the cursor position to a file on disk.
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct FileCursorStore {
+    path: PathBuf,
+}
+
+impl FileCursorStore {
+    /// Create a new file cursor store backed by the given path
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl CursorStore for FileCursorStore {
+    fn load(&self) -> Option<u64> {
+        let contents = match std::fs::read_to_string(&self.path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return None,
+            Err(err) => {
+                log::warn!("failed to read cursor file {}: {err:?}", self.path.display());
+                return None;
+            }
+        };
+        match contents.trim().parse() {
+            Ok(id) => Some(id),
+            Err(err) => {
+                log::warn!(
+                    "invalid cursor file contents in {}: {err:?}",
+                    self.path.display()
+                );
+                None
+            }
+        }
+    }
+
+    fn save(&self, id: u64) {
+        // Write-then-rename so the cursor file is replaced atomically - a
+        // crash mid-write must not leave a truncated file behind, or the next
+        // connect would silently resume without a cursor.
+        let temp_path = self.path.with_extension("tmp");
+        if let Err(err) = std::fs::write(&temp_path, id.to_string()) {
+            log::warn!("failed to write cursor file {}: {err:?}", temp_path.display());
+            return;
+        }
+        if let Err(err) = std::fs::rename(&temp_path, &self.path) {
+            log::warn!("failed to replace cursor file {}: {err:?}", self.path.display());
+        }
+    }
+}
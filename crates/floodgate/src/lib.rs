@@ -1,7 +1,11 @@
 pub mod api;
 pub mod channel;
 pub mod client;
+pub mod cursor;
+pub mod metrics;
+pub mod registry;
 pub mod extern_types {
     pub use jacquard_common::types::*;
+    pub use tokio_util::sync::CancellationToken;
     pub use url::Url;
 }
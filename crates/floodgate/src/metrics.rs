@@ -0,0 +1,113 @@
+/// Observability callbacks for channel connection activity.
+///
+/// Attach an implementation with [`Channel::with_metrics`](crate::channel::Channel::with_metrics)
+/// to instrument the handler loop without wrapping it in every binary. All callbacks have empty
+/// default implementations so implementors only need to override the ones they care about.
+pub trait ChannelMetrics: std::fmt::Debug + Send + Sync {
+    /// Called when an event is received from the channel
+    fn on_event_received(&self) {}
+
+    /// Called when an event has been handled successfully and its ack queued
+    fn on_event_acked(&self) {}
+
+    /// Called when an ack frame is written to the socket, with the number of
+    /// event ids it carried. Together with [`on_event_acked`](Self::on_event_acked)
+    /// this shows how much frame coalescing an [`AckStrategy`](crate::channel::AckStrategy)
+    /// is buying
+    fn on_ack_frame_sent(&self, _ids: usize) {}
+
+    /// Called when a handler returns an error or panics
+    fn on_handler_error(&self) {}
+
+    /// Called with the raw text of an event that failed to parse, so
+    /// implementations can capture the exact bytes for later inspection
+    fn on_parse_error(&self, _raw: &str) {}
+
+    /// Called when a reconnection attempt is made by [`Channel::connect_with_retry`](crate::channel::Channel::connect_with_retry)
+    fn on_reconnect(&self) {}
+}
+
+/// A [`ChannelMetrics`] implementation backed by counters in a [`prometheus::Registry`].
+#[cfg(feature = "prometheus")]
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct PrometheusChannelMetrics {
+    events_received: prometheus::IntCounter,
+    events_acked: prometheus::IntCounter,
+    ack_frames: prometheus::IntCounter,
+    handler_errors: prometheus::IntCounter,
+    parse_errors: prometheus::IntCounter,
+    reconnects: prometheus::IntCounter,
+}
+
+#[cfg(feature = "prometheus")]
+impl PrometheusChannelMetrics {
+    /// Create channel metrics counters and register them with the given registry
+    pub fn new(registry: &prometheus::Registry) -> Result<Self, prometheus::Error> {
+        let events_received = prometheus::IntCounter::new(
+            "floodgate_events_received_total",
+            "Total number of events received from the channel",
+        )?;
+        let events_acked = prometheus::IntCounter::new(
+            "floodgate_events_acked_total",
+            "Total number of events successfully handled and acked",
+        )?;
+        let ack_frames = prometheus::IntCounter::new(
+            "floodgate_ack_frames_total",
+            "Total number of ack frames written to the websocket",
+        )?;
+        let handler_errors = prometheus::IntCounter::new(
+            "floodgate_handler_errors_total",
+            "Total number of events whose handler failed or panicked",
+        )?;
+        let parse_errors = prometheus::IntCounter::new(
+            "floodgate_parse_errors_total",
+            "Total number of events that failed to parse",
+        )?;
+        let reconnects = prometheus::IntCounter::new(
+            "floodgate_reconnects_total",
+            "Total number of channel reconnection attempts",
+        )?;
+        registry.register(Box::new(events_received.clone()))?;
+        registry.register(Box::new(events_acked.clone()))?;
+        registry.register(Box::new(ack_frames.clone()))?;
+        registry.register(Box::new(handler_errors.clone()))?;
+        registry.register(Box::new(parse_errors.clone()))?;
+        registry.register(Box::new(reconnects.clone()))?;
+        Ok(Self {
+            events_received,
+            events_acked,
+            ack_frames,
+            handler_errors,
+            parse_errors,
+            reconnects,
+        })
+    }
+}
+
+#[cfg(feature = "prometheus")]
+impl ChannelMetrics for PrometheusChannelMetrics {
+    fn on_event_received(&self) {
+        self.events_received.inc();
+    }
+
+    fn on_event_acked(&self) {
+        self.events_acked.inc();
+    }
+
+    fn on_ack_frame_sent(&self, _ids: usize) {
+        self.ack_frames.inc();
+    }
+
+    fn on_handler_error(&self) {
+        self.handler_errors.inc();
+    }
+
+    fn on_parse_error(&self, _raw: &str) {
+        self.parse_errors.inc();
+    }
+
+    fn on_reconnect(&self) {
+        self.reconnects.inc();
+    }
+}
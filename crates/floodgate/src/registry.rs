@@ -0,0 +1,194 @@
+use crate::api::{EventData, IdentityEventData, RecordAction, RecordEventData};
+use jacquard_common::{IntoStatic, types::collection::Collection, xrpc::XrpcResp};
+use serde::Deserialize;
+use std::{collections::HashMap, future::Future, pin::Pin, sync::Arc};
+
+type BoxFuture<T> = Pin<Box<dyn Future<Output = T> + Send>>;
+type RecordHandlerFn<S, E> =
+    Arc<dyn Fn(Arc<S>, RecordEventData<'static>) -> BoxFuture<Result<(), DispatchError<E>>> + Send + Sync>;
+type IdentityHandlerFn<S, E> =
+    Arc<dyn Fn(Arc<S>, IdentityEventData<'static>) -> BoxFuture<Result<(), DispatchError<E>>> + Send + Sync>;
+
+#[derive(thiserror::Error, Debug)]
+#[non_exhaustive]
+pub enum DispatchError<E: std::fmt::Debug> {
+    #[error("failed to decode record payload: {0}")]
+    Decode(#[from] serde_json::Error),
+    #[error("no handler registered for collection {0}")]
+    UnhandledCollection(String),
+    #[error("no handler registered for identity events")]
+    UnhandledIdentity,
+    #[error("handler failed: {0:?}")]
+    Handler(E),
+}
+
+/// A typed per-collection event handler registry.
+///
+/// Handlers are registered against a collection's record marker type and receive the
+/// record payload already decoded into the concrete lexicon type, avoiding the
+/// re-serialize/re-parse round-trip of matching on the collection NSID by hand.
+#[must_use]
+pub struct HandlerRegistry<S, E: std::fmt::Debug> {
+    record_handlers: HashMap<&'static str, RecordHandlerFn<S, E>>,
+    delete_handlers: HashMap<&'static str, RecordHandlerFn<S, E>>,
+    identity_handler: Option<IdentityHandlerFn<S, E>>,
+    unhandled_handler: Option<RecordHandlerFn<S, E>>,
+}
+
+impl<S, E: std::fmt::Debug> Default for HandlerRegistry<S, E> {
+    fn default() -> Self {
+        Self {
+            record_handlers: HashMap::new(),
+            delete_handlers: HashMap::new(),
+            identity_handler: None,
+            unhandled_handler: None,
+        }
+    }
+}
+
+impl<S, E> HandlerRegistry<S, E>
+where
+    S: Send + Sync + 'static,
+    E: std::fmt::Debug + 'static,
+{
+    /// Create a new registry with no handlers registered
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a handler for record create/update events in `T`'s collection.
+    ///
+    /// The handler receives the record event data alongside the payload decoded into
+    /// the collection's concrete lexicon type.
+    pub fn on_record<T, F, Fut>(mut self, handler: F) -> Self
+    where
+        T: XrpcResp,
+        for<'de> <T as XrpcResp>::Output<'de>:
+            IntoStatic<Output = <T as XrpcResp>::Output<'static>>,
+        <T as XrpcResp>::Output<'static>: Send,
+        F: Fn(Arc<S>, RecordEventData<'static>, <T as XrpcResp>::Output<'static>) -> Fut
+            + Send
+            + Sync
+            + 'static,
+        Fut: Future<Output = Result<(), E>> + Send + 'static,
+    {
+        let handler = Arc::new(handler);
+        self.record_handlers.insert(
+            T::NSID,
+            Arc::new(move |state, record| {
+                let handler = handler.clone();
+                Box::pin(async move {
+                    let payload = match &record.action {
+                        RecordAction::Create {
+                            record: payload, ..
+                        }
+                        | RecordAction::Update {
+                            record: payload, ..
+                        } => payload,
+                        // Delete events are dispatched to the delete handler map.
+                        RecordAction::Delete => return Ok(()),
+                    };
+                    let decoded: <T as XrpcResp>::Output<'_> =
+                        Deserialize::deserialize(payload.raw())?;
+                    let decoded = decoded.into_static();
+                    handler(state, record, decoded)
+                        .await
+                        .map_err(DispatchError::Handler)
+                })
+            }),
+        );
+        self
+    }
+
+    /// Register a handler for record delete events in `T`'s collection
+    pub fn on_delete<T, F, Fut>(mut self, handler: F) -> Self
+    where
+        T: Collection,
+        F: Fn(Arc<S>, RecordEventData<'static>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), E>> + Send + 'static,
+    {
+        let handler = Arc::new(handler);
+        self.delete_handlers.insert(
+            T::NSID,
+            Arc::new(move |state, record| {
+                let handler = handler.clone();
+                Box::pin(async move {
+                    handler(state, record)
+                        .await
+                        .map_err(DispatchError::Handler)
+                })
+            }),
+        );
+        self
+    }
+
+    /// Register a catch-all handler for record events in collections without a registered handler
+    pub fn on_unhandled<F, Fut>(mut self, handler: F) -> Self
+    where
+        F: Fn(Arc<S>, RecordEventData<'static>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), E>> + Send + 'static,
+    {
+        let handler = Arc::new(handler);
+        self.unhandled_handler = Some(Arc::new(move |state, record| {
+            let handler = handler.clone();
+            Box::pin(async move {
+                handler(state, record)
+                    .await
+                    .map_err(DispatchError::Handler)
+            })
+        }));
+        self
+    }
+
+    /// Register a handler for identity events
+    pub fn on_identity<F, Fut>(mut self, handler: F) -> Self
+    where
+        F: Fn(Arc<S>, IdentityEventData<'static>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), E>> + Send + 'static,
+    {
+        let handler = Arc::new(handler);
+        self.identity_handler = Some(Arc::new(move |state, identity| {
+            let handler = handler.clone();
+            Box::pin(async move {
+                handler(state, identity)
+                    .await
+                    .map_err(DispatchError::Handler)
+            })
+        }));
+        self
+    }
+
+    /// Dispatch an event to the matching registered handler.
+    ///
+    /// Intended to be used as the handler function of a
+    /// [`ChannelConnectionHandle`](crate::channel::ChannelConnectionHandle).
+    pub async fn dispatch(
+        &self,
+        state: Arc<S>,
+        data: EventData<'static>,
+    ) -> Result<(), DispatchError<E>> {
+        match data {
+            EventData::Identity { identity, .. } => match &self.identity_handler {
+                Some(handler) => handler(state, identity).await,
+                None => Err(DispatchError::UnhandledIdentity),
+            },
+            EventData::Record { record, .. } => {
+                let handlers = match &record.action {
+                    RecordAction::Create { .. } | RecordAction::Update { .. } => {
+                        &self.record_handlers
+                    }
+                    RecordAction::Delete => &self.delete_handlers,
+                };
+                match handlers
+                    .get(record.collection.as_str())
+                    .or(self.unhandled_handler.as_ref())
+                {
+                    Some(handler) => handler(state, record).await,
+                    None => Err(DispatchError::UnhandledCollection(
+                        record.collection.to_string(),
+                    )),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,272 @@
+mod support;
+
+use floodgate::{
+    api::EventData,
+    channel::{AckStrategy, Channel},
+    cursor::CursorStore,
+    extern_types::{CancellationToken, nsid::Nsid},
+};
+use std::{
+    num::NonZero,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Duration,
+};
+use support::{ConnectionScript, MockTap};
+
+/// A canned record event as TAP would serialize it on the wire.
+fn record_event(id: u64, rkey: &str) -> String {
+    collection_event(id, "net.gifdex.feed.post", rkey)
+}
+
+/// Like [`record_event`], but for an arbitrary collection.
+fn collection_event(id: u64, collection: &str, rkey: &str) -> String {
+    format!(
+        r#"{{"id":{id},"type":"record","record":{{"live":true,"did":"did:plc:ewvi7nxzyoun6zhxrhs64oiz","rev":"3jzfcijpj2z2b","collection":"{collection}","rkey":"{rkey}","action":"delete"}}}}"#
+    )
+}
+
+/// Poll `predicate` until it holds, failing the test after a few seconds.
+async fn wait_for<F: Fn() -> bool>(what: &str, predicate: F) {
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while !predicate() {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .unwrap_or_else(|_| panic!("timed out waiting for {what}"));
+}
+
+/// Only events whose handler returns `Ok` are acked; failed handlers and
+/// frames that don't parse as events leave no ack behind.
+#[tokio::test]
+async fn acks_only_on_handler_success() {
+    let server = MockTap::start(vec![ConnectionScript {
+        events: vec![
+            record_event(1, "failme"),
+            "not an event".to_string(),
+            record_event(2, "okay"),
+        ],
+        close_after_acks: Some(1),
+    }])
+    .await;
+
+    let channel = Channel::builder(server.url.clone()).build().unwrap();
+    let handle = channel.connect().await.unwrap();
+    let handled = Arc::new(Mutex::new(Vec::new()));
+    let handler_handled = handled.clone();
+    handle
+        .handler(move |event| {
+            let handled = handler_handled.clone();
+            async move {
+                let EventData::Record { record, .. } = event else {
+                    return Err("unexpected event type");
+                };
+                handled.lock().unwrap().push(record.rkey.to_string());
+                match record.rkey.as_str() {
+                    "failme" => Err("scripted handler failure"),
+                    _ => Ok(()),
+                }
+            }
+        })
+        .await;
+
+    assert_eq!(server.acks(), vec![2]);
+    // Both well-formed events reached the handler; the malformed frame was
+    // dropped before it.
+    let mut handled = handled.lock().unwrap().clone();
+    handled.sort();
+    assert_eq!(handled, ["failme", "okay"]);
+}
+
+/// A shutdown request stops pulling new messages but drains the in-flight
+/// handler to completion and flushes its ack before closing the socket.
+#[tokio::test]
+async fn shutdown_drains_in_flight_handlers() {
+    let server = MockTap::start(vec![ConnectionScript {
+        events: vec![record_event(1, "slow")],
+        close_after_acks: None,
+    }])
+    .await;
+
+    let channel = Channel::builder(server.url.clone()).build().unwrap();
+    let handle = channel.connect().await.unwrap();
+    let finished = Arc::new(AtomicBool::new(false));
+    let handler_finished = finished.clone();
+    let shutdown = CancellationToken::new();
+    let handler_task = tokio::spawn(handle.handler_with_shutdown(
+        move |_event| {
+            let finished = handler_finished.clone();
+            async move {
+                tokio::time::sleep(Duration::from_millis(200)).await;
+                finished.store(true, Ordering::SeqCst);
+                Ok::<(), &str>(())
+            }
+        },
+        shutdown.clone(),
+    ));
+
+    // Let the event reach the handler, then request shutdown while the
+    // handler is still sleeping.
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    shutdown.cancel();
+    handler_task.await.unwrap();
+
+    assert!(finished.load(Ordering::SeqCst));
+    wait_for("the drained ack to arrive", || server.acks() == vec![1]).await;
+}
+
+/// With a batched ack strategy, a full batch of event ids goes out as a
+/// single ack frame rather than one frame per event.
+#[tokio::test]
+async fn batched_acks_coalesce_into_one_frame() {
+    let server = MockTap::start(vec![ConnectionScript {
+        events: vec![
+            record_event(1, "one"),
+            record_event(2, "two"),
+            record_event(3, "three"),
+        ],
+        close_after_acks: Some(3),
+    }])
+    .await;
+
+    let channel = Channel::builder(server.url.clone())
+        .ack_strategy(AckStrategy::Batched {
+            max_ids: NonZero::new(3).unwrap(),
+        })
+        .build()
+        .unwrap();
+    let handle = channel.connect().await.unwrap();
+    handle
+        .handler(|_event| async move { Ok::<(), &str>(()) })
+        .await;
+
+    let mut acks = server.acks();
+    acks.sort_unstable();
+    assert_eq!(acks, vec![1, 2, 3]);
+    assert_eq!(server.ack_frames(), 1);
+}
+
+/// A per-collection concurrency limit keeps that collection's handlers
+/// serialized without holding up events from other collections - even when
+/// the backlog for the limited collection exceeds the whole pool, because
+/// queued events hand their pool permit back while they wait.
+#[tokio::test]
+async fn collection_limit_serializes_only_its_own_collection() {
+    let server = MockTap::start(vec![ConnectionScript {
+        events: vec![
+            collection_event(1, "net.gifdex.feed.post", "slow-one"),
+            collection_event(2, "net.gifdex.feed.post", "slow-two"),
+            collection_event(3, "net.gifdex.feed.post", "slow-three"),
+            collection_event(4, "net.gifdex.actor.profile", "fast"),
+        ],
+        close_after_acks: Some(4),
+    }])
+    .await;
+
+    let channel = Channel::builder(server.url.clone())
+        .max_concurrent(NonZero::new(2).unwrap())
+        .collection_concurrency(
+            &Nsid::new("net.gifdex.feed.post").unwrap(),
+            NonZero::new(1).unwrap(),
+        )
+        .build()
+        .unwrap();
+    let handle = channel.connect().await.unwrap();
+    let slow_in_flight = Arc::new(Mutex::new((0usize, 0usize)));
+    let fast_handled = Arc::new(AtomicBool::new(false));
+    let handler_slow = slow_in_flight.clone();
+    let handler_fast = fast_handled.clone();
+    handle
+        .handler(move |event| {
+            let slow_in_flight = handler_slow.clone();
+            let fast_handled = handler_fast.clone();
+            async move {
+                let EventData::Record { record, .. } = event else {
+                    return Err("unexpected event type");
+                };
+                if record.collection.as_str() == "net.gifdex.feed.post" {
+                    {
+                        let mut counts = slow_in_flight.lock().unwrap();
+                        counts.0 += 1;
+                        counts.1 = counts.1.max(counts.0);
+                    }
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                    // The cheap collection must not have had to wait for the
+                    // slow handlers to finish.
+                    assert!(fast_handled.load(Ordering::SeqCst));
+                    slow_in_flight.lock().unwrap().0 -= 1;
+                } else {
+                    fast_handled.store(true, Ordering::SeqCst);
+                }
+                Ok(())
+            }
+        })
+        .await;
+
+    let mut acks = server.acks();
+    acks.sort_unstable();
+    assert_eq!(acks, vec![1, 2, 3, 4]);
+    assert_eq!(slow_in_flight.lock().unwrap().1, 1);
+}
+
+/// A cursor store observable from the test.
+#[derive(Debug, Clone, Default)]
+struct MemoryCursorStore(Arc<Mutex<Option<u64>>>);
+
+impl CursorStore for MemoryCursorStore {
+    fn load(&self) -> Option<u64> {
+        *self.0.lock().unwrap()
+    }
+
+    fn save(&self, id: u64) {
+        *self.0.lock().unwrap() = Some(id);
+    }
+}
+
+/// When the server drops the connection the consumer's connect-handle loop
+/// reconnects, resuming from the last acked event id via the cursor store.
+#[tokio::test]
+async fn reconnects_and_resumes_from_acked_cursor() {
+    let server = MockTap::start(vec![
+        ConnectionScript {
+            events: vec![record_event(1, "first")],
+            close_after_acks: Some(1),
+        },
+        ConnectionScript {
+            events: vec![record_event(2, "second")],
+            close_after_acks: Some(1),
+        },
+    ])
+    .await;
+
+    let cursor = MemoryCursorStore::default();
+    let channel = Channel::builder(server.url.clone())
+        .cursor_store(cursor.clone())
+        .reconnect_backoff(Duration::from_millis(10), Duration::from_millis(50))
+        .build()
+        .unwrap();
+
+    // The same connect-handle loop the consumers run: each scripted
+    // connection closes after its ack, handing control back.
+    for expected_cursor in [1, 2] {
+        let handle = channel.connect_with_retry().await;
+        handle
+            .handler(|_event| async move { Ok::<(), &str>(()) })
+            .await;
+        // The final cursor position is persisted as the connection's writer
+        // task winds down.
+        wait_for("the cursor position to be persisted", || {
+            cursor.load() == Some(expected_cursor)
+        })
+        .await;
+    }
+
+    assert_eq!(server.connections(), 2);
+    assert_eq!(server.acks(), vec![1, 2]);
+    // The first connection starts fresh; the second resumes from the last
+    // acked event id.
+    assert_eq!(server.cursors(), vec![None, Some(1)]);
+}
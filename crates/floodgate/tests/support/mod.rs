@@ -0,0 +1,155 @@
+//! A mock TAP server for channel tests: serves canned event JSON over the
+//! `/channel` websocket route, records the acks it receives and can script
+//! a disconnect per connection.
+
+use axum::{
+    Router,
+    extract::{
+        Query, State, WebSocketUpgrade,
+        ws::{Message, WebSocket},
+    },
+    response::Response,
+    routing::get,
+};
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
+use url::Url;
+
+/// What a single accepted connection serves and when it goes away.
+pub struct ConnectionScript {
+    /// Raw text frames sent to the client in order after the handshake.
+    pub events: Vec<String>,
+    /// Close the connection once this many acks have been received. `None`
+    /// keeps it open until the client closes.
+    pub close_after_acks: Option<usize>,
+}
+
+#[derive(Clone, Default)]
+struct ServerState {
+    scripts: Arc<Mutex<VecDeque<ConnectionScript>>>,
+    acks: Arc<Mutex<Vec<u64>>>,
+    cursors: Arc<Mutex<Vec<Option<u64>>>>,
+    connections: Arc<AtomicUsize>,
+    ack_frames: Arc<AtomicUsize>,
+}
+
+/// A mock TAP server bound to an ephemeral local port.
+pub struct MockTap {
+    /// Base `ws://` URL to hand to [`floodgate::channel::Channel::builder`].
+    pub url: Url,
+    state: ServerState,
+}
+
+impl MockTap {
+    /// Start the server, handing each accepted connection the next script
+    /// in order. Connections beyond the scripted ones serve nothing.
+    pub async fn start(scripts: Vec<ConnectionScript>) -> Self {
+        let state = ServerState {
+            scripts: Arc::new(Mutex::new(scripts.into())),
+            ..Default::default()
+        };
+        let router = Router::new()
+            .route("/channel", get(channel))
+            .with_state(state.clone());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            axum::serve(listener, router).await.unwrap();
+        });
+        MockTap {
+            url: Url::parse(&format!("ws://127.0.0.1:{port}")).unwrap(),
+            state,
+        }
+    }
+
+    /// Event ids acked by the client so far, in arrival order.
+    pub fn acks(&self) -> Vec<u64> {
+        self.state.acks.lock().unwrap().clone()
+    }
+
+    /// The `cursor` query parameter of each connection so far.
+    pub fn cursors(&self) -> Vec<Option<u64>> {
+        self.state.cursors.lock().unwrap().clone()
+    }
+
+    /// Number of connections accepted so far.
+    pub fn connections(&self) -> usize {
+        self.state.connections.load(Ordering::SeqCst)
+    }
+
+    /// Number of ack frames received so far, batched or not.
+    pub fn ack_frames(&self) -> usize {
+        self.state.ack_frames.load(Ordering::SeqCst)
+    }
+}
+
+async fn channel(
+    State(state): State<ServerState>,
+    Query(params): Query<HashMap<String, String>>,
+    upgrade: WebSocketUpgrade,
+) -> Response {
+    state.connections.fetch_add(1, Ordering::SeqCst);
+    state
+        .cursors
+        .lock()
+        .unwrap()
+        .push(params.get("cursor").and_then(|cursor| cursor.parse().ok()));
+    let script = state
+        .scripts
+        .lock()
+        .unwrap()
+        .pop_front()
+        .unwrap_or(ConnectionScript {
+            events: Vec::new(),
+            close_after_acks: None,
+        });
+    upgrade.on_upgrade(move |socket| serve_script(socket, script, state))
+}
+
+#[derive(serde::Deserialize)]
+struct Ack {
+    #[serde(rename = "type")]
+    type_: String,
+    id: Option<u64>,
+    ids: Option<Vec<u64>>,
+}
+
+async fn serve_script(mut socket: WebSocket, script: ConnectionScript, state: ServerState) {
+    for event in &script.events {
+        if socket
+            .send(Message::Text(event.clone().into()))
+            .await
+            .is_err()
+        {
+            return;
+        }
+    }
+    if script.close_after_acks == Some(0) {
+        let _ = socket.send(Message::Close(None)).await;
+        return;
+    }
+    let mut acks_seen = 0;
+    while let Some(Ok(message)) = socket.recv().await {
+        if let Message::Text(text) = message {
+            let ack: Ack = serde_json::from_str(&text)
+                .unwrap_or_else(|err| panic!("client sent a non-ack text frame: {text} ({err})"));
+            assert_eq!(ack.type_, "ack", "unexpected message type from client");
+            state.ack_frames.fetch_add(1, Ordering::SeqCst);
+            let ids = ack.ids.or_else(|| ack.id.map(|id| vec![id])).unwrap();
+            acks_seen += ids.len();
+            state.acks.lock().unwrap().extend(ids);
+            if script
+                .close_after_acks
+                .is_some_and(|wanted| acks_seen >= wanted)
+            {
+                let _ = socket.send(Message::Close(None)).await;
+                return;
+            }
+        }
+    }
+}
@@ -1,14 +1,29 @@
 use anyhow::Result;
 use sqlx::{PgExecutor, PgPool, migrate, postgres::PgPoolOptions};
+use std::time::Duration;
+
+/// Connection pool tuning for [`Database::new`], wired from each binary's
+/// CLI arguments. The defaults match the pool the services have always used.
+#[derive(Clone, Debug)]
+pub struct DatabaseConfig {
+    /// Maximum number of connections held by the pool.
+    pub max_connections: u32,
+    /// How long to wait for a free connection before failing the query.
+    pub acquire_timeout: Duration,
+    /// How long an idle connection is kept in the pool before being closed.
+    pub idle_timeout: Duration,
+}
 
 pub struct Database {
     pool: PgPool,
 }
 
 impl Database {
-    pub async fn new(connect_string: &str) -> Result<Self> {
+    pub async fn new(connect_string: &str, config: &DatabaseConfig) -> Result<Self> {
         let pool = PgPoolOptions::new()
-            .max_connections(20)
+            .max_connections(config.max_connections)
+            .acquire_timeout(config.acquire_timeout)
+            .idle_timeout(config.idle_timeout)
             .connect(connect_string)
             .await?;
         migrate!("../sqlx-migrations").run(&pool).await?;
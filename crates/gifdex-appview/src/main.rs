@@ -1,15 +1,25 @@
 mod database;
 mod routes;
+#[cfg(test)]
+mod test_support;
 
 use crate::routes::{
     handle_index,
     well_known::handle_well_known_did,
     xrpc::{
-        com_atproto::sync::handle_get_repo_status,
+        com_atproto::{
+            identity::handle_resolve_handle, server::handle_describe_server,
+            sync::handle_get_repo_status,
+        },
         health::handle_health,
         net_gifdex::{
-            actor::{handle_get_profile, handle_get_profiles},
-            feed::{handle_get_post, handle_get_posts_by_actor, handle_get_posts_by_query},
+            actor::{handle_get_profile, handle_get_profiles, handle_search_actors},
+            feed::{
+                handle_get_actor_favourites, handle_get_feed_skeleton, handle_get_post,
+                handle_get_post_favourites,
+                handle_get_posts, handle_get_posts_by_actor, handle_get_posts_by_query,
+                handle_get_posts_by_tag, handle_get_trending, handle_search_posts,
+            },
         },
     },
 };
@@ -22,16 +32,27 @@ use axum::{
     routing::get,
 };
 use clap::Parser;
-use database::Database;
+use database::{Database, DatabaseConfig};
 use dotenvy::dotenv;
 use gifdex_lexicons::net_gifdex::{
-    actor::{get_profile::GetProfileRequest, get_profiles::GetProfilesRequest},
+    actor::{
+        get_profile::GetProfileRequest, get_profiles::GetProfilesRequest,
+        search_actors::SearchActorsRequest,
+    },
     feed::{
-        get_post::GetPostRequest, get_posts_by_actor::GetPostsByActorRequest,
-        get_posts_by_query::GetPostsByQueryRequest,
+        get_actor_favourites::GetActorFavouritesRequest,
+        get_feed_skeleton::GetFeedSkeletonRequest, get_post::GetPostRequest,
+        get_post_favourites::GetPostFavouritesRequest, get_posts::GetPostsRequest,
+        get_posts_by_actor::GetPostsByActorRequest, get_posts_by_query::GetPostsByQueryRequest,
+        get_posts_by_tag::GetPostsByTagRequest, get_trending::GetTrendingRequest,
+        search_posts::SearchPostsRequest,
     },
 };
-use jacquard_api::com_atproto::sync::get_repo_status::GetRepoStatusRequest;
+use gifdex_metrics::{HttpMetrics, metrics_router, track_http};
+use jacquard_api::com_atproto::{
+    identity::resolve_handle::ResolveHandleRequest,
+    server::describe_server::DescribeServerRequest, sync::get_repo_status::GetRepoStatusRequest,
+};
 use jacquard_axum::{
     IntoRouter,
     service_auth::{ServiceAuth, ServiceAuthConfig},
@@ -46,13 +67,14 @@ use jacquard_common::{
     url::Url,
 };
 use jacquard_identity::{JacquardResolver, resolver::ResolverOptions};
-use std::{collections::BTreeMap, net::SocketAddr, sync::Arc};
+use std::{collections::BTreeMap, net::SocketAddr, sync::Arc, time::Duration};
 use tokio::{net::TcpListener, signal};
 use tower_http::{
     catch_panic::CatchPanicLayer,
     cors::{Any, CorsLayer},
     normalize_path::NormalizePathLayer,
-    trace::{self, DefaultOnFailure, DefaultOnRequest, DefaultOnResponse, TraceLayer},
+    request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer},
+    trace::{DefaultOnFailure, DefaultOnRequest, TraceLayer},
 };
 use tracing::{Level, info};
 use tracing_subscriber::EnvFilter;
@@ -78,6 +100,30 @@ struct Arguments {
     )]
     database_url: String,
 
+    /// Maximum number of connections in the database pool.
+    #[arg(
+        long = "database-max-connections",
+        env = "GIFDEX_APPVIEW_DATABASE_MAX_CONNECTIONS",
+        default_value_t = 20
+    )]
+    database_max_connections: u32,
+
+    /// Seconds to wait for a free database connection before failing a query.
+    #[arg(
+        long = "database-acquire-timeout",
+        env = "GIFDEX_APPVIEW_DATABASE_ACQUIRE_TIMEOUT",
+        default_value_t = 30
+    )]
+    database_acquire_timeout: u64,
+
+    /// Seconds an idle database connection is kept around before being closed.
+    #[arg(
+        long = "database-idle-timeout",
+        env = "GIFDEX_APPVIEW_DATABASE_IDLE_TIMEOUT",
+        default_value_t = 600
+    )]
+    database_idle_timeout: u64,
+
     /// Host to use for serving media from.
     ///
     /// The host must serve Gifdex-compatiable endpoints with the expected formats.
@@ -89,14 +135,73 @@ struct Arguments {
     /// Used for generating a `well-known/did.json` document, `did:web` identity and a AppView service endpoint.
     #[arg(long = "host", env = "GIFDEX_APPVIEW_HOST")]
     host: Url,
+
+    /// DID that this AppView identifies itself as when participating in atproto.
+    ///
+    /// Used as the service-auth audience, the label source and in `describeServer`.
+    /// Defaults to a `did:web` derived from the host when unset.
+    #[arg(long = "service-did", env = "GIFDEX_APPVIEW_SERVICE_DID")]
+    service_did: Option<String>,
+
+    /// Comma-separated DIDs of the moderation labeler accounts whose labels
+    /// this AppView hydrates onto views.
+    ///
+    /// Defaults to just the service DID when unset. Clients pick between the
+    /// configured labelers with the `atproto-accept-labelers` header.
+    #[arg(
+        long = "labeler-did",
+        env = "GIFDEX_APPVIEW_LABELER_DIDS",
+        value_delimiter = ','
+    )]
+    labelers: Vec<String>,
+
+    /// Shared secret used to sign CDN media URLs. Media URL signing is
+    /// disabled when unset.
+    #[arg(
+        long = "media-signing-secret",
+        env = "GIFDEX_APPVIEW_MEDIA_SIGNING_SECRET"
+    )]
+    media_signing_secret: Option<String>,
+
+    /// Seconds a signed media URL stays valid for.
+    #[arg(
+        long = "media-url-ttl",
+        env = "GIFDEX_APPVIEW_MEDIA_URL_TTL",
+        default_value_t = 86400
+    )]
+    media_url_ttl: u64,
+
+    /// OTLP gRPC endpoint to export trace spans to. Span export is disabled
+    /// when unset; only available when built with the `otel` feature.
+    #[cfg(feature = "otel")]
+    #[arg(long = "otlp-endpoint", env = "GIFDEX_APPVIEW_OTLP_ENDPOINT")]
+    otlp_endpoint: Option<String>,
+
+    /// Emit logs as newline-delimited JSON instead of human-readable text.
+    #[arg(long = "log-json", env = "GIFDEX_APPVIEW_LOG_JSON")]
+    log_json: bool,
+
+    /// Comma-separated list of origins allowed to make cross-origin requests,
+    /// or `*` to allow any origin.
+    #[arg(
+        long = "cors-allow-origin",
+        env = "GIFDEX_APPVIEW_CORS_ALLOW_ORIGIN",
+        value_delimiter = ',',
+        default_value = "*"
+    )]
+    cors_allow_origin: Vec<String>,
 }
 
 #[derive(Clone)]
 struct AppState {
     database: Arc<Database>,
     cdn_url: Url,
+    service_did: Did<'static>,
     service_did_document: DidDocument<'static>,
     service_auth_config: ServiceAuthConfig<JacquardResolver>,
+    labelers: Vec<String>,
+    media_signing_secret: Option<String>,
+    media_url_ttl: Duration,
 }
 
 impl ServiceAuth for AppState {
@@ -118,66 +223,151 @@ impl ServiceAuth for AppState {
 #[tokio::main]
 async fn main() -> Result<()> {
     dotenv().ok();
-    tracing_subscriber::fmt()
-        .with_env_filter(EnvFilter::try_from_default_env().unwrap_or(EnvFilter::new("info")))
-        .init();
     let args = Arguments::parse();
+    // Structured JSON output for log aggregators when requested, the default
+    // human-readable text format otherwise.
+    let log_filter = EnvFilter::try_from_default_env().unwrap_or(EnvFilter::new("info"));
+    #[cfg(feature = "otel")]
+    match &args.otlp_endpoint {
+        Some(endpoint) => init_otlp_tracing(endpoint, log_filter, args.log_json)?,
+        None => init_local_tracing(log_filter, args.log_json),
+    }
+    #[cfg(not(feature = "otel"))]
+    init_local_tracing(log_filter, args.log_json);
 
     // Create ATProto service information.
-    let service_did = Did::new_owned(format!(
-        "did:web:{}",
-        args.host
-            .host_str()
-            .context("unable to get host from host url")?
-    ))
-    .context("failed to create did:web from host")?;
+    let service_did = match args.service_did {
+        Some(did) => Did::new_owned(did).context("invalid service DID provided")?,
+        None => Did::new_owned(format!(
+            "did:web:{}",
+            args.host
+                .host_str()
+                .context("unable to get host from host url")?
+        ))
+        .context("failed to create did:web from host")?,
+    };
     let service_did_doc = build_service_did_doc(&service_did, &args.host);
+    // Labels are only hydrated from moderation accounts on this list.
+    let labelers = match args.labelers.is_empty() {
+        true => vec![service_did.as_str().to_owned()],
+        false => args
+            .labelers
+            .iter()
+            .map(|did| {
+                Did::new_owned(did.clone())
+                    .map(|did| did.as_str().to_owned())
+                    .map_err(|_| anyhow::anyhow!("invalid labeler DID: {did}"))
+            })
+            .collect::<Result<Vec<_>>>()?,
+    };
     let service_auth_config = ServiceAuthConfig::new(
-        service_did,
+        service_did.clone(),
         JacquardResolver::new(reqwest::Client::new(), ResolverOptions::default()),
     );
 
     // Initialise application state and required services.
+    let database_config = DatabaseConfig {
+        max_connections: args.database_max_connections,
+        acquire_timeout: Duration::from_secs(args.database_acquire_timeout),
+        idle_timeout: Duration::from_secs(args.database_idle_timeout),
+    };
     let database = Arc::new(
-        Database::new(&args.database_url)
+        Database::new(&args.database_url, &database_config)
             .await
             .context("failed to connect to database")?,
     );
 
     // Start server.
+    // With an explicit allow-list the matching origin is echoed back
+    // per-request (and preflights answered accordingly) rather than the
+    // wildcard, so responses stay cacheable per-origin.
+    let cors = match args.cors_allow_origin.iter().any(|origin| origin == "*") {
+        true => CorsLayer::new().allow_origin("*".parse::<HeaderValue>().unwrap()),
+        false => CorsLayer::new().allow_origin(
+            args.cors_allow_origin
+                .iter()
+                .map(|origin| origin.parse::<HeaderValue>())
+                .collect::<Result<Vec<_>, _>>()
+                .context("invalid CORS origin provided")?,
+        ),
+    }
+    .allow_methods([Method::GET, Method::POST, Method::OPTIONS])
+    .allow_headers(Any);
+
+    let metrics = Arc::new(HttpMetrics::new());
     let router = Router::new()
         .route("/", get(handle_index))
+        .route("/health", get(handle_health))
         .route("/xrpc/_health", get(handle_health))
         .route("/.well-known/did.json", get(handle_well_known_did))
+        // AtProto Server
+        .merge(DescribeServerRequest::into_router(handle_describe_server))
+        // AtProto Identity
+        .merge(ResolveHandleRequest::into_router(handle_resolve_handle))
         // AtProto Sync
         .merge(GetRepoStatusRequest::into_router(handle_get_repo_status))
         // Gifdex Actor
         .merge(GetProfileRequest::into_router(handle_get_profile))
         .merge(GetProfilesRequest::into_router(handle_get_profiles))
+        .merge(SearchActorsRequest::into_router(handle_search_actors))
         // Gifdex Feed
         .merge(GetPostRequest::into_router(handle_get_post))
+        .merge(GetPostFavouritesRequest::into_router(
+            handle_get_post_favourites,
+        ))
+        .merge(GetPostsRequest::into_router(handle_get_posts))
         .merge(GetPostsByQueryRequest::into_router(
             handle_get_posts_by_query,
         ))
         .merge(GetPostsByActorRequest::into_router(
             handle_get_posts_by_actor,
         ))
+        .merge(GetPostsByTagRequest::into_router(handle_get_posts_by_tag))
+        .merge(GetActorFavouritesRequest::into_router(
+            handle_get_actor_favourites,
+        ))
+        .merge(GetTrendingRequest::into_router(handle_get_trending))
+        .merge(GetFeedSkeletonRequest::into_router(handle_get_feed_skeleton))
+        .merge(SearchPostsRequest::into_router(handle_search_posts))
         // Gifdex Moderation
+        // Echo the request's correlation id back on the response.
+        .layer(PropagateRequestIdLayer::x_request_id())
         .layer(
             TraceLayer::new_for_http()
-                .make_span_with(trace::DefaultMakeSpan::new().level(Level::INFO))
+                .make_span_with(|request: &Request| {
+                    let request_id = request
+                        .headers()
+                        .get("x-request-id")
+                        .and_then(|value| value.to_str().ok())
+                        .unwrap_or("unknown");
+                    tracing::info_span!(
+                        "request",
+                        method = %request.method(),
+                        path = %request.uri().path(),
+                        request_id,
+                    )
+                })
                 .on_request(DefaultOnRequest::default().level(Level::INFO))
-                .on_response(DefaultOnResponse::default().level(Level::INFO))
+                .on_response(
+                    |response: &axum::response::Response,
+                     latency: std::time::Duration,
+                     _span: &tracing::Span| {
+                        tracing::info!(
+                            status = response.status().as_u16(),
+                            latency_ms = latency.as_millis() as u64,
+                            "finished processing request"
+                        );
+                    },
+                )
                 .on_failure(DefaultOnFailure::default().level(Level::ERROR)),
         )
+        .layer(axum_middleware::from_fn_with_state(
+            metrics.clone(),
+            track_http,
+        ))
         .layer(NormalizePathLayer::trim_trailing_slash())
         .layer(CatchPanicLayer::new()) // TODO: Use custom panic handler to return Xrpc InternalServerError.
-        .layer(
-            CorsLayer::new()
-                .allow_origin("*".parse::<HeaderValue>().unwrap())
-                .allow_methods([Method::GET, Method::POST, Method::OPTIONS])
-                .allow_headers(Any),
-        )
+        .layer(cors)
         .layer(axum_middleware::from_fn(
             async |req: Request, next: Next| {
                 let mut res = next.run(req).await;
@@ -190,12 +380,22 @@ async fn main() -> Result<()> {
                 res
             },
         ))
+        // Give every request a correlation id - an incoming `X-Request-Id`
+        // header is kept, otherwise a UUID is generated - before anything
+        // else sees the request.
+        .layer(SetRequestIdLayer::x_request_id(MakeRequestUuid))
         .with_state(AppState {
             database,
             cdn_url: args.cdn,
+            service_did,
             service_did_document: service_did_doc,
             service_auth_config,
-        });
+            labelers,
+            media_signing_secret: args.media_signing_secret,
+            media_url_ttl: Duration::from_secs(args.media_url_ttl),
+        })
+        // Scrapes stay outside the instrumented, CORS-allowed routes.
+        .merge(metrics_router(metrics));
 
     let tcp_listener = TcpListener::bind(args.address).await?;
     info!(
@@ -209,6 +409,54 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
+/// Initialise the plain local tracing subscriber.
+fn init_local_tracing(log_filter: EnvFilter, log_json: bool) {
+    match log_json {
+        true => tracing_subscriber::fmt()
+            .with_env_filter(log_filter)
+            .json()
+            .init(),
+        false => tracing_subscriber::fmt().with_env_filter(log_filter).init(),
+    }
+}
+
+/// Initialise tracing with an OTLP span exporter layered on top of the local
+/// log output, shipping spans to the collector at `endpoint`. The W3C trace
+/// context propagator is installed globally so outbound requests can carry
+/// the active trace.
+#[cfg(feature = "otel")]
+fn init_otlp_tracing(endpoint: &str, log_filter: EnvFilter, log_json: bool) -> Result<()> {
+    use opentelemetry::trace::TracerProvider as _;
+    use opentelemetry_otlp::WithExportConfig;
+    use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+    let exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_tonic()
+        .with_endpoint(endpoint)
+        .build()?;
+    let provider = opentelemetry_sdk::trace::SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(
+            opentelemetry_sdk::Resource::builder()
+                .with_service_name(env!("CARGO_PKG_NAME"))
+                .build(),
+        )
+        .build();
+    let tracer = provider.tracer(env!("CARGO_PKG_NAME"));
+    opentelemetry::global::set_tracer_provider(provider);
+    opentelemetry::global::set_text_map_propagator(
+        opentelemetry_sdk::propagation::TraceContextPropagator::new(),
+    );
+    let registry = tracing_subscriber::registry()
+        .with(log_filter)
+        .with(tracing_opentelemetry::layer().with_tracer(tracer));
+    match log_json {
+        true => registry.with(tracing_subscriber::fmt::layer().json()).init(),
+        false => registry.with(tracing_subscriber::fmt::layer()).init(),
+    }
+    Ok(())
+}
+
 async fn shutdown_signal() {
     let ctrl_c = async {
         signal::ctrl_c()
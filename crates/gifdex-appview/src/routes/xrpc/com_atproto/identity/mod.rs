@@ -0,0 +1,3 @@
+mod resolve_handle;
+
+pub use resolve_handle::*;
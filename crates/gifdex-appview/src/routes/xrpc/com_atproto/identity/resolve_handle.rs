@@ -0,0 +1,51 @@
+use crate::{AppState, routes::xrpc::AppError};
+use axum::{Json, extract::State};
+use jacquard_api::com_atproto::identity::resolve_handle::{
+    ResolveHandle, ResolveHandleError, ResolveHandleOutput, ResolveHandleRequest,
+};
+use jacquard_axum::{ExtractXrpc, XrpcErrorResponse, service_auth::ServiceAuth};
+use jacquard_common::{
+    types::did::Did,
+    xrpc::{XrpcError, XrpcRequest},
+};
+use jacquard_identity::resolver::IdentityResolver;
+use sqlx::query;
+use tracing::debug;
+
+pub async fn handle_resolve_handle(
+    State(state): State<AppState>,
+    ExtractXrpc(request): ExtractXrpc<ResolveHandleRequest>,
+) -> Result<Json<ResolveHandleOutput<'static>>, XrpcErrorResponse<ResolveHandleError<'static>>> {
+    // Accounts we index carry their handle, so most lookups are answered
+    // locally without touching the network.
+    let record = query!(
+        "SELECT did FROM accounts WHERE handle = $1",
+        request.handle.as_str()
+    )
+    .fetch_optional(state.database.executor())
+    .await
+    .map_err(|err| AppError::database(ResolveHandle::NSID, err))?;
+
+    if let Some(account) = record {
+        let did = account
+            .did
+            .parse::<Did>()
+            .map_err(|err| AppError::data(ResolveHandle::NSID, err))?;
+        return Ok(Json(ResolveHandleOutput {
+            did,
+            extra_data: None,
+        }));
+    }
+
+    // Fall back to a live resolution for handles we haven't ingested.
+    match state.resolver().resolve_handle(&request.handle).await {
+        Ok(did) => Ok(Json(ResolveHandleOutput {
+            did,
+            extra_data: None,
+        })),
+        Err(err) => {
+            debug!("failed to resolve handle {}: {err}", request.handle);
+            Err(XrpcError::Xrpc(ResolveHandleError::HandleNotFound(None)).into())
+        }
+    }
+}
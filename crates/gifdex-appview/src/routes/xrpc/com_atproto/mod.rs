@@ -1 +1,3 @@
+pub mod identity;
+pub mod server;
 pub mod sync;
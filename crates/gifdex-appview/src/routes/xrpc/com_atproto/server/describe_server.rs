@@ -0,0 +1,22 @@
+use crate::AppState;
+use axum::{Json, extract::State};
+use jacquard_api::com_atproto::server::describe_server::{
+    DescribeServerOutput, DescribeServerRequest,
+};
+use jacquard_axum::{ExtractXrpc, XrpcErrorResponse};
+use jacquard_common::xrpc::GenericXrpcError;
+
+pub async fn handle_describe_server(
+    State(state): State<AppState>,
+    ExtractXrpc(_request): ExtractXrpc<DescribeServerRequest>,
+) -> Result<Json<DescribeServerOutput<'static>>, XrpcErrorResponse<GenericXrpcError>> {
+    Ok(Json(DescribeServerOutput {
+        available_user_domains: Vec::new(),
+        contact: None,
+        did: state.service_did.clone(),
+        invite_code_required: None,
+        links: None,
+        phone_verification_required: None,
+        extra_data: None,
+    }))
+}
@@ -0,0 +1,3 @@
+mod describe_server;
+
+pub use describe_server::*;
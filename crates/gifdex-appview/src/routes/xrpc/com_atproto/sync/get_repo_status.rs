@@ -1,10 +1,10 @@
-use crate::AppState;
+use crate::{AppState, routes::xrpc::AppError};
 use axum::{Json, extract::State};
 use jacquard_api::com_atproto::sync::get_repo_status::{
-    GetRepoStatusError, GetRepoStatusOutput, GetRepoStatusRequest,
+    GetRepoStatus, GetRepoStatusError, GetRepoStatusOutput, GetRepoStatusRequest,
 };
 use jacquard_axum::{ExtractXrpc, XrpcErrorResponse};
-use jacquard_common::xrpc::XrpcError;
+use jacquard_common::xrpc::{XrpcError, XrpcRequest};
 use sqlx::query;
 
 pub async fn handle_get_repo_status(
@@ -17,7 +17,7 @@ pub async fn handle_get_repo_status(
     )
     .fetch_optional(state.database.executor())
     .await
-    .unwrap(); // TODO: Use Xrpc error.
+    .map_err(|err| AppError::database(GetRepoStatus::NSID, err))?;
 
     let Some(account) = record else {
         return Err(XrpcError::Xrpc(GetRepoStatusError::RepoNotFound(None)).into());
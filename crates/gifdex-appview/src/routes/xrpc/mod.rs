@@ -1,3 +1,86 @@
+use axum::http::StatusCode;
+use jacquard_axum::XrpcErrorResponse;
+use jacquard_common::{
+    IntoStatic,
+    smol_str::SmolStr,
+    xrpc::{GenericXrpcError, XrpcError},
+};
+use std::sync::atomic::{AtomicU64, Ordering};
+
 pub mod com_atproto;
 pub mod health;
 pub mod net_gifdex;
+
+/// An internal failure raised while serving an XRPC endpoint, tagged with the
+/// endpoint's NSID.
+///
+/// Converting into a response classifies the failure - connection and pool
+/// problems surface as `UpstreamFailure`, everything else (including row
+/// decode failures) as `InternalServerError` - and stamps both the log line
+/// and the response body with a correlation id so user reports can be matched
+/// up with logs.
+pub enum AppError {
+    /// A database query failed.
+    Database {
+        nsid: &'static str,
+        source: sqlx::Error,
+    },
+    /// Data loaded from the database could not be decoded into its API shape.
+    Data {
+        nsid: &'static str,
+        source: Box<dyn std::fmt::Debug>,
+    },
+}
+
+impl AppError {
+    pub fn database(nsid: &'static str, source: sqlx::Error) -> Self {
+        Self::Database { nsid, source }
+    }
+
+    pub fn data(nsid: &'static str, source: impl std::fmt::Debug + 'static) -> Self {
+        Self::Data {
+            nsid,
+            source: Box::new(source),
+        }
+    }
+}
+
+impl<E> From<AppError> for XrpcErrorResponse<E>
+where
+    E: std::error::Error + IntoStatic,
+{
+    fn from(err: AppError) -> Self {
+        static NEXT_ERROR_ID: AtomicU64 = AtomicU64::new(1);
+        let error_id = NEXT_ERROR_ID.fetch_add(1, Ordering::Relaxed);
+        let (nsid, error, http_status, source): (_, _, _, Box<dyn std::fmt::Debug>) = match err {
+            AppError::Database { nsid, source } => match source {
+                sqlx::Error::PoolTimedOut | sqlx::Error::PoolClosed | sqlx::Error::Io(_) => {
+                    (nsid, "UpstreamFailure", StatusCode::BAD_GATEWAY, Box::new(source) as _)
+                }
+                _ => (
+                    nsid,
+                    "InternalServerError",
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Box::new(source) as _,
+                ),
+            },
+            AppError::Data { nsid, source } => (
+                nsid,
+                "InternalServerError",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                source,
+            ),
+        };
+        tracing::error!("{error} serving {nsid} (error id {error_id}): {source:?}");
+        XrpcError::Generic(GenericXrpcError {
+            error: SmolStr::new_static(error),
+            message: Some(SmolStr::new(format!(
+                "An internal error occurred (error id {error_id})"
+            ))),
+            nsid,
+            method: "GET",
+            http_status,
+        })
+        .into()
+    }
+}
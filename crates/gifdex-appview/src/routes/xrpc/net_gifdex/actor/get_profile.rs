@@ -1,43 +1,62 @@
-use crate::AppState;
-use axum::{Json, extract::State};
+use crate::{AppState, routes::xrpc::AppError};
+use axum::{Json, extract::State, http::HeaderMap};
 use gifdex_lexicons::net_gifdex::actor::{
     ProfileView,
-    get_profile::{GetProfileError, GetProfileOutput, GetProfileRequest},
+    get_profile::{GetProfile, GetProfileError, GetProfileOutput, GetProfileRequest},
 };
 use jacquard_axum::{ExtractXrpc, XrpcErrorResponse, service_auth::ExtractOptionalServiceAuth};
 use jacquard_common::{
-    types::{string::Handle, uri::Uri},
-    xrpc::XrpcError,
+    types::{did::Did, string::Handle, uri::Uri},
+    xrpc::{XrpcError, XrpcRequest},
 };
 use sqlx::query;
+use tracing::warn;
 
 pub async fn handle_get_profile(
     State(state): State<AppState>,
     ExtractOptionalServiceAuth(_auth): ExtractOptionalServiceAuth,
+    headers: HeaderMap,
     ExtractXrpc(request): ExtractXrpc<GetProfileRequest>,
 ) -> Result<Json<GetProfileOutput<'static>>, XrpcErrorResponse<GetProfileError<'static>>> {
+    // Non-active (deactivated, suspended or taken-down) accounts are treated
+    // as not found, whether or not the identity purge already ran.
     let account = query!(
         "SELECT did, handle, display_name, avatar_blob_cid, pronouns, indexed_at,
         (SELECT COUNT(*) FROM posts WHERE did = accounts.did) as \"post_count!\"
-        FROM accounts WHERE did = $1 OR handle = $1",
+        FROM accounts WHERE (did = $1 OR handle = $1)
+        AND is_active AND status = 'active'",
         request.actor.as_str()
     )
     .fetch_optional(state.database.executor())
     .await
-    .unwrap(); // TODO: Use Xrpc error.
+    .map_err(|err| AppError::database(GetProfile::NSID, err))?;
 
     let Some(account) = account else {
         return Err(XrpcError::Xrpc(GetProfileError::ProfileNotFound(None)).into());
     };
 
+    // `actor` may be a handle, so the view's DID comes from the matched row
+    // rather than the request.
+    let did = account
+        .did
+        .parse::<Did>()
+        .map_err(|err| AppError::data(GetProfile::NSID, err))?;
+
+    let labelers = super::super::accepted_labelers(&state, &headers);
+    let mut labels = super::super::actor_labels(&state, std::slice::from_ref(&account.did), &labelers)
+        .await
+        .map_err(|err| AppError::database(GetProfile::NSID, err))?;
+
     Ok(Json(GetProfileOutput {
         value: ProfileView::new()
-            .did(request.actor)
-            .handle(
-                account
-                    .handle
-                    .map(|handle| handle.parse::<Handle>().unwrap()),
-            )
+            .did(did)
+            .handle(account.handle.and_then(|handle| {
+                handle
+                    .parse::<Handle>()
+                    .inspect_err(|err| warn!("Malformed handle stored for account: {err:?}"))
+                    .ok()
+            }))
+            .labels(labels.remove(&account.did))
             .display_name(account.display_name.map(|display_name| display_name.into()))
             .pronouns(account.pronouns.map(|pronouns| pronouns.into()))
             .avatar(account.avatar_blob_cid.map(|blob_cid| {
@@ -1,58 +1,111 @@
-use crate::AppState;
-use axum::{Json, extract::State};
+use crate::{AppState, routes::xrpc::AppError};
+use axum::{Json, extract::State, http::HeaderMap, http::StatusCode};
 use gifdex_lexicons::net_gifdex::actor::{
     ProfileView,
-    get_profiles::{GetProfilesOutput, GetProfilesRequest},
+    get_profiles::{GetProfiles, GetProfilesOutput, GetProfilesRequest},
 };
 use jacquard_axum::{ExtractXrpc, XrpcErrorResponse, service_auth::ExtractOptionalServiceAuth};
 use jacquard_common::{
+    smol_str::SmolStr,
     types::{did::Did, string::Handle, uri::Uri},
-    xrpc::GenericXrpcError,
+    xrpc::{GenericXrpcError, XrpcError, XrpcRequest},
 };
 use sqlx::query;
+use std::collections::HashMap;
+use tracing::warn;
+
+/// Maximum number of actors accepted per request. Matches the
+/// `net.gifdex.actor.getProfiles` lexicon bound.
+const MAX_ACTORS: usize = 25;
 
 pub async fn handle_get_profiles(
     State(state): State<AppState>,
     ExtractOptionalServiceAuth(_auth): ExtractOptionalServiceAuth,
+    headers: HeaderMap,
     ExtractXrpc(request): ExtractXrpc<GetProfilesRequest>,
 ) -> Result<Json<GetProfilesOutput<'static>>, XrpcErrorResponse<GenericXrpcError>> {
+    if request.actors.len() > MAX_ACTORS {
+        return Err(XrpcError::Generic(GenericXrpcError {
+            error: SmolStr::new_static("InvalidRequest"),
+            message: Some(SmolStr::new(format!(
+                "At most {MAX_ACTORS} actors can be requested at once"
+            ))),
+            nsid: GetProfiles::NSID,
+            method: "GET",
+            http_status: StatusCode::BAD_REQUEST,
+        })
+        .into());
+    }
     let actors: Vec<String> = request.actors.iter().map(|d| d.to_string()).collect();
+    // Non-active (deactivated, suspended or taken-down) accounts are treated
+    // as not found, whether or not the identity purge already ran.
     let account = query!(
         "SELECT did, handle, display_name, avatar_blob_cid, pronouns, indexed_at,
          (SELECT COUNT(*) FROM posts WHERE did = accounts.did) as \"post_count!\"
-         FROM accounts WHERE did = ANY($1) OR handle = ANY($1)",
+         FROM accounts WHERE (did = ANY($1) OR handle = ANY($1))
+         AND is_active AND status = 'active'",
         &actors
     )
     .fetch_all(state.database.executor())
     .await
-    .unwrap(); // TODO: Use Xrpc error.
+    .map_err(|err| AppError::database(GetProfiles::NSID, err))?;
+
+    let dids: Vec<String> = account.iter().map(|account| account.did.clone()).collect();
+    let labelers = super::super::accepted_labelers(&state, &headers);
+    let mut labels = super::super::actor_labels(&state, &dids, &labelers)
+        .await
+        .map_err(|err| AppError::database(GetProfiles::NSID, err))?;
+
+    // Build the profile views keyed by both DID and handle so the response
+    // can be reordered below to match the request, whichever form each entry
+    // used.
+    let mut views: HashMap<String, ProfileView> = HashMap::new();
+    for account in account {
+        let Ok(did) = account
+            .did
+            .parse::<Did>()
+            .inspect_err(|err| warn!("Malformed DID stored for account: {err:?}"))
+        else {
+            continue;
+        };
+        let handle = account.handle.clone();
+        let profile = ProfileView::new()
+            .did(did)
+            .handle(account.handle.and_then(|handle| {
+                handle
+                    .parse::<Handle>()
+                    .inspect_err(|err| warn!("Malformed handle stored for account: {err:?}"))
+                    .ok()
+            }))
+            .labels(labels.remove(&account.did))
+            .display_name(account.display_name.map(|s| s.into()))
+            .pronouns(account.pronouns.map(|pronouns| pronouns.into()))
+            .avatar(account.avatar_blob_cid.map(|blob_cid| {
+                Uri::new_owned(
+                    state
+                        .cdn_url
+                        .join(&format!("/avatar/{}/{}", account.did, blob_cid))
+                        .unwrap(),
+                )
+                .unwrap()
+            }))
+            .post_count(account.post_count)
+            .build();
+        if let Some(handle) = handle {
+            views.insert(handle, profile.clone());
+        }
+        views.insert(account.did, profile);
+    }
+
+    // Return the found profiles in request order, omitting missing ones (and
+    // repeats of an actor requested more than once).
+    let profiles: Vec<ProfileView> = actors
+        .iter()
+        .filter_map(|actor| views.remove(actor))
+        .collect();
 
     Ok(Json(GetProfilesOutput {
-        profiles: account
-            .into_iter()
-            .map(|account| {
-                ProfileView::new()
-                    .did(account.did.parse::<Did>().unwrap())
-                    .handle(
-                        account
-                            .handle
-                            .map(|handle| handle.parse::<Handle>().unwrap()),
-                    )
-                    .display_name(account.display_name.map(|s| s.into()))
-                    .pronouns(account.pronouns.map(|pronouns| pronouns.into()))
-                    .avatar(account.avatar_blob_cid.map(|blob_cid| {
-                        Uri::new_owned(
-                            state
-                                .cdn_url
-                                .join(&format!("/avatar/{}/{}", account.did, blob_cid))
-                                .unwrap(),
-                        )
-                        .unwrap()
-                    }))
-                    .post_count(account.post_count)
-                    .build()
-            })
-            .collect(),
+        profiles,
         extra_data: None,
     }))
 }
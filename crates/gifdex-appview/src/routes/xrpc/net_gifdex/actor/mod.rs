@@ -1,5 +1,7 @@
 mod get_profile;
 mod get_profiles;
+mod search_actors;
 
 pub use get_profile::*;
 pub use get_profiles::*;
+pub use search_actors::*;
@@ -0,0 +1,127 @@
+use crate::{AppState, routes::xrpc::AppError};
+use axum::{Json, extract::State, http::HeaderMap};
+use gifdex_lexicons::net_gifdex::actor::{
+    ProfileView,
+    search_actors::{SearchActors, SearchActorsError, SearchActorsOutput, SearchActorsRequest},
+};
+use jacquard_axum::{ExtractXrpc, XrpcErrorResponse, service_auth::ExtractOptionalServiceAuth};
+use jacquard_common::{
+    types::{did::Did, string::Handle, uri::Uri},
+    xrpc::{XrpcError, XrpcRequest},
+};
+use sqlx::query;
+use tracing::warn;
+
+pub async fn handle_search_actors(
+    State(state): State<AppState>,
+    ExtractOptionalServiceAuth(_auth): ExtractOptionalServiceAuth,
+    headers: HeaderMap,
+    ExtractXrpc(request): ExtractXrpc<SearchActorsRequest>,
+) -> Result<Json<SearchActorsOutput<'static>>, XrpcErrorResponse<SearchActorsError<'static>>> {
+    if request.q.trim().is_empty() {
+        return Err(XrpcError::Xrpc(SearchActorsError::InvalidQuery(Some(
+            "Search query must not be empty".into(),
+        )))
+        .into());
+    }
+
+    // Cursors are opaque tokens wrapping `{post_count}:{did}` of the last row
+    // of the previous page.
+    let (cursor_post_count, cursor_did) = match request.cursor.as_deref() {
+        Some(cursor) => {
+            let parsed =
+                super::super::feed::decode_cursor("post_count", cursor).and_then(|payload| {
+                    let (post_count, did) = payload.split_once(':')?;
+                    Some((post_count.parse::<i64>().ok()?, did.to_owned()))
+                });
+            let Some((post_count, did)) = parsed else {
+                return Err(XrpcError::Xrpc(SearchActorsError::InvalidQuery(Some(
+                    "Malformed cursor".into(),
+                )))
+                .into());
+            };
+            (Some(post_count), Some(did))
+        }
+        None => (None, None),
+    };
+
+    let limit = request.limit.unwrap_or(50).min(100);
+    // Substring match against handle and display name, ranked by how prolific
+    // the account is. Inactive and taken-down accounts never surface.
+    let accounts = query!(
+        "SELECT a.did, a.handle, a.display_name, a.avatar_blob_cid, a.pronouns, \
+            (SELECT COUNT(*) FROM posts WHERE did = a.did) as \"post_count!\" \
+         FROM accounts a \
+         WHERE (a.handle ILIKE '%' || $1 || '%' OR a.display_name ILIKE '%' || $1 || '%') \
+         AND a.is_active AND a.status = 'active' \
+         AND ($2::BIGINT IS NULL OR \
+            ((SELECT COUNT(*) FROM posts WHERE did = a.did), a.did) < ($2, $3)) \
+         ORDER BY (SELECT COUNT(*) FROM posts WHERE did = a.did) DESC, a.did DESC \
+         LIMIT $4",
+        request.q.as_ref(),
+        cursor_post_count,
+        cursor_did,
+        limit
+    )
+    .fetch_all(state.database.executor())
+    .await
+    .map_err(|err| AppError::database(SearchActors::NSID, err))?;
+
+    // Generate cursor if we have more accounts.
+    let cursor = if accounts.len() == limit as usize {
+        accounts.last().map(|account| {
+            super::super::feed::encode_cursor(
+                "post_count",
+                format!("{}:{}", account.post_count, account.did),
+            )
+        })
+    } else {
+        None
+    };
+
+    let dids: Vec<String> = accounts.iter().map(|account| account.did.clone()).collect();
+    let labelers = super::super::accepted_labelers(&state, &headers);
+    let mut labels = super::super::actor_labels(&state, &dids, &labelers)
+        .await
+        .map_err(|err| AppError::database(SearchActors::NSID, err))?;
+
+    Ok(Json(SearchActorsOutput {
+        actors: accounts
+            .into_iter()
+            .filter_map(|account| {
+                let did = account
+                    .did
+                    .parse::<Did>()
+                    .inspect_err(|err| warn!("Malformed DID stored for account: {err:?}"))
+                    .ok()?;
+                let profile = ProfileView::new()
+                    .did(did)
+                    .handle(account.handle.and_then(|handle| {
+                        handle
+                            .parse::<Handle>()
+                            .inspect_err(|err| {
+                                warn!("Malformed handle stored for account: {err:?}")
+                            })
+                            .ok()
+                    }))
+                    .labels(labels.remove(&account.did))
+                    .display_name(account.display_name.map(|s| s.into()))
+                    .pronouns(account.pronouns.map(|pronouns| pronouns.into()))
+                    .avatar(account.avatar_blob_cid.map(|blob_cid| {
+                        Uri::new_owned(
+                            state
+                                .cdn_url
+                                .join(&format!("/avatar/{}/{}", account.did, blob_cid))
+                                .unwrap(),
+                        )
+                        .unwrap()
+                    }))
+                    .post_count(account.post_count)
+                    .build();
+                Some(profile)
+            })
+            .collect(),
+        cursor: cursor.map(|cursor| cursor.into()),
+        extra_data: None,
+    }))
+}
@@ -0,0 +1,130 @@
+use crate::{AppState, routes::xrpc::AppError};
+use axum::{Json, extract::State, http::StatusCode, http::HeaderMap};
+use gifdex_lexicons::net_gifdex::feed::{
+    get_actor_favourites::{
+        GetActorFavourites, GetActorFavouritesError, GetActorFavouritesOutput,
+        GetActorFavouritesRequest,
+    },
+    post::Post,
+};
+use jacquard_axum::{ExtractXrpc, XrpcErrorResponse, service_auth::ExtractOptionalServiceAuth};
+use jacquard_common::{
+    smol_str::SmolStr,
+    types::collection::Collection,
+    xrpc::{GenericXrpcError, XrpcError, XrpcRequest},
+};
+use sqlx::query;
+
+pub async fn handle_get_actor_favourites(
+    State(state): State<AppState>,
+    ExtractOptionalServiceAuth(auth): ExtractOptionalServiceAuth,
+    headers: HeaderMap,
+    ExtractXrpc(request): ExtractXrpc<GetActorFavouritesRequest>,
+) -> Result<
+    Json<GetActorFavouritesOutput<'static>>,
+    XrpcErrorResponse<GetActorFavouritesError<'static>>,
+> {
+    let viewer_did = auth.as_ref().map(|a| a.did().as_str());
+    let limit = request.limit.unwrap_or(50).min(100);
+    // Cursors are opaque tokens wrapping the `created_at` of the last row of
+    // the previous page.
+    let cursor = match request.cursor.as_deref() {
+        Some(cursor) => match super::decode_cursor("created_at", cursor)
+            .and_then(|payload| payload.parse::<i64>().ok())
+        {
+            Some(created_at) => Some(created_at),
+            None => {
+                return Err(XrpcError::Generic(GenericXrpcError {
+                    error: SmolStr::new_static("InvalidRequest"),
+                    message: Some(SmolStr::new_static("Malformed cursor")),
+                    nsid: GetActorFavourites::NSID,
+                    method: "GET",
+                    http_status: StatusCode::BAD_REQUEST,
+                })
+                .into());
+            }
+        },
+        None => None,
+    };
+    // The INNER JOIN on posts skips favourites whose post has since been
+    // deleted.
+    let posts = query!(
+        "SELECT \
+            p.did, p.rkey, p.title, p.tags, p.languages, p.media_blob_cid, p.media_blob_mime, \
+            p.media_blob_alt, p.media_blob_width, p.media_blob_height, p.blurhash, p.created_at, \
+            p.edited_at, p.indexed_at as post_indexed_at, \
+            pf.created_at as fav_created_at, \
+            p.favourite_count, \
+            (SELECT vf.rkey \
+             FROM post_favourites vf \
+             WHERE vf.post_did = p.did AND vf.post_rkey = p.rkey AND vf.did = $4 \
+             LIMIT 1) as \"favourite_rkey\" \
+         FROM post_favourites pf \
+         INNER JOIN posts p ON p.did = pf.post_did AND p.rkey = pf.post_rkey \
+         WHERE pf.did = $1 AND ($2::BIGINT IS NULL OR pf.created_at < $2) \
+         AND EXISTS ( \
+            SELECT FROM accounts a \
+            WHERE a.did = pf.did AND a.is_active AND a.status = 'active') \
+         AND NOT EXISTS ( \
+            SELECT FROM labels l \
+            INNER JOIN labeler_rules r ON r.did = l.rule_did AND r.rkey = l.rule_rkey \
+            WHERE l.subject_did = p.did AND l.subject_rkey = p.rkey \
+                AND l.subject_collection = $5 AND r.takedown \
+                AND (l.expires_at IS NULL OR l.expires_at > (extract(epoch from now()) * 1000)::BIGINT)) \
+         ORDER BY pf.created_at DESC LIMIT $3",
+        request.actor.as_str(),
+        cursor,
+        limit,
+        viewer_did,
+        Post::NSID
+    )
+    .fetch_all(state.database.executor())
+    .await
+    .map_err(|err| AppError::database(GetActorFavourites::NSID, err))?;
+
+    // If no favourites found, check if the account exists.
+    if posts.is_empty() {
+        let account_exists = query!(
+            "SELECT 1 as exists FROM accounts WHERE did = $1",
+            request.actor.as_str()
+        )
+        .fetch_optional(state.database.executor())
+        .await
+        .map_err(|err| AppError::database(GetActorFavourites::NSID, err))?;
+        if account_exists.is_none() {
+            return Err(XrpcError::Xrpc(GetActorFavouritesError::ActorNotFound(None)).into());
+        }
+    }
+
+    // Generate cursor if we have more favourites.
+    let cursor = super::next_created_at_cursor(&posts, limit, |post| post.fav_created_at);
+
+    let keys: Vec<(String, String)> = posts
+        .iter()
+        .map(|post| (post.did.clone(), post.rkey.clone()))
+        .collect();
+    let labelers = super::super::accepted_labelers(&state, &headers);
+    let labels = super::super::post_labels(&state, &keys, &labelers)
+        .await
+        .map_err(|err| AppError::database(GetActorFavourites::NSID, err))?;
+
+    let mut author_dids: Vec<String> = posts.iter().map(|post| post.did.clone()).collect();
+    author_dids.sort();
+    author_dids.dedup();
+    let authors = super::super::author_profiles(&state, &author_dids)
+        .await
+        .map_err(|err| AppError::database(GetActorFavourites::NSID, err))?;
+
+    let post_views = super::hydrate_post_views(
+        &state,
+        posts.into_iter().map(|post| super::post_row!(post)).collect(),
+        labels,
+        &authors,
+    );
+
+    Ok(Json(GetActorFavouritesOutput {
+        feed: post_views,
+        cursor: cursor.map(|cursor| cursor.into()),
+        extra_data: None,
+    }))
+}
@@ -0,0 +1,164 @@
+use crate::{AppState, routes::xrpc::AppError};
+use axum::{Json, extract::State, http::StatusCode};
+use gifdex_lexicons::net_gifdex::feed::{
+    SkeletonFeedPost,
+    get_feed_skeleton::{
+        GetFeedSkeleton, GetFeedSkeletonError, GetFeedSkeletonOutput, GetFeedSkeletonRequest,
+    },
+    post::Post,
+};
+use jacquard_axum::{ExtractXrpc, XrpcErrorResponse, service_auth::ExtractOptionalServiceAuth};
+use jacquard_common::{
+    smol_str::SmolStr,
+    types::{aturi::AtUri, collection::Collection},
+    xrpc::{GenericXrpcError, XrpcError, XrpcRequest},
+};
+use sqlx::query;
+use tracing::warn;
+
+/// Window in milliseconds that favourites count towards the `trending`
+/// skeleton's ranking. Matches `getTrending`'s default window.
+const TRENDING_WINDOW_MILLIS: i64 = 48 * 60 * 60 * 1000;
+
+/// Serve URI-only feed skeletons for the server-side feed algorithms,
+/// deferring hydration to the client via `getPosts`.
+pub async fn handle_get_feed_skeleton(
+    State(state): State<AppState>,
+    ExtractOptionalServiceAuth(_auth): ExtractOptionalServiceAuth,
+    ExtractXrpc(request): ExtractXrpc<GetFeedSkeletonRequest>,
+) -> Result<Json<GetFeedSkeletonOutput<'static>>, XrpcErrorResponse<GetFeedSkeletonError<'static>>>
+{
+    let limit = request.limit.unwrap_or(50).min(100);
+
+    let (keys, cursor): (Vec<(String, String)>, Option<String>) = match request.feed.as_ref() {
+        // Posts ranked by how many favourites they received inside the
+        // window, exactly like `getTrending` with its default window.
+        "trending" => {
+            let (cursor_score, cursor_created_at) = match request.cursor.as_deref() {
+                Some(cursor) => {
+                    let parsed = super::decode_cursor("score", cursor).and_then(|payload| {
+                        let (score, created_at) = payload.split_once(':')?;
+                        Some((score.parse::<i64>().ok()?, created_at.parse::<i64>().ok()?))
+                    });
+                    let Some((score, created_at)) = parsed else {
+                        return Err(malformed_cursor());
+                    };
+                    (Some(score), Some(created_at))
+                }
+                None => (None, None),
+            };
+            let posts = query!(
+                "SELECT p.did, p.rkey, COUNT(*) as \"score!\", p.created_at \
+                 FROM post_favourites f \
+                 INNER JOIN posts p ON p.did = f.post_did AND p.rkey = f.post_rkey \
+                 WHERE f.created_at > (extract(epoch from now()) * 1000)::BIGINT - $1 \
+                 AND NOT EXISTS ( \
+                    SELECT FROM labels l \
+                    INNER JOIN labeler_rules r ON r.did = l.rule_did AND r.rkey = l.rule_rkey \
+                    WHERE l.subject_did = p.did AND l.subject_rkey = p.rkey \
+                        AND l.subject_collection = $5 AND r.takedown \
+                        AND (l.expires_at IS NULL OR l.expires_at > (extract(epoch from now()) * 1000)::BIGINT)) \
+                 GROUP BY p.did, p.rkey \
+                 HAVING ($2::BIGINT IS NULL OR (COUNT(*), p.created_at) < ($2, $3)) \
+                 ORDER BY COUNT(*) DESC, p.created_at DESC \
+                 LIMIT $4",
+                TRENDING_WINDOW_MILLIS,
+                cursor_score,
+                cursor_created_at,
+                limit,
+                Post::NSID
+            )
+            .fetch_all(state.database.executor())
+            .await
+            .map_err(|err| AppError::database(GetFeedSkeleton::NSID, err))?;
+
+            let cursor = if posts.len() == limit as usize {
+                posts.last().map(|post| {
+                    super::encode_cursor("score", format!("{}:{}", post.score, post.created_at))
+                })
+            } else {
+                None
+            };
+            (
+                posts
+                    .into_iter()
+                    .map(|post| (post.did, post.rkey))
+                    .collect(),
+                cursor,
+            )
+        }
+        // Posts in reverse chronological order.
+        "latest" => {
+            let cursor = match request.cursor.as_deref() {
+                Some(cursor) => match super::decode_cursor("created_at", cursor)
+                    .and_then(|payload| payload.parse::<i64>().ok())
+                {
+                    Some(created_at) => Some(created_at),
+                    None => return Err(malformed_cursor()),
+                },
+                None => None,
+            };
+            let posts = query!(
+                "SELECT p.did, p.rkey, p.created_at \
+                 FROM posts p \
+                 WHERE ($1::BIGINT IS NULL OR p.created_at < $1) \
+                 AND EXISTS ( \
+                    SELECT FROM accounts a \
+                    WHERE a.did = p.did AND a.is_active AND a.status = 'active') \
+                 AND NOT EXISTS ( \
+                    SELECT FROM labels l \
+                    INNER JOIN labeler_rules r ON r.did = l.rule_did AND r.rkey = l.rule_rkey \
+                    WHERE l.subject_did = p.did AND l.subject_rkey = p.rkey \
+                        AND l.subject_collection = $3 AND r.takedown \
+                        AND (l.expires_at IS NULL OR l.expires_at > (extract(epoch from now()) * 1000)::BIGINT)) \
+                 ORDER BY p.created_at DESC \
+                 LIMIT $2",
+                cursor,
+                limit,
+                Post::NSID
+            )
+            .fetch_all(state.database.executor())
+            .await
+            .map_err(|err| AppError::database(GetFeedSkeleton::NSID, err))?;
+
+            let cursor = super::next_created_at_cursor(&posts, limit, |post| post.created_at);
+            (
+                posts
+                    .into_iter()
+                    .map(|post| (post.did, post.rkey))
+                    .collect(),
+                cursor,
+            )
+        }
+        _ => {
+            return Err(XrpcError::Xrpc(GetFeedSkeletonError::UnknownFeed(None)).into());
+        }
+    };
+
+    let feed: Vec<SkeletonFeedPost> = keys
+        .into_iter()
+        .filter_map(|(did, rkey)| {
+            let uri = AtUri::new_owned(format!("at://{did}/{}/{rkey}", Post::NSID))
+                .inspect_err(|err| warn!("Malformed at-uri components stored for post: {err:?}"))
+                .ok()?;
+            Some(SkeletonFeedPost::new().post(uri).build())
+        })
+        .collect();
+
+    Ok(Json(GetFeedSkeletonOutput {
+        feed,
+        cursor: cursor.map(|cursor| cursor.into()),
+        extra_data: None,
+    }))
+}
+
+fn malformed_cursor() -> XrpcErrorResponse<GetFeedSkeletonError<'static>> {
+    XrpcError::Generic(GenericXrpcError {
+        error: SmolStr::new_static("InvalidRequest"),
+        message: Some(SmolStr::new_static("Malformed cursor")),
+        nsid: GetFeedSkeleton::NSID,
+        method: "GET",
+        http_status: StatusCode::BAD_REQUEST,
+    })
+    .into()
+}
@@ -1,10 +1,10 @@
-use crate::AppState;
+use crate::{AppState, routes::xrpc::AppError};
 use axum::{Json, extract::State};
 use gifdex_lexicons::net_gifdex::{
     actor::ProfileViewBasic,
     feed::{
         self, PostView, PostViewMedia, PostViewMediaDimensions,
-        get_post::{GetPostError, GetPostOutput, GetPostRequest},
+        get_post::{GetPost, GetPostError, GetPostOutput, GetPostRequest},
     },
 };
 use jacquard_axum::{ExtractXrpc, XrpcErrorResponse, service_auth::ExtractOptionalServiceAuth};
@@ -12,9 +12,10 @@ use jacquard_common::{
     IntoStatic,
     chrono::{TimeZone, Utc},
     types::{aturi::AtUri, string::Handle, tid::Tid, uri::Uri},
-    xrpc::XrpcError,
+    xrpc::{XrpcError, XrpcRequest},
 };
 use sqlx::query;
+use tracing::warn;
 
 pub async fn handle_get_post(
     State(state): State<AppState>,
@@ -26,10 +27,9 @@ pub async fn handle_get_post(
         "SELECT  \
             a.did, a.display_name, a.handle, a.avatar_blob_cid, a.indexed_at as account_indexed_at, \
             p.rkey, p.title, p.tags, p.languages, p.media_blob_cid, p.media_blob_mime, \
-            p.media_blob_alt, p.created_at, p.edited_at, p.indexed_at as post_indexed_at, \
+            p.media_blob_alt, p.blurhash, p.created_at, p.edited_at, p.indexed_at as post_indexed_at, \
             p.media_blob_width, p.media_blob_height, \
-            (SELECT COUNT(*) FROM post_favourites \
-            WHERE post_did = p.did AND post_rkey = p.rkey) as \"favourite_count!\", \
+            p.favourite_count, \
             (SELECT pf.rkey \
             FROM post_favourites pf \
             WHERE pf.post_did = p.did AND pf.post_rkey = p.rkey AND pf.did = $3 \
@@ -43,7 +43,7 @@ pub async fn handle_get_post(
     )
     .fetch_optional(state.database.executor())
     .await
-    .unwrap(); // TODO: Use Xrpc error.
+    .map_err(|err| AppError::database(GetPost::NSID, err))?;
 
     let Some(result) = result else {
         return Err(XrpcError::Xrpc(GetPostError::PostNotFound(None)).into());
@@ -54,7 +54,7 @@ pub async fn handle_get_post(
         "at://{}/net.gifdex.feed.post/{}",
         result.did, result.rkey
     ))
-    .unwrap();
+    .map_err(|err| AppError::data(GetPost::NSID, err))?;
     let post_view = PostView::new()
         .uri(uri)
         .title(result.title.into_static())
@@ -70,26 +70,11 @@ pub async fn handle_get_post(
         )
         .media(
             PostViewMedia::new()
-                .fullsize_url(
-                    Uri::new_owned(
-                        state
-                            .cdn_url
-                            .join(&format!("/media/{}/{}", result.did, result.rkey))
-                            .unwrap(),
-                    )
-                    .unwrap(),
-                )
-                .thumbnail_url(
-                    Uri::new_owned(
-                        state
-                            .cdn_url
-                            .join(&format!("/media/{}/{}", result.did, result.rkey))
-                            .unwrap(),
-                    )
-                    .unwrap(),
-                )
+                .fullsize_url(super::super::media_url(&state, &result.did, &result.rkey))
+                .thumbnail_url(super::super::media_url(&state, &result.did, &result.rkey))
                 .mime_type(result.media_blob_mime.into_static())
                 .alt(result.media_blob_alt.map(|s| s.into()))
+                .blurhash(result.blurhash.map(|s| s.into()))
                 .dimensions(
                     PostViewMediaDimensions::new()
                         .height(result.media_blob_height)
@@ -102,11 +87,12 @@ pub async fn handle_get_post(
         .author(
             ProfileViewBasic::new()
                 .did(request.actor.clone())
-                .handle(
-                    result
-                        .handle
-                        .map(|handle| handle.parse::<Handle>().unwrap()),
-                )
+                .handle(result.handle.and_then(|handle| {
+                    handle
+                        .parse::<Handle>()
+                        .inspect_err(|err| warn!("Malformed handle stored for account: {err:?}"))
+                        .ok()
+                }))
                 .display_name(result.display_name.map(|s| s.into()))
                 .avatar(result.avatar_blob_cid.map(|blob_cid| {
                     Uri::new_owned(
@@ -123,7 +109,7 @@ pub async fn handle_get_post(
             favourite: result
                 .favourite_rkey
                 .as_ref()
-                .map(|uri| uri.parse::<Tid>().unwrap()),
+                .and_then(|rkey| rkey.parse::<Tid>().ok()),
             ..Default::default()
         })
         .created_at(
@@ -131,6 +117,12 @@ pub async fn handle_get_post(
                 .unwrap()
                 .fixed_offset(),
         )
+        .maybe_edited_at(result.edited_at.map(|edited_at| {
+            Utc.timestamp_millis_opt(edited_at)
+                .unwrap()
+                .fixed_offset()
+                .into()
+        }))
         .indexed_at(
             Utc.timestamp_millis_opt(result.post_indexed_at)
                 .unwrap()
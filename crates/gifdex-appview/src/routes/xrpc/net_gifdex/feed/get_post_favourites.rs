@@ -0,0 +1,119 @@
+use crate::{AppState, routes::xrpc::AppError};
+use axum::{Json, extract::State, http::StatusCode};
+use gifdex_lexicons::net_gifdex::{
+    actor::ProfileViewBasic,
+    feed::get_post_favourites::{
+        GetPostFavourites, GetPostFavouritesError, GetPostFavouritesOutput,
+        GetPostFavouritesRequest,
+    },
+};
+use jacquard_axum::{ExtractXrpc, XrpcErrorResponse, service_auth::ExtractOptionalServiceAuth};
+use jacquard_common::{
+    smol_str::SmolStr,
+    types::{did::Did, string::Handle, uri::Uri},
+    xrpc::{GenericXrpcError, XrpcError, XrpcRequest},
+};
+use sqlx::query;
+use tracing::warn;
+
+pub async fn handle_get_post_favourites(
+    State(state): State<AppState>,
+    ExtractOptionalServiceAuth(_auth): ExtractOptionalServiceAuth,
+    ExtractXrpc(request): ExtractXrpc<GetPostFavouritesRequest>,
+) -> Result<
+    Json<GetPostFavouritesOutput<'static>>,
+    XrpcErrorResponse<GetPostFavouritesError<'static>>,
+> {
+    let limit = request.limit.unwrap_or(50).min(100);
+    // Cursors are opaque tokens wrapping the `created_at` of the last row of
+    // the previous page.
+    let cursor = match request.cursor.as_deref() {
+        Some(cursor) => match super::decode_cursor("created_at", cursor)
+            .and_then(|payload| payload.parse::<i64>().ok())
+        {
+            Some(created_at) => Some(created_at),
+            None => {
+                return Err(XrpcError::Generic(GenericXrpcError {
+                    error: SmolStr::new_static("InvalidRequest"),
+                    message: Some(SmolStr::new_static("Malformed cursor")),
+                    nsid: GetPostFavourites::NSID,
+                    method: "GET",
+                    http_status: StatusCode::BAD_REQUEST,
+                })
+                .into());
+            }
+        },
+        None => None,
+    };
+    let favourites = query!(
+        "SELECT \
+            a.did, a.display_name, a.handle, a.avatar_blob_cid, pf.created_at \
+         FROM post_favourites pf \
+         INNER JOIN accounts a ON a.did = pf.did \
+         WHERE pf.post_did = $1 AND pf.post_rkey = $2 \
+         AND ($3::BIGINT IS NULL OR pf.created_at < $3) \
+         ORDER BY pf.created_at DESC LIMIT $4",
+        request.actor.as_str(),
+        request.rkey.as_ref(),
+        cursor,
+        limit
+    )
+    .fetch_all(state.database.executor())
+    .await
+    .map_err(|err| AppError::database(GetPostFavourites::NSID, err))?;
+
+    // If no favourites found, check if the post exists.
+    if favourites.is_empty() {
+        let post_exists = query!(
+            "SELECT 1 as exists FROM posts WHERE did = $1 AND rkey = $2",
+            request.actor.as_str(),
+            request.rkey.as_ref()
+        )
+        .fetch_optional(state.database.executor())
+        .await
+        .map_err(|err| AppError::database(GetPostFavourites::NSID, err))?;
+        if post_exists.is_none() {
+            return Err(XrpcError::Xrpc(GetPostFavouritesError::PostNotFound(None)).into());
+        }
+    }
+
+    // Generate cursor if we have more favourites.
+    let cursor =
+        super::next_created_at_cursor(&favourites, limit, |favourite| favourite.created_at);
+
+    let favourited_by: Vec<ProfileViewBasic> = favourites
+        .into_iter()
+        .filter_map(|favourite| {
+            let did = favourite
+                .did
+                .parse::<Did>()
+                .inspect_err(|err| warn!("Malformed DID stored for account: {err:?}"))
+                .ok()?;
+            let profile = ProfileViewBasic::new()
+                .did(did)
+                .handle(favourite.handle.and_then(|handle| {
+                    Handle::new_owned(handle)
+                        .inspect_err(|err| warn!("Malformed handle stored for account: {err:?}"))
+                        .ok()
+                }))
+                .display_name(favourite.display_name.map(|s| s.into()))
+                .avatar(favourite.avatar_blob_cid.map(|blob_cid| {
+                    Uri::new_owned(
+                        state
+                            .cdn_url
+                            .join(&format!("/avatar/{}/{}", favourite.did, blob_cid))
+                            .unwrap(),
+                    )
+                    .unwrap()
+                }))
+                .build();
+            Some(profile)
+        })
+        .collect();
+
+    Ok(Json(GetPostFavouritesOutput {
+        favourited_by,
+        cursor: cursor.map(|cursor| cursor.into()),
+        extra_data: None,
+    }))
+}
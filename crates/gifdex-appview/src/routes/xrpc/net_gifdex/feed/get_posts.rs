@@ -0,0 +1,117 @@
+use crate::{AppState, routes::xrpc::AppError};
+use axum::{Json, extract::State, http::HeaderMap, http::StatusCode};
+use gifdex_lexicons::net_gifdex::feed::{
+    PostFeedView,
+    get_posts::{GetPosts, GetPostsOutput, GetPostsRequest},
+    post::Post,
+};
+use jacquard_axum::{ExtractXrpc, XrpcErrorResponse, service_auth::ExtractOptionalServiceAuth};
+use jacquard_common::{
+    smol_str::SmolStr,
+    types::{collection::Collection, ident::AtIdentifier},
+    xrpc::{GenericXrpcError, XrpcError, XrpcRequest},
+};
+use sqlx::query;
+use std::collections::HashMap;
+
+/// Maximum number of URIs accepted per request. Matches the
+/// `net.gifdex.feed.getPosts` lexicon bound.
+const MAX_URIS: usize = 25;
+
+pub async fn handle_get_posts(
+    State(state): State<AppState>,
+    ExtractOptionalServiceAuth(auth): ExtractOptionalServiceAuth,
+    headers: HeaderMap,
+    ExtractXrpc(request): ExtractXrpc<GetPostsRequest>,
+) -> Result<Json<GetPostsOutput<'static>>, XrpcErrorResponse<GenericXrpcError>> {
+    if request.uris.len() > MAX_URIS {
+        return Err(XrpcError::Generic(GenericXrpcError {
+            error: SmolStr::new_static("InvalidRequest"),
+            message: Some(SmolStr::new(format!(
+                "At most {MAX_URIS} uris can be requested at once"
+            ))),
+            nsid: GetPosts::NSID,
+            method: "GET",
+            http_status: StatusCode::BAD_REQUEST,
+        })
+        .into());
+    }
+    let viewer_did = auth.as_ref().map(|a| a.did().as_str());
+
+    // Parse each AT-URI into its `(did, rkey)` pair. URIs for other
+    // collections, with missing components or with a handle authority are
+    // treated the same as posts we don't have: omitted from the response.
+    let keys: Vec<(String, String)> = request
+        .uris
+        .iter()
+        .filter_map(|uri| {
+            let AtIdentifier::Did(did) = uri.authority() else {
+                return None;
+            };
+            let collection = uri.collection()?;
+            if collection.as_str() != Post::NSID {
+                return None;
+            }
+            let rkey = uri.rkey()?;
+            Some((did.as_str().to_owned(), rkey.0.as_str().to_owned()))
+        })
+        .collect();
+
+    let dids: Vec<String> = keys.iter().map(|(did, _)| did.clone()).collect();
+    let rkeys: Vec<String> = keys.iter().map(|(_, rkey)| rkey.clone()).collect();
+    let posts = query!(
+        "SELECT \
+            p.did, p.rkey, p.title, p.tags, p.languages, p.media_blob_cid, p.media_blob_mime, \
+            p.media_blob_alt, p.media_blob_width, p.media_blob_height, p.blurhash, p.created_at, \
+            p.edited_at, p.indexed_at as post_indexed_at, \
+            p.favourite_count, \
+            (SELECT pf.rkey \
+             FROM post_favourites pf \
+             WHERE pf.post_did = p.did AND pf.post_rkey = p.rkey AND pf.did = $3 \
+             LIMIT 1) as \"favourite_rkey\" \
+         FROM posts p \
+         WHERE (p.did, p.rkey) IN (SELECT * FROM unnest($1::TEXT[], $2::TEXT[]))",
+        &dids,
+        &rkeys,
+        viewer_did
+    )
+    .fetch_all(state.database.executor())
+    .await
+    .map_err(|err| AppError::database(GetPosts::NSID, err))?;
+
+    let labelers = super::super::accepted_labelers(&state, &headers);
+    let mut labels = super::super::post_labels(&state, &keys, &labelers)
+        .await
+        .map_err(|err| AppError::database(GetPosts::NSID, err))?;
+
+    let mut author_dids: Vec<String> = posts.iter().map(|post| post.did.clone()).collect();
+    author_dids.sort();
+    author_dids.dedup();
+    let authors = super::super::author_profiles(&state, &author_dids)
+        .await
+        .map_err(|err| AppError::database(GetPosts::NSID, err))?;
+
+    // Build post views keyed by `(did, rkey)` so they can be returned in
+    // request order below.
+    let mut views: HashMap<(String, String), PostFeedView> = posts
+        .into_iter()
+        .filter_map(|post| {
+            let key = (post.did.clone(), post.rkey.clone());
+            let view =
+                super::hydrate_post_view(&state, super::post_row!(post), &mut labels, &authors)?;
+            Some((key, view))
+        })
+        .collect();
+
+    // Return the found posts in request order, omitting missing ones (and
+    // repeats of a URI requested more than once).
+    let post_views: Vec<PostFeedView> = keys
+        .iter()
+        .filter_map(|key| views.remove(key))
+        .collect();
+
+    Ok(Json(GetPostsOutput {
+        posts: post_views,
+        extra_data: None,
+    }))
+}
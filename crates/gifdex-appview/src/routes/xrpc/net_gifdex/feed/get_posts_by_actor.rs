@@ -1,169 +1,194 @@
-use crate::AppState;
-use axum::{Json, extract::State};
-use gifdex_lexicons::net_gifdex::{
-    actor::ProfileViewBasic,
-    feed::{
-        self, PostFeedView, PostViewMedia, PostViewMediaDimensions,
-        get_posts_by_actor::{GetPostsByActorError, GetPostsByActorOutput, GetPostsByActorRequest},
-        post::Post,
+use crate::{AppState, routes::xrpc::AppError};
+use axum::{Json, extract::State, http::StatusCode, http::HeaderMap};
+use gifdex_lexicons::net_gifdex::feed::{
+    get_posts_by_actor::{
+        GetPostsByActor, GetPostsByActorError, GetPostsByActorOutput, GetPostsByActorRequest,
     },
+    post::Post,
 };
 use jacquard_axum::{ExtractXrpc, XrpcErrorResponse, service_auth::ExtractOptionalServiceAuth};
 use jacquard_common::{
-    IntoStatic,
-    chrono::{TimeZone, Utc},
-    types::{aturi::AtUri, collection::Collection, string::Handle, tid::Tid, uri::Uri},
-    xrpc::XrpcError,
+    smol_str::SmolStr,
+    types::collection::Collection,
+    xrpc::{GenericXrpcError, XrpcError, XrpcRequest},
 };
 use sqlx::query;
 
 pub async fn handle_get_posts_by_actor(
     State(state): State<AppState>,
     ExtractOptionalServiceAuth(auth): ExtractOptionalServiceAuth,
+    headers: HeaderMap,
     ExtractXrpc(request): ExtractXrpc<GetPostsByActorRequest>,
 ) -> Result<Json<GetPostsByActorOutput<'static>>, XrpcErrorResponse<GetPostsByActorError<'static>>>
 {
     let viewer_did = auth.as_ref().map(|a| a.did().as_str());
-    let limit = request.limit.unwrap_or(50).min(100) as i64;
+    let limit = request.limit.unwrap_or(50).min(100);
+    let reverse = request.reverse.unwrap_or(false);
+    // Cursors are opaque tokens wrapping the `created_at` of the last row of
+    // the previous page.
+    let cursor = match request.cursor.as_deref() {
+        Some(cursor) => match super::decode_cursor("created_at", cursor)
+            .and_then(|payload| payload.parse::<i64>().ok())
+        {
+            Some(created_at) => Some(created_at),
+            None => {
+                return Err(XrpcError::Generic(GenericXrpcError {
+                    error: SmolStr::new_static("InvalidRequest"),
+                    message: Some(SmolStr::new_static("Malformed cursor")),
+                    nsid: GetPostsByActor::NSID,
+                    method: "GET",
+                    http_status: StatusCode::BAD_REQUEST,
+                })
+                .into());
+            }
+        },
+        None => None,
+    };
+
+    // `actor` is an at-identifier: resolve handles to the account's DID up
+    // front so both forms work interchangeably. A non-active account still
+    // resolves here, so its feed comes back empty rather than erroring.
+    let actor = query!(
+        "SELECT did FROM accounts WHERE did = $1 OR handle = $1",
+        request.actor.as_str()
+    )
+    .fetch_optional(state.database.executor())
+    .await
+    .map_err(|err| AppError::database(GetPostsByActor::NSID, err))?;
+    let Some(actor) = actor else {
+        return Err(XrpcError::Xrpc(GetPostsByActorError::ActorNotFound(None)).into());
+    };
+
     let posts = query!(
         "SELECT \
-            a.did, a.display_name, a.handle, a.avatar_blob_cid, a.indexed_at as account_indexed_at, \
-            p.rkey, p.title, p.tags, p.languages, p.media_blob_cid, p.media_blob_mime, \
-            p.media_blob_alt, p.media_blob_width, p.media_blob_height, p.created_at, \
-            p.edited_at, p.indexed_at as post_indexed_at, \
-            (SELECT COUNT(*) FROM post_favourites \
-             WHERE post_did = p.did AND post_rkey = p.rkey) as \"favourite_count!\", \
+            p.did, p.rkey, p.title, p.tags, p.languages, p.media_blob_cid, p.media_blob_mime, \
+            p.media_blob_alt, p.media_blob_width, p.media_blob_height, p.blurhash, p.created_at, \
+            p.edited_at, p.indexed_at as post_indexed_at, p.favourite_count, \
             (SELECT pf.rkey \
              FROM post_favourites pf \
              WHERE pf.post_did = p.did AND pf.post_rkey = p.rkey AND pf.did = $4 \
              LIMIT 1) as \"favourite_rkey\" \
-         FROM accounts a \
-         INNER JOIN posts p ON a.did = p.did \
-         WHERE a.did = $1 AND ($2::BIGINT IS NULL OR p.created_at < $2) \
-         ORDER BY p.created_at DESC LIMIT $3",
-        request.actor.as_str(),
-        request.cursor,
+         FROM posts p \
+         WHERE p.did = $1 AND ($2::BIGINT IS NULL OR \
+            CASE WHEN $6 THEN p.created_at > $2 ELSE p.created_at < $2 END) \
+         AND EXISTS ( \
+            SELECT FROM accounts a \
+            WHERE a.did = p.did AND a.is_active AND a.status = 'active') \
+         AND NOT EXISTS ( \
+            SELECT FROM labels l \
+            INNER JOIN labeler_rules r ON r.did = l.rule_did AND r.rkey = l.rule_rkey \
+            WHERE l.subject_did = p.did AND l.subject_rkey = p.rkey \
+                AND l.subject_collection = $5 AND r.takedown \
+                AND (l.expires_at IS NULL OR l.expires_at > (extract(epoch from now()) * 1000)::BIGINT)) \
+         ORDER BY CASE WHEN $6 THEN p.created_at END ASC, \
+            CASE WHEN NOT $6 THEN p.created_at END DESC \
+         LIMIT $3",
+        actor.did,
+        cursor,
         limit,
-        viewer_did
+        viewer_did,
+        Post::NSID,
+        reverse
     )
     .fetch_all(state.database.executor())
     .await
-    .unwrap(); // TODO: Use Xrpc error
-
-    // If no posts found, check if the account exists.
-    if posts.is_empty() {
-        let account_exists = query!(
-            "SELECT 1 as exists FROM accounts WHERE did = $1",
-            request.actor.as_str()
-        )
-        .fetch_optional(state.database.executor())
-        .await
-        .unwrap();
-        if account_exists.is_none() {
-            return Err(XrpcError::Xrpc(GetPostsByActorError::ActorNotFound(None)).into());
-        }
-    }
+    .map_err(|err| AppError::database(GetPostsByActor::NSID, err))?;
 
     // Generate cursor if we have more posts.
-    let cursor = if posts.len() == limit as usize {
-        posts.last().map(|post| post.created_at)
-    } else {
-        None
-    };
-
-    // Build post views (if we have any posts)
-    let post_views: Vec<PostFeedView> = posts
-        .into_iter()
-        .map(|post| {
-            // Build the profile view from the joined account data
-            let profile = ProfileViewBasic::new()
-                .did(request.actor.clone())
-                .handle(
-                    post.handle
-                        .clone()
-                        .map(|handle| Handle::new_owned(handle).unwrap()),
-                )
-                .display_name(post.display_name.clone().map(|s| s.into()))
-                .avatar(post.avatar_blob_cid.clone().map(|blob_cid| {
-                    Uri::new_owned(
-                        state
-                            .cdn_url
-                            .join(&format!("/avatar/{}/{}", post.did, blob_cid))
-                            .unwrap(),
-                    )
-                    .unwrap()
-                }))
-                .build();
+    let cursor = super::next_created_at_cursor(&posts, limit, |post| post.created_at);
 
-            let uri = AtUri::new_owned(format!("at://{}/{}/{}", post.did, Post::NSID, post.rkey))
-                .unwrap();
-            PostFeedView::new()
-                .uri(uri)
-                .title(post.title.into_static())
-                .tags(
-                    post.tags
-                        .map(|tags| tags.into_iter().map(|t| t.into()).collect()),
-                )
-                .languages(
-                    post.languages
-                        .map(|langs| langs.into_iter().map(|l| l.into()).collect()),
-                )
-                .media(
-                    PostViewMedia::new()
-                        .fullsize_url(
-                            Uri::new_owned(
-                                state
-                                    .cdn_url
-                                    .join(&format!("/media/{}/{}", post.did, post.rkey))
-                                    .unwrap(),
-                            )
-                            .unwrap(),
-                        )
-                        .thumbnail_url(
-                            Uri::new_owned(
-                                state
-                                    .cdn_url
-                                    .join(&format!("/media/{}/{}", post.did, post.rkey))
-                                    .unwrap(),
-                            )
-                            .unwrap(),
-                        )
-                        .mime_type(post.media_blob_mime.into_static())
-                        .alt(post.media_blob_alt.map(|s| s.into()))
-                        .dimensions(
-                            PostViewMediaDimensions::new()
-                                .height(post.media_blob_height)
-                                .width(post.media_blob_width)
-                                .build(),
-                        )
-                        .build(),
-                )
-                .favourite_count(post.favourite_count)
-                .author(profile)
-                .viewer(feed::ViewerState {
-                    favourite: post
-                        .favourite_rkey
-                        .as_ref()
-                        .map(|uri| Tid::new(uri.clone()).unwrap()),
-                    ..Default::default()
-                })
-                .created_at(
-                    Utc.timestamp_millis_opt(post.created_at)
-                        .unwrap()
-                        .fixed_offset(),
-                )
-                .indexed_at(
-                    Utc.timestamp_millis_opt(post.post_indexed_at)
-                        .unwrap()
-                        .fixed_offset(),
-                )
-                .build()
-        })
+    let keys: Vec<(String, String)> = posts
+        .iter()
+        .map(|post| (post.did.clone(), post.rkey.clone()))
         .collect();
+    let labelers = super::super::accepted_labelers(&state, &headers);
+    let labels = super::super::post_labels(&state, &keys, &labelers)
+        .await
+        .map_err(|err| AppError::database(GetPostsByActor::NSID, err))?;
+
+    let mut author_dids: Vec<String> = posts.iter().map(|post| post.did.clone()).collect();
+    author_dids.sort();
+    author_dids.dedup();
+    let authors = super::super::author_profiles(&state, &author_dids)
+        .await
+        .map_err(|err| AppError::database(GetPostsByActor::NSID, err))?;
+
+    let post_views = super::hydrate_post_views(
+        &state,
+        posts.into_iter().map(|post| super::post_row!(post)).collect(),
+        labels,
+        &authors,
+    );
 
     Ok(Json(GetPostsByActorOutput {
         feed: post_views,
-        cursor,
+        cursor: cursor.map(|cursor| cursor.into()),
         extra_data: None,
     }))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+    use jacquard_common::types::ident::AtIdentifier;
+
+    const AUTHOR: &str = "did:plc:ewvi7nxzyoun6zhxrhs64oiz";
+
+    /// End-to-end read over the real schema: posts come back newest-first
+    /// with their stored millisecond timestamps surfaced, and an unknown
+    /// actor is a typed `ActorNotFound`.
+    #[tokio::test]
+    #[ignore = "requires a container runtime"]
+    async fn get_posts_by_actor_pages_newest_first() {
+        let (_postgres, state) = test_support::test_state().await;
+        let executor = state.database.executor();
+        gifdex_testing::seed_account(executor, AUTHOR, Some("author.test"), 1_700_000_000_000)
+            .await;
+        let older = format!("3jzfcijpj2z2a:{}", gifdex_testing::TEST_CID);
+        let newer = format!("3jzfcijpj2z2b:{}", gifdex_testing::TEST_CID);
+        gifdex_testing::seed_post(executor, AUTHOR, &older, "Older", 1_700_000_000_000).await;
+        gifdex_testing::seed_post(executor, AUTHOR, &newer, "Newer", 1_700_000_100_000).await;
+
+        let request = GetPostsByActor::new()
+            .actor(AtIdentifier::new_static(AUTHOR).unwrap())
+            .build();
+        let response = handle_get_posts_by_actor(
+            State(state.clone()),
+            ExtractOptionalServiceAuth(None),
+            HeaderMap::new(),
+            ExtractXrpc(request),
+        )
+        .await
+        .expect("handler failed")
+        .0;
+
+        let titles: Vec<&str> = response
+            .feed
+            .iter()
+            .map(|post| post.title.as_ref())
+            .collect();
+        assert_eq!(titles, ["Newer", "Older"]);
+        assert_eq!(
+            response.feed[0].created_at.as_ref().timestamp_millis(),
+            1_700_000_100_000
+        );
+        assert!(response.cursor.is_none());
+
+        let request = GetPostsByActor::new()
+            .actor(AtIdentifier::new_static("did:plc:yk4dd2qkboz2yv6tpubpc6co").unwrap())
+            .build();
+        let error = handle_get_posts_by_actor(
+            State(state),
+            ExtractOptionalServiceAuth(None),
+            HeaderMap::new(),
+            ExtractXrpc(request),
+        )
+        .await
+        .expect_err("unknown actor should not resolve");
+        assert!(matches!(
+            error.error,
+            XrpcError::Xrpc(GetPostsByActorError::ActorNotFound(_))
+        ));
+    }
+}
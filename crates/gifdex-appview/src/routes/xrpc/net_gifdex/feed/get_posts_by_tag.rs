@@ -0,0 +1,119 @@
+use crate::{AppState, routes::xrpc::AppError};
+use axum::{Json, extract::State, http::StatusCode, http::HeaderMap};
+use gifdex_lexicons::net_gifdex::feed::{
+    get_posts_by_tag::{GetPostsByTag, GetPostsByTagOutput, GetPostsByTagRequest},
+    post::Post,
+};
+use jacquard_axum::{ExtractXrpc, XrpcErrorResponse, service_auth::ExtractOptionalServiceAuth};
+use jacquard_common::{
+    smol_str::SmolStr,
+    types::collection::Collection,
+    xrpc::{GenericXrpcError, XrpcError, XrpcRequest},
+};
+use sqlx::query;
+
+pub async fn handle_get_posts_by_tag(
+    State(state): State<AppState>,
+    ExtractOptionalServiceAuth(auth): ExtractOptionalServiceAuth,
+    headers: HeaderMap,
+    ExtractXrpc(request): ExtractXrpc<GetPostsByTagRequest>,
+) -> Result<Json<GetPostsByTagOutput<'static>>, XrpcErrorResponse<GenericXrpcError>> {
+    let viewer_did = auth.as_ref().map(|a| a.did().as_str());
+    let limit = request.limit.unwrap_or(50).min(100);
+    // Cursors are opaque tokens wrapping the `created_at` of the last row of
+    // the previous page.
+    let cursor = match request.cursor.as_deref() {
+        Some(cursor) => match super::decode_cursor("created_at", cursor)
+            .and_then(|payload| payload.parse::<i64>().ok())
+        {
+            Some(created_at) => Some(created_at),
+            None => {
+                return Err(XrpcError::Generic(GenericXrpcError {
+                    error: SmolStr::new_static("InvalidRequest"),
+                    message: Some(SmolStr::new_static("Malformed cursor")),
+                    nsid: GetPostsByTag::NSID,
+                    method: "GET",
+                    http_status: StatusCode::BAD_REQUEST,
+                })
+                .into());
+            }
+        },
+        None => None,
+    };
+    // Optional language filter: only posts tagged with at least one requested
+    // language, plus - when explicitly asked for - posts that declare none.
+    let languages: Option<Vec<String>> = request
+        .languages
+        .as_ref()
+        .filter(|languages| !languages.is_empty())
+        .map(|languages| {
+            languages
+                .iter()
+                .map(|language| language.as_str().to_owned())
+                .collect()
+        });
+    let include_unspecified = request.include_unspecified_languages.unwrap_or(false);
+    let posts = query!(
+        "SELECT \
+            p.did, p.rkey, p.title, p.tags, p.languages, p.media_blob_cid, p.media_blob_mime, \
+            p.media_blob_alt, p.media_blob_width, p.media_blob_height, p.blurhash, p.created_at, \
+            p.edited_at, p.indexed_at as post_indexed_at, \
+            p.favourite_count, \
+            (SELECT pf.rkey \
+             FROM post_favourites pf \
+             WHERE pf.post_did = p.did AND pf.post_rkey = p.rkey AND pf.did = $4 \
+             LIMIT 1) as \"favourite_rkey\" \
+         FROM posts p \
+         WHERE $1 = ANY(p.tags) AND ($2::BIGINT IS NULL OR p.created_at < $2) \
+         AND ($6::TEXT[] IS NULL OR p.languages && $6 OR (p.languages IS NULL AND $7)) \
+         AND NOT EXISTS ( \
+            SELECT FROM labels l \
+            INNER JOIN labeler_rules r ON r.did = l.rule_did AND r.rkey = l.rule_rkey \
+            WHERE l.subject_did = p.did AND l.subject_rkey = p.rkey \
+                AND l.subject_collection = $5 AND r.takedown \
+                AND (l.expires_at IS NULL OR l.expires_at > (extract(epoch from now()) * 1000)::BIGINT)) \
+         ORDER BY p.created_at DESC LIMIT $3",
+        request.tag.as_ref(),
+        cursor,
+        limit,
+        viewer_did,
+        Post::NSID,
+        languages.as_deref(),
+        include_unspecified
+    )
+    .fetch_all(state.database.executor())
+    .await
+    .map_err(|err| AppError::database(GetPostsByTag::NSID, err))?;
+
+    // Generate cursor if we have more posts.
+    let cursor = super::next_created_at_cursor(&posts, limit, |post| post.created_at);
+
+    let keys: Vec<(String, String)> = posts
+        .iter()
+        .map(|post| (post.did.clone(), post.rkey.clone()))
+        .collect();
+    let labelers = super::super::accepted_labelers(&state, &headers);
+    let labels = super::super::post_labels(&state, &keys, &labelers)
+        .await
+        .map_err(|err| AppError::database(GetPostsByTag::NSID, err))?;
+
+    let mut author_dids: Vec<String> = posts.iter().map(|post| post.did.clone()).collect();
+    author_dids.sort();
+    author_dids.dedup();
+    let authors = super::super::author_profiles(&state, &author_dids)
+        .await
+        .map_err(|err| AppError::database(GetPostsByTag::NSID, err))?;
+
+    let post_views = super::hydrate_post_views(
+        &state,
+        posts.into_iter().map(|post| super::post_row!(post)).collect(),
+        labels,
+        &authors,
+    );
+
+    Ok(Json(GetPostsByTagOutput {
+        feed: post_views,
+        cursor: cursor.map(|cursor| cursor.into()),
+        extra_data: None,
+    }))
+}
@@ -0,0 +1,142 @@
+use crate::{AppState, routes::xrpc::AppError};
+use axum::{Json, extract::State, http::StatusCode, http::HeaderMap};
+use gifdex_lexicons::net_gifdex::feed::{
+    get_trending::{GetTrending, GetTrendingOutput, GetTrendingRequest},
+    post::Post,
+};
+use jacquard_axum::{ExtractXrpc, XrpcErrorResponse, service_auth::ExtractOptionalServiceAuth};
+use jacquard_common::{
+    smol_str::SmolStr,
+    types::collection::Collection,
+    xrpc::{GenericXrpcError, XrpcError, XrpcRequest},
+};
+use sqlx::query;
+
+pub async fn handle_get_trending(
+    State(state): State<AppState>,
+    ExtractOptionalServiceAuth(auth): ExtractOptionalServiceAuth,
+    headers: HeaderMap,
+    ExtractXrpc(request): ExtractXrpc<GetTrendingRequest>,
+) -> Result<Json<GetTrendingOutput<'static>>, XrpcErrorResponse<GenericXrpcError>> {
+    let viewer_did = auth.as_ref().map(|a| a.did().as_str());
+    let limit = request.limit.unwrap_or(50).min(100);
+    let window_millis = request.window.unwrap_or(48).clamp(1, 168) * 60 * 60 * 1000;
+
+    // Cursors are opaque tokens wrapping `{score}:{created_at}` of the last
+    // row of the previous page.
+    let (cursor_score, cursor_created_at) = match request.cursor.as_deref() {
+        Some(cursor) => {
+            let parsed = super::decode_cursor("score", cursor).and_then(|payload| {
+                let (score, created_at) = payload.split_once(':')?;
+                Some((score.parse::<i64>().ok()?, created_at.parse::<i64>().ok()?))
+            });
+            let Some((score, created_at)) = parsed else {
+                return Err(XrpcError::Generic(GenericXrpcError {
+                    error: SmolStr::new_static("InvalidRequest"),
+                    message: Some(SmolStr::new_static("Malformed cursor")),
+                    nsid: GetTrending::NSID,
+                    method: "GET",
+                    http_status: StatusCode::BAD_REQUEST,
+                })
+                .into());
+            };
+            (Some(score), Some(created_at))
+        }
+        None => (None, None),
+    };
+
+    // Rank posts by how many favourites they received inside the window,
+    // breaking ties on post recency. Grouping by the posts primary key makes
+    // the remaining selected columns functionally dependent, so they don't
+    // need to be listed in the GROUP BY.
+    // Optional language filter: only posts tagged with at least one requested
+    // language, plus - when explicitly asked for - posts that declare none.
+    let languages: Option<Vec<String>> = request
+        .languages
+        .as_ref()
+        .filter(|languages| !languages.is_empty())
+        .map(|languages| {
+            languages
+                .iter()
+                .map(|language| language.as_str().to_owned())
+                .collect()
+        });
+    let include_unspecified = request.include_unspecified_languages.unwrap_or(false);
+    let posts = query!(
+        "SELECT \
+            p.did, p.rkey, p.title, p.tags, p.languages, p.media_blob_cid, p.media_blob_mime, \
+            p.media_blob_alt, p.media_blob_width, p.media_blob_height, p.blurhash, p.created_at, \
+            p.edited_at, p.indexed_at as post_indexed_at, \
+            COUNT(*) as \"score!\", \
+            p.favourite_count, \
+            (SELECT vf.rkey \
+             FROM post_favourites vf \
+             WHERE vf.post_did = p.did AND vf.post_rkey = p.rkey AND vf.did = $5 \
+             LIMIT 1) as \"favourite_rkey\" \
+         FROM post_favourites f \
+         INNER JOIN posts p ON p.did = f.post_did AND p.rkey = f.post_rkey \
+         WHERE f.created_at > (extract(epoch from now()) * 1000)::BIGINT - $1 \
+         AND ($7::TEXT[] IS NULL OR p.languages && $7 OR (p.languages IS NULL AND $8)) \
+         AND NOT EXISTS ( \
+            SELECT FROM labels l \
+            INNER JOIN labeler_rules r ON r.did = l.rule_did AND r.rkey = l.rule_rkey \
+            WHERE l.subject_did = p.did AND l.subject_rkey = p.rkey \
+                AND l.subject_collection = $6 AND r.takedown \
+                AND (l.expires_at IS NULL OR l.expires_at > (extract(epoch from now()) * 1000)::BIGINT)) \
+         GROUP BY p.did, p.rkey \
+         HAVING ($2::BIGINT IS NULL OR (COUNT(*), p.created_at) < ($2, $3)) \
+         ORDER BY COUNT(*) DESC, p.created_at DESC \
+         LIMIT $4",
+        window_millis,
+        cursor_score,
+        cursor_created_at,
+        limit,
+        viewer_did,
+        Post::NSID,
+        languages.as_deref(),
+        include_unspecified
+    )
+    .fetch_all(state.database.executor())
+    .await
+    .map_err(|err| AppError::database(GetTrending::NSID, err))?;
+
+    // Generate cursor if we have more posts.
+    let cursor = if posts.len() == limit as usize {
+        posts
+            .last()
+            .map(|post| {
+                super::encode_cursor("score", format!("{}:{}", post.score, post.created_at))
+            })
+    } else {
+        None
+    };
+
+    let keys: Vec<(String, String)> = posts
+        .iter()
+        .map(|post| (post.did.clone(), post.rkey.clone()))
+        .collect();
+    let labelers = super::super::accepted_labelers(&state, &headers);
+    let labels = super::super::post_labels(&state, &keys, &labelers)
+        .await
+        .map_err(|err| AppError::database(GetTrending::NSID, err))?;
+
+    let mut author_dids: Vec<String> = posts.iter().map(|post| post.did.clone()).collect();
+    author_dids.sort();
+    author_dids.dedup();
+    let authors = super::super::author_profiles(&state, &author_dids)
+        .await
+        .map_err(|err| AppError::database(GetTrending::NSID, err))?;
+
+    let post_views = super::hydrate_post_views(
+        &state,
+        posts.into_iter().map(|post| super::post_row!(post)).collect(),
+        labels,
+        &authors,
+    );
+
+    Ok(Json(GetTrendingOutput {
+        feed: post_views,
+        cursor: cursor.map(|cursor| cursor.into()),
+        extra_data: None,
+    }))
+}
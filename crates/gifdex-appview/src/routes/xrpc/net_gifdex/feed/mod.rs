@@ -1,7 +1,207 @@
+mod get_actor_favourites;
+mod get_feed_skeleton;
 mod get_post;
+mod get_post_favourites;
+mod get_posts;
 mod get_posts_by_actor;
 mod get_posts_by_query;
+mod get_posts_by_tag;
+mod get_trending;
+mod search_posts;
 
+pub use get_actor_favourites::*;
+pub use get_feed_skeleton::*;
 pub use get_post::*;
+pub use get_post_favourites::*;
+pub use get_posts::*;
 pub use get_posts_by_actor::*;
 pub use get_posts_by_query::*;
+pub use get_posts_by_tag::*;
+pub use get_trending::*;
+pub use search_posts::*;
+
+use crate::AppState;
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use gifdex_lexicons::net_gifdex::{
+    actor::ProfileViewBasic,
+    feed::{self, PostFeedView, PostViewMedia, PostViewMediaDimensions, post::Post},
+    labeler::LabelView,
+};
+use jacquard_common::{
+    IntoStatic,
+    chrono::{TimeZone, Utc},
+    types::{aturi::AtUri, collection::Collection, tid::Tid},
+};
+use std::collections::HashMap;
+use tracing::warn;
+
+/// Encode a pagination cursor payload as an opaque token.
+///
+/// The sort field name is embedded alongside the payload so a cursor minted
+/// for one ordering can't be replayed against a feed sorted differently, and
+/// the whole token is base64-encoded so clients have nothing to parse - the
+/// internal format can evolve without breaking them.
+pub(crate) fn encode_cursor(field: &str, payload: impl std::fmt::Display) -> String {
+    URL_SAFE_NO_PAD.encode(format!("{field}:{payload}"))
+}
+
+/// Decode an opaque cursor minted by [`encode_cursor`], returning the raw
+/// payload. Returns `None` when the token isn't valid base64, isn't UTF-8 or
+/// embeds a different sort field - callers reject those with an
+/// `InvalidRequest` error rather than silently restarting the feed.
+pub(crate) fn decode_cursor(field: &str, cursor: &str) -> Option<String> {
+    let decoded = String::from_utf8(URL_SAFE_NO_PAD.decode(cursor).ok()?).ok()?;
+    let (embedded_field, payload) = decoded.split_once(':')?;
+    (embedded_field == field).then(|| payload.to_owned())
+}
+
+/// Compute the `created_at` cursor for the next page of a feed query.
+///
+/// Feeds are ordered by `created_at` and paginated by fetching rows strictly
+/// past the cursor in the feed's direction, so the cursor for the next page
+/// is the `created_at` of the last row - but only when the current page is
+/// full, as a short page means there's nothing left to fetch.
+pub(crate) fn next_created_at_cursor<T>(
+    posts: &[T],
+    limit: i64,
+    created_at: impl Fn(&T) -> i64,
+) -> Option<String> {
+    if posts.len() == limit as usize {
+        posts
+            .last()
+            .map(|post| encode_cursor("created_at", created_at(post)))
+    } else {
+        None
+    }
+}
+
+/// The post columns every feed query selects, decoupling view building from
+/// the anonymous record type each handler's `query!` invocation produces.
+pub(crate) struct PostRow {
+    did: String,
+    rkey: String,
+    title: String,
+    tags: Option<Vec<String>>,
+    languages: Option<Vec<String>>,
+    media_blob_mime: String,
+    media_blob_alt: Option<String>,
+    media_blob_width: i32,
+    media_blob_height: i32,
+    blurhash: Option<String>,
+    created_at: i64,
+    edited_at: Option<i64>,
+    post_indexed_at: i64,
+    favourite_count: i64,
+    favourite_rkey: Option<String>,
+}
+
+/// Adapt a feed query row into a [`PostRow`]. The queries all select the post
+/// columns under the same aliases, so one field-by-field mapping covers every
+/// anonymous record type sqlx generates for them.
+macro_rules! post_row {
+    ($row:expr) => {{
+        let row = $row;
+        super::PostRow {
+            did: row.did,
+            rkey: row.rkey,
+            title: row.title,
+            tags: row.tags,
+            languages: row.languages,
+            media_blob_mime: row.media_blob_mime,
+            media_blob_alt: row.media_blob_alt,
+            media_blob_width: row.media_blob_width,
+            media_blob_height: row.media_blob_height,
+            blurhash: row.blurhash,
+            created_at: row.created_at,
+            edited_at: row.edited_at,
+            post_indexed_at: row.post_indexed_at,
+            favourite_count: row.favourite_count,
+            favourite_rkey: row.favourite_rkey,
+        }
+    }};
+}
+pub(crate) use post_row;
+
+/// Build the feed view for a single post row, or `None` when the author has
+/// no hydrated profile or the stored identifiers don't parse.
+pub(crate) fn hydrate_post_view(
+    state: &AppState,
+    post: PostRow,
+    labels: &mut HashMap<(String, String), Vec<LabelView<'static>>>,
+    authors: &HashMap<String, ProfileViewBasic<'static>>,
+) -> Option<PostFeedView<'static>> {
+    // Look the author up from the batch-hydrated profiles
+    let profile = authors.get(&post.did)?.clone();
+
+    let uri = AtUri::new_owned(format!("at://{}/{}/{}", post.did, Post::NSID, post.rkey))
+        .inspect_err(|err| warn!("Malformed at-uri components stored for post: {err:?}"))
+        .ok()?;
+    let view = PostFeedView::new()
+        .uri(uri)
+        .title(post.title.into_static())
+        .tags(
+            post.tags
+                .map(|tags| tags.into_iter().map(|t| t.into()).collect()),
+        )
+        .languages(
+            post.languages
+                .map(|langs| langs.into_iter().map(|l| l.into()).collect()),
+        )
+        .labels(labels.remove(&(post.did.clone(), post.rkey.clone())))
+        .media(
+            PostViewMedia::new()
+                .fullsize_url(super::media_url(state, &post.did, &post.rkey))
+                .thumbnail_url(super::media_url(state, &post.did, &post.rkey))
+                .mime_type(post.media_blob_mime.into_static())
+                .alt(post.media_blob_alt.map(|s| s.into()))
+                .blurhash(post.blurhash.map(|s| s.into()))
+                .dimensions(
+                    PostViewMediaDimensions::new()
+                        .height(post.media_blob_height)
+                        .width(post.media_blob_width)
+                        .build(),
+                )
+                .build(),
+        )
+        .favourite_count(post.favourite_count)
+        .author(profile)
+        .viewer(feed::ViewerState {
+            favourite: post
+                .favourite_rkey
+                .as_ref()
+                .and_then(|rkey| Tid::new(rkey.clone()).ok()),
+            ..Default::default()
+        })
+        .created_at(
+            Utc.timestamp_millis_opt(post.created_at)
+                .unwrap()
+                .fixed_offset(),
+        )
+        .maybe_edited_at(post.edited_at.map(|edited_at| {
+            Utc.timestamp_millis_opt(edited_at)
+                .unwrap()
+                .fixed_offset()
+                .into()
+        }))
+        .indexed_at(
+            Utc.timestamp_millis_opt(post.post_indexed_at)
+                .unwrap()
+                .fixed_offset(),
+        )
+        .build();
+    Some(view)
+}
+
+/// Build the feed views for a page of post rows, dropping rows
+/// [`hydrate_post_view`] can't represent.
+pub(crate) fn hydrate_post_views(
+    state: &AppState,
+    posts: Vec<PostRow>,
+    mut labels: HashMap<(String, String), Vec<LabelView<'static>>>,
+    authors: &HashMap<String, ProfileViewBasic<'static>>,
+) -> Vec<PostFeedView<'static>> {
+    posts
+        .into_iter()
+        .filter_map(|post| hydrate_post_view(state, post, &mut labels, authors))
+        .collect()
+}
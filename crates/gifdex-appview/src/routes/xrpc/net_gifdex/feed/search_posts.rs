@@ -0,0 +1,135 @@
+use crate::{AppState, routes::xrpc::AppError};
+use axum::{Json, extract::State, http::HeaderMap};
+use gifdex_lexicons::net_gifdex::feed::{
+    post::Post,
+    search_posts::{SearchPosts, SearchPostsError, SearchPostsOutput, SearchPostsRequest},
+};
+use jacquard_axum::{ExtractXrpc, XrpcErrorResponse, service_auth::ExtractOptionalServiceAuth};
+use jacquard_common::{
+    types::collection::Collection,
+    xrpc::{XrpcError, XrpcRequest},
+};
+use sqlx::query;
+
+pub async fn handle_search_posts(
+    State(state): State<AppState>,
+    ExtractOptionalServiceAuth(auth): ExtractOptionalServiceAuth,
+    headers: HeaderMap,
+    ExtractXrpc(request): ExtractXrpc<SearchPostsRequest>,
+) -> Result<Json<SearchPostsOutput<'static>>, XrpcErrorResponse<SearchPostsError<'static>>> {
+    if request.q.trim().is_empty() {
+        return Err(XrpcError::Xrpc(SearchPostsError::InvalidQuery(Some(
+            "Search query must not be empty".into(),
+        )))
+        .into());
+    }
+
+    // Cursors are opaque tokens wrapping `{rank}:{created_at}` of the last
+    // row of the previous page.
+    let (cursor_rank, cursor_created_at) = match request.cursor.as_deref() {
+        Some(cursor) => {
+            let parsed = super::decode_cursor("rank", cursor).and_then(|payload| {
+                let (rank, created_at) = payload.split_once(':')?;
+                Some((rank.parse::<f32>().ok()?, created_at.parse::<i64>().ok()?))
+            });
+            let Some((rank, created_at)) = parsed else {
+                return Err(XrpcError::Xrpc(SearchPostsError::InvalidQuery(Some(
+                    "Malformed cursor".into(),
+                )))
+                .into());
+            };
+            (Some(rank), Some(created_at))
+        }
+        None => (None, None),
+    };
+
+    let viewer_did = auth.as_ref().map(|a| a.did().as_str());
+    let limit = request.limit.unwrap_or(50).min(100);
+    // Optional language filter: only posts tagged with at least one requested
+    // language, plus - when explicitly asked for - posts that declare none.
+    let languages: Option<Vec<String>> = request
+        .languages
+        .as_ref()
+        .filter(|languages| !languages.is_empty())
+        .map(|languages| {
+            languages
+                .iter()
+                .map(|language| language.as_str().to_owned())
+                .collect()
+        });
+    let include_unspecified = request.include_unspecified_languages.unwrap_or(false);
+    let posts = query!(
+        "SELECT \
+            p.did, p.rkey, p.title, p.tags, p.languages, p.media_blob_cid, p.media_blob_mime, \
+            p.media_blob_alt, p.media_blob_width, p.media_blob_height, p.blurhash, p.created_at, \
+            p.edited_at, p.indexed_at as post_indexed_at, \
+            ts_rank(p.search, plainto_tsquery('simple', $1)) as \"rank!\", \
+            p.favourite_count, \
+            (SELECT pf.rkey \
+             FROM post_favourites pf \
+             WHERE pf.post_did = p.did AND pf.post_rkey = p.rkey AND pf.did = $5 \
+             LIMIT 1) as \"favourite_rkey\" \
+         FROM posts p \
+         WHERE p.search @@ plainto_tsquery('simple', $1) \
+         AND ($7::TEXT[] IS NULL OR p.languages && $7 OR (p.languages IS NULL AND $8)) \
+         AND ($2::REAL IS NULL OR \
+             (ts_rank(p.search, plainto_tsquery('simple', $1)), p.created_at) < ($2, $3)) \
+         AND NOT EXISTS ( \
+            SELECT FROM labels l \
+            INNER JOIN labeler_rules r ON r.did = l.rule_did AND r.rkey = l.rule_rkey \
+            WHERE l.subject_did = p.did AND l.subject_rkey = p.rkey \
+                AND l.subject_collection = $6 AND r.takedown \
+                AND (l.expires_at IS NULL OR l.expires_at > (extract(epoch from now()) * 1000)::BIGINT)) \
+         ORDER BY ts_rank(p.search, plainto_tsquery('simple', $1)) DESC, p.created_at DESC \
+         LIMIT $4",
+        request.q.as_ref(),
+        cursor_rank,
+        cursor_created_at,
+        limit,
+        viewer_did,
+        Post::NSID,
+        languages.as_deref(),
+        include_unspecified
+    )
+    .fetch_all(state.database.executor())
+    .await
+    .map_err(|err| AppError::database(SearchPosts::NSID, err))?;
+
+    // Generate cursor if we have more posts.
+    let cursor = if posts.len() == limit as usize {
+        posts
+            .last()
+            .map(|post| super::encode_cursor("rank", format!("{}:{}", post.rank, post.created_at)))
+    } else {
+        None
+    };
+
+    let keys: Vec<(String, String)> = posts
+        .iter()
+        .map(|post| (post.did.clone(), post.rkey.clone()))
+        .collect();
+    let labelers = super::super::accepted_labelers(&state, &headers);
+    let labels = super::super::post_labels(&state, &keys, &labelers)
+        .await
+        .map_err(|err| AppError::database(SearchPosts::NSID, err))?;
+
+    let mut author_dids: Vec<String> = posts.iter().map(|post| post.did.clone()).collect();
+    author_dids.sort();
+    author_dids.dedup();
+    let authors = super::super::author_profiles(&state, &author_dids)
+        .await
+        .map_err(|err| AppError::database(SearchPosts::NSID, err))?;
+
+    let post_views = super::hydrate_post_views(
+        &state,
+        posts.into_iter().map(|post| super::post_row!(post)).collect(),
+        labels,
+        &authors,
+    );
+
+    Ok(Json(SearchPostsOutput {
+        feed: post_views,
+        cursor: cursor.map(|c| c.into()),
+        extra_data: None,
+    }))
+}
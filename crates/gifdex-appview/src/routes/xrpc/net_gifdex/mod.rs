@@ -1,2 +1,230 @@
 pub mod actor;
 pub mod feed;
+
+use crate::AppState;
+use gifdex_lexicons::{
+    net_gifdex::{
+        actor::ProfileViewBasic,
+        feed::post::Post,
+        labeler::{LabelView, rule::Rule},
+    },
+    signing,
+};
+use jacquard_common::{
+    chrono::{TimeZone, Utc},
+    types::{aturi::AtUri, collection::Collection, did::Did, string::Handle, uri::Uri},
+};
+use axum::http::HeaderMap;
+use sqlx::query;
+use std::collections::HashMap;
+use tracing::warn;
+
+/// Build the CDN URL for a post's media blob, appending an `exp`/`sig`
+/// signature pair when media URL signing is configured. The signature covers
+/// the `{did}/{rkey}` resource, so the CDN accepts it for the thumbnail and
+/// transcoded variants too.
+pub(crate) fn media_url(state: &AppState, did: &str, rkey: &str) -> Uri<'static> {
+    let mut url = state
+        .cdn_url
+        .join(&format!("/media/{did}/{rkey}"))
+        .unwrap();
+    if let Some(secret) = &state.media_signing_secret {
+        let expires_at = Utc::now().timestamp_millis() + state.media_url_ttl.as_millis() as i64;
+        let signature = signing::sign_media(secret.as_bytes(), &format!("{did}/{rkey}"), expires_at);
+        url.query_pairs_mut()
+            .append_pair("exp", &expires_at.to_string())
+            .append_pair("sig", &signature);
+    }
+    Uri::new_owned(url).unwrap()
+}
+
+/// Batch-fetch basic profile views for a page of post authors, keyed by DID.
+///
+/// Feed pages can span many authors, so all accounts are fetched with a single
+/// `ANY` query rather than one query per post. Accounts with a malformed
+/// stored DID are skipped with a warning.
+pub(crate) async fn author_profiles(
+    state: &AppState,
+    dids: &[String],
+) -> sqlx::Result<HashMap<String, ProfileViewBasic<'static>>> {
+    let accounts = query!(
+        "SELECT did, handle, display_name, avatar_blob_cid \
+         FROM accounts WHERE did = ANY($1)",
+        dids
+    )
+    .fetch_all(state.database.executor())
+    .await?;
+
+    let mut profiles = HashMap::new();
+    for account in accounts {
+        let Ok(did) = account
+            .did
+            .parse::<Did>()
+            .inspect_err(|err| warn!("Malformed DID stored for account: {err:?}"))
+        else {
+            continue;
+        };
+        let profile = ProfileViewBasic::new()
+            .did(did)
+            .handle(account.handle.and_then(|handle| {
+                Handle::new_owned(handle)
+                    .inspect_err(|err| warn!("Malformed handle stored for account: {err:?}"))
+                    .ok()
+            }))
+            .display_name(account.display_name.map(|s| s.into()))
+            .avatar(account.avatar_blob_cid.map(|blob_cid| {
+                Uri::new_owned(
+                    state
+                        .cdn_url
+                        .join(&format!("/avatar/{}/{}", account.did, blob_cid))
+                        .unwrap(),
+                )
+                .unwrap()
+            }))
+            .build();
+        profiles.insert(account.did, profile);
+    }
+    Ok(profiles)
+}
+
+/// Labelers whose labels the client accepts, parsed from the
+/// `atproto-accept-labelers` header and intersected with the labelers this
+/// appview is configured to hydrate from.
+///
+/// Clients send a comma-separated list of labeler DIDs, optionally carrying
+/// parameters like `;redact` which are ignored here. An absent header
+/// defaults to every configured labeler; listing a subset lets a client
+/// request labels from one specific labeler. The emitting labeler is always
+/// visible in each label view's `src`.
+pub(crate) fn accepted_labelers(state: &AppState, headers: &HeaderMap) -> Vec<String> {
+    match headers.get("atproto-accept-labelers") {
+        Some(value) => value
+            .to_str()
+            .unwrap_or("")
+            .split(',')
+            .map(|entry| entry.split(';').next().unwrap_or("").trim())
+            .filter(|did| state.labelers.iter().any(|labeler| labeler == did))
+            .map(|did| did.to_owned())
+            .collect(),
+        None => state.labelers.clone(),
+    }
+}
+
+/// Fetch the active labels applied to a page of posts, keyed by `(did, rkey)`.
+///
+/// Labels are looked up for the whole page in one query so feed handlers don't
+/// issue a query per post. Expired labels are filtered out database-side.
+pub(crate) async fn post_labels(
+    state: &AppState,
+    posts: &[(String, String)],
+    labelers: &[String],
+) -> sqlx::Result<HashMap<(String, String), Vec<LabelView<'static>>>> {
+    let (dids, rkeys): (Vec<String>, Vec<String>) = posts.iter().cloned().unzip();
+    let rows = query!(
+        "SELECT \
+            l.subject_did, l.subject_rkey as \"subject_rkey!\", l.did, l.rule_did, l.rule_rkey, \
+            l.reason, l.created_at, l.expires_at \
+         FROM labels l \
+         INNER JOIN unnest($1::TEXT[], $2::TEXT[]) AS subject(did, rkey) \
+            ON l.subject_did = subject.did AND l.subject_rkey = subject.rkey \
+         WHERE l.subject_collection = $3 AND l.did = ANY($4) \
+            AND (l.expires_at IS NULL OR l.expires_at > (extract(epoch from now()) * 1000)::BIGINT)",
+        &dids,
+        &rkeys,
+        Post::NSID,
+        labelers
+    )
+    .fetch_all(state.database.executor())
+    .await?;
+
+    let mut labels: HashMap<(String, String), Vec<LabelView<'static>>> = HashMap::new();
+    for row in rows {
+        let Some(label) = build_label_view(
+            row.did,
+            row.rule_did,
+            row.rule_rkey,
+            row.reason,
+            row.created_at,
+            row.expires_at,
+        ) else {
+            continue;
+        };
+        labels
+            .entry((row.subject_did, row.subject_rkey))
+            .or_default()
+            .push(label);
+    }
+    Ok(labels)
+}
+
+/// Fetch the active labels applied to a set of accounts, keyed by DID.
+///
+/// Account-level labels are stored with a null subject collection/rkey, as the
+/// subject of the label is the whole repo rather than a record in it.
+pub(crate) async fn actor_labels(
+    state: &AppState,
+    dids: &[String],
+    labelers: &[String],
+) -> sqlx::Result<HashMap<String, Vec<LabelView<'static>>>> {
+    let rows = query!(
+        "SELECT \
+            l.subject_did, l.did, l.rule_did, l.rule_rkey, \
+            l.reason, l.created_at, l.expires_at \
+         FROM labels l \
+         WHERE l.subject_did = ANY($1) AND l.subject_collection IS NULL AND l.did = ANY($2) \
+            AND (l.expires_at IS NULL OR l.expires_at > (extract(epoch from now()) * 1000)::BIGINT)",
+        dids,
+        labelers
+    )
+    .fetch_all(state.database.executor())
+    .await?;
+
+    let mut labels: HashMap<String, Vec<LabelView<'static>>> = HashMap::new();
+    for row in rows {
+        let Some(label) = build_label_view(
+            row.did,
+            row.rule_did,
+            row.rule_rkey,
+            row.reason,
+            row.created_at,
+            row.expires_at,
+        ) else {
+            continue;
+        };
+        labels.entry(row.subject_did).or_default().push(label);
+    }
+    Ok(labels)
+}
+
+/// Build a [`LabelView`] from a `labels` row, skipping rows with malformed
+/// stored identifiers.
+fn build_label_view(
+    src: String,
+    rule_did: String,
+    rule_rkey: String,
+    reason: Option<String>,
+    created_at: i64,
+    expires_at: Option<i64>,
+) -> Option<LabelView<'static>> {
+    let src = src
+        .parse::<Did>()
+        .inspect_err(|err| warn!("Malformed DID stored for label: {err:?}"))
+        .ok()?;
+    let rule = AtUri::new_owned(format!("at://{}/{}/{}", rule_did, Rule::NSID, rule_rkey))
+        .inspect_err(|err| warn!("Malformed at-uri components stored for label: {err:?}"))
+        .ok()?;
+    Some(
+        LabelView::new()
+            .src(src)
+            .rule(rule)
+            .reason(reason.map(|r| r.into()))
+            .created_at(Utc.timestamp_millis_opt(created_at).unwrap().fixed_offset())
+            .maybe_expires_at(expires_at.map(|expiry| {
+                Utc.timestamp_millis_opt(expiry)
+                    .unwrap()
+                    .fixed_offset()
+                    .into()
+            }))
+            .build(),
+    )
+}
@@ -0,0 +1,48 @@
+//! Support code for handler tests: a containerised Postgres from
+//! [`gifdex_testing`] plus an [`AppState`] wired with the same defaults the
+//! binary uses.
+
+use crate::{
+    AppState, build_service_did_doc,
+    database::{Database, DatabaseConfig},
+};
+use gifdex_testing::TestPostgres;
+use jacquard_axum::service_auth::ServiceAuthConfig;
+use jacquard_common::{types::did::Did, url::Url};
+use jacquard_identity::{JacquardResolver, resolver::ResolverOptions};
+use std::{sync::Arc, time::Duration};
+
+/// Spin up a disposable Postgres, apply the workspace migrations to it and
+/// build an [`AppState`] around it with default configuration under an
+/// unroutable service identity.
+pub async fn test_state() -> (TestPostgres, AppState) {
+    let postgres = gifdex_testing::postgres().await;
+    let database = Arc::new(
+        Database::new(
+            &postgres.url,
+            &DatabaseConfig {
+                max_connections: 5,
+                acquire_timeout: Duration::from_secs(30),
+                idle_timeout: Duration::from_secs(600),
+            },
+        )
+        .await
+        .expect("failed to connect to test database"),
+    );
+    let service_did = Did::new_static("did:web:appview.invalid").unwrap();
+    let host = Url::parse("https://appview.invalid/").unwrap();
+    let state = AppState {
+        database,
+        cdn_url: Url::parse("https://cdn.invalid/").unwrap(),
+        service_did_document: build_service_did_doc(&service_did, &host),
+        service_auth_config: ServiceAuthConfig::new(
+            service_did.clone(),
+            JacquardResolver::new(reqwest::Client::new(), ResolverOptions::default()),
+        ),
+        labelers: vec![service_did.as_str().to_owned()],
+        service_did,
+        media_signing_secret: None,
+        media_url_ttl: Duration::from_secs(86400),
+    };
+    (postgres, state)
+}
@@ -0,0 +1,114 @@
+use axum::body::Bytes;
+use cid::Cid;
+use jacquard_common::types::did::Did;
+use std::{path::PathBuf, sync::Mutex, time::SystemTime};
+use tokio::fs;
+use tracing::warn;
+
+/// An on-disk cache of validated blobs keyed by `(did, cid)`.
+///
+/// Blobs are stored as flat files in the cache directory with an in-memory LRU
+/// index. When the total cached size exceeds the configured maximum the least
+/// recently used blobs are evicted from disk.
+pub struct BlobCache {
+    dir: PathBuf,
+    max_size: u64,
+    state: Mutex<CacheState>,
+}
+
+struct CacheState {
+    entries: lru::LruCache<String, u64>,
+    total_size: u64,
+}
+
+impl BlobCache {
+    /// Open the cache directory, seeding the index from any blobs left over from
+    /// a previous run (oldest first, so they are first in line for eviction).
+    pub async fn new(dir: PathBuf, max_size: u64) -> std::io::Result<Self> {
+        fs::create_dir_all(&dir).await?;
+        let mut existing = Vec::new();
+        let mut dir_entries = fs::read_dir(&dir).await?;
+        while let Some(entry) = dir_entries.next_entry().await? {
+            let metadata = entry.metadata().await?;
+            if metadata.is_file() {
+                existing.push((
+                    entry.file_name().to_string_lossy().into_owned(),
+                    metadata.len(),
+                    metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+                ));
+            }
+        }
+        existing.sort_by_key(|(_, _, modified)| *modified);
+
+        let mut entries = lru::LruCache::unbounded();
+        let mut total_size = 0;
+        for (name, size, _) in existing {
+            total_size += size;
+            entries.put(name, size);
+        }
+        Ok(Self {
+            dir,
+            max_size,
+            state: Mutex::new(CacheState {
+                entries,
+                total_size,
+            }),
+        })
+    }
+
+    fn file_name(did: &Did<'_>, cid: &Cid) -> String {
+        format!("{did}-{cid}")
+    }
+
+    /// Look up a cached blob, promoting it to most recently used on a hit.
+    pub async fn get(&self, did: &Did<'_>, cid: &Cid) -> Option<Bytes> {
+        let name = Self::file_name(did, cid);
+        self.state.lock().unwrap().entries.get(&name)?;
+        match fs::read(self.dir.join(&name)).await {
+            Ok(bytes) => Some(Bytes::from(bytes)),
+            Err(err) => {
+                // The file went missing underneath us - drop it from the index.
+                warn!("failed to read cached blob {name}: {err:?}");
+                let mut state = self.state.lock().unwrap();
+                if let Some(size) = state.entries.pop(&name) {
+                    state.total_size -= size;
+                }
+                None
+            }
+        }
+    }
+
+    /// Store a blob in the cache, evicting least recently used blobs if the
+    /// total cached size would exceed the maximum.
+    pub async fn put(&self, did: &Did<'_>, cid: &Cid, bytes: &[u8]) {
+        if bytes.len() as u64 > self.max_size {
+            return;
+        }
+        let name = Self::file_name(did, cid);
+        if let Err(err) = fs::write(self.dir.join(&name), bytes).await {
+            warn!("failed to write blob {name} to cache: {err:?}");
+            return;
+        }
+        let evicted: Vec<String> = {
+            let mut state = self.state.lock().unwrap();
+            if let Some(previous) = state.entries.put(name, bytes.len() as u64) {
+                state.total_size -= previous;
+            }
+            state.total_size += bytes.len() as u64;
+            let mut evicted = Vec::new();
+            while state.total_size > self.max_size {
+                let Some((name, size)) = state.entries.pop_lru() else {
+                    break;
+                };
+                state.total_size -= size;
+                evicted.push(name);
+            }
+            evicted
+        };
+        for name in evicted {
+            if let Err(err) = fs::remove_file(self.dir.join(&name)).await {
+                warn!("failed to evict cached blob {name}: {err:?}");
+            }
+        }
+    }
+}
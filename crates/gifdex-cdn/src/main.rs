@@ -1,31 +1,52 @@
+mod cache;
 mod database;
 mod routes;
 
-use crate::routes::{avatar::get_avatar_handler, gif::get_gif_handler};
-use anyhow::Result;
+use crate::{
+    cache::BlobCache,
+    routes::{
+        admin::prefetch_handler, avatar::get_avatar_handler, get_blob::get_blob_handler,
+        gif::get_gif_handler, thumb::get_thumb_handler,
+    },
+};
+use anyhow::{Context, Result};
 use axum::{
     Router,
     extract::Request,
-    http::{HeaderValue, StatusCode, header},
+    http::{HeaderValue, Method, StatusCode, header},
     middleware::{self as axum_middleware, Next},
-    routing::get,
+    routing::{get, post},
 };
 use clap::Parser;
-use database::Database;
+use database::{Database, DatabaseConfig};
 use dotenvy::dotenv;
-use std::{net::SocketAddr, sync::Arc};
-use tokio::{net::TcpListener, signal};
+use gifdex_lexicons::limits::{
+    BlobLimits, DEFAULT_AVATAR_MIME_TYPES, DEFAULT_MAX_AVATAR_SIZE, DEFAULT_MAX_BLOB_SIZE,
+    DEFAULT_MEDIA_MIME_TYPES,
+};
+use gifdex_metrics::{HttpMetrics, metrics_router, track_http};
+use prometheus::{IntCounterVec, Opts};
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use tokio::{
+    net::TcpListener,
+    signal,
+    sync::{OwnedSemaphorePermit, Semaphore, SemaphorePermit},
+};
 use tower_http::{
     catch_panic::CatchPanicLayer,
+    cors::{Any, CorsLayer},
     normalize_path::NormalizePathLayer,
-    trace::{self, DefaultOnFailure, DefaultOnRequest, DefaultOnResponse, TraceLayer},
+    request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer},
+    trace::{DefaultOnFailure, DefaultOnRequest, TraceLayer},
 };
 use tracing::{Level, info};
 use tracing_subscriber::EnvFilter;
 
-const MAX_AVATAR_SIZE: usize = 3 * 1024 * 1024; // 3MB
-const MAX_BLOB_SIZE: usize = 10 * 1024 * 1024; // 10MB
-
 #[derive(Debug, Clone, Parser)]
 #[clap(author, about, version)]
 struct Arguments {
@@ -39,66 +60,541 @@ struct Arguments {
 
     #[arg(long = "database-url", env = "DATABASE_URL")]
     database_url: String,
+
+    /// Maximum number of connections in the database pool.
+    #[arg(
+        long = "database-max-connections",
+        env = "GIFDEX_CDN_DATABASE_MAX_CONNECTIONS",
+        default_value_t = 20
+    )]
+    database_max_connections: u32,
+
+    /// Seconds to wait for a free database connection before failing a query.
+    #[arg(
+        long = "database-acquire-timeout",
+        env = "GIFDEX_CDN_DATABASE_ACQUIRE_TIMEOUT",
+        default_value_t = 30
+    )]
+    database_acquire_timeout: u64,
+
+    /// Seconds an idle database connection is kept around before being closed.
+    #[arg(
+        long = "database-idle-timeout",
+        env = "GIFDEX_CDN_DATABASE_IDLE_TIMEOUT",
+        default_value_t = 600
+    )]
+    database_idle_timeout: u64,
+
+    /// Directory to cache validated blobs in. Caching is disabled if unset.
+    #[arg(long = "cache-dir", env = "GIFDEX_CDN_CACHE_DIR")]
+    cache_dir: Option<std::path::PathBuf>,
+
+    /// Maximum total size of the blob cache in bytes.
+    #[arg(
+        long = "cache-max-size",
+        env = "GIFDEX_CDN_CACHE_MAX_SIZE",
+        default_value_t = 1024 * 1024 * 1024
+    )]
+    cache_max_size: u64,
+
+    /// Maximum time in seconds to wait for a blob fetch from an upstream PDS.
+    #[arg(
+        long = "pds-fetch-timeout",
+        env = "GIFDEX_CDN_PDS_FETCH_TIMEOUT",
+        default_value_t = 30
+    )]
+    pds_fetch_timeout: u64,
+
+    /// Maximum number of concurrent blob fetches per upstream PDS host.
+    #[arg(
+        long = "pds-fetch-concurrency",
+        env = "GIFDEX_CDN_PDS_FETCH_CONCURRENCY",
+        default_value_t = 8
+    )]
+    pds_fetch_concurrency: usize,
+
+    /// Maximum number of redirects followed when fetching a blob from an
+    /// upstream PDS. Every redirect target is vetted against the blocked
+    /// address ranges like the original host. Set to 0 to refuse redirects
+    /// entirely.
+    #[arg(
+        long = "pds-fetch-max-redirects",
+        env = "GIFDEX_CDN_PDS_FETCH_MAX_REDIRECTS",
+        default_value_t = 2
+    )]
+    pds_fetch_max_redirects: usize,
+
+    /// Comma-separated CIDR ranges blob fetches must not resolve into.
+    ///
+    /// A hostile DID document can point its PDS endpoint at an internal
+    /// address to probe the CDN's network, so fetches to PDS hosts resolving
+    /// into any of these ranges are refused. Defaults to the loopback,
+    /// private, link-local and unique-local ranges; operators who do run
+    /// internal PDSes can narrow the list.
+    #[arg(
+        long = "blocked-pds-ranges",
+        env = "GIFDEX_CDN_BLOCKED_PDS_RANGES",
+        value_delimiter = ',',
+        default_value = "0.0.0.0/8,127.0.0.0/8,10.0.0.0/8,172.16.0.0/12,192.168.0.0/16,169.254.0.0/16,::1/128,fc00::/7,fe80::/10"
+    )]
+    blocked_pds_ranges: Vec<ipnet::IpNet>,
+
+    /// Number of times a failed blob fetch is retried against the upstream
+    /// PDS before giving up.
+    ///
+    /// Connection errors and 5xx responses are retried; 4xx responses and
+    /// timeouts are not.
+    #[arg(
+        long = "pds-fetch-retries",
+        env = "GIFDEX_CDN_PDS_FETCH_RETRIES",
+        default_value_t = 2
+    )]
+    pds_fetch_retries: u32,
+
+    /// Base delay in milliseconds between blob fetch retries. The delay grows
+    /// linearly with the attempt number.
+    #[arg(
+        long = "pds-fetch-retry-delay",
+        env = "GIFDEX_CDN_PDS_FETCH_RETRY_DELAY",
+        default_value_t = 250
+    )]
+    pds_fetch_retry_delay: u64,
+
+    /// Maximum total bytes of blob data buffered in memory across all
+    /// in-flight fetches.
+    ///
+    /// Each uncached request can buffer up to the per-blob size limit, so
+    /// this caps worst-case memory use regardless of request concurrency.
+    /// Requests that would exceed the budget are rejected with a 503.
+    #[arg(
+        long = "fetch-memory-budget",
+        env = "GIFDEX_CDN_FETCH_MEMORY_BUDGET",
+        default_value_t = 512 * 1024 * 1024
+    )]
+    fetch_memory_budget: usize,
+
+    /// Comma-separated media types accepted for post media blobs.
+    ///
+    /// Should match the ingester's allow-list so the CDN never refuses to
+    /// serve media that was accepted at ingest time.
+    #[arg(
+        long = "media-mime-types",
+        env = "GIFDEX_CDN_MEDIA_MIME_TYPES",
+        value_delimiter = ',',
+        default_values_t = DEFAULT_MEDIA_MIME_TYPES.iter().map(|mime| mime.to_string())
+    )]
+    media_mime_types: Vec<String>,
+
+    /// Comma-separated media types accepted for avatar blobs.
+    #[arg(
+        long = "avatar-mime-types",
+        env = "GIFDEX_CDN_AVATAR_MIME_TYPES",
+        value_delimiter = ',',
+        default_values_t = DEFAULT_AVATAR_MIME_TYPES.iter().map(|mime| mime.to_string())
+    )]
+    avatar_mime_types: Vec<String>,
+
+    /// Maximum accepted size in bytes of a post's media blob.
+    ///
+    /// Should match the ingester's limit so the CDN never refuses to serve
+    /// media that was accepted at ingest time.
+    #[arg(
+        long = "max-blob-size",
+        env = "GIFDEX_CDN_MAX_BLOB_SIZE",
+        default_value_t = DEFAULT_MAX_BLOB_SIZE
+    )]
+    max_blob_size: usize,
+
+    /// Maximum accepted size in bytes of a profile avatar blob.
+    #[arg(
+        long = "max-avatar-size",
+        env = "GIFDEX_CDN_MAX_AVATAR_SIZE",
+        default_value_t = DEFAULT_MAX_AVATAR_SIZE
+    )]
+    max_avatar_size: usize,
+
+    /// Seconds a successfully served blob may be cached by clients and
+    /// intermediaries.
+    ///
+    /// Blobs are content-addressed, so successful responses are also marked
+    /// `immutable` and can carry a long lifetime safely.
+    #[arg(
+        long = "cache-max-age",
+        env = "GIFDEX_CDN_CACHE_MAX_AGE",
+        default_value_t = 31536000
+    )]
+    cache_max_age: u64,
+
+    /// Seconds a cache may keep serving a stale blob while it revalidates
+    /// against us in the background. Set to 0 to omit the directive.
+    #[arg(
+        long = "cache-stale-while-revalidate",
+        env = "GIFDEX_CDN_CACHE_STALE_WHILE_REVALIDATE",
+        default_value_t = 86400
+    )]
+    cache_stale_while_revalidate: u64,
+
+    /// Seconds a post's takedown-label lookup is cached before the database
+    /// is consulted again. Moderation actions can take up to this long to
+    /// start suppressing media delivery.
+    #[arg(
+        long = "takedown-cache-ttl",
+        env = "GIFDEX_CDN_TAKEDOWN_CACHE_TTL",
+        default_value_t = 60
+    )]
+    takedown_cache_ttl: u64,
+
+    /// Password protecting the admin endpoints (`POST /admin/prefetch`),
+    /// presented as basic auth with the `admin` username. The endpoints are
+    /// disabled when unset.
+    #[arg(long = "admin-password", env = "GIFDEX_CDN_ADMIN_PASSWORD")]
+    admin_password: Option<String>,
+
+    /// Allow blob fetches against plain-http PDSes.
+    ///
+    /// Off by default - outbound connections are HTTPS-only. Only meant for
+    /// local development against an `http://` dev PDS; never enable this in
+    /// production.
+    #[arg(
+        long = "allow-insecure-pds",
+        env = "GIFDEX_CDN_ALLOW_INSECURE_PDS"
+    )]
+    allow_insecure_pds: bool,
+
+    /// Shared secret used to verify signed media URLs minted by the AppView.
+    #[arg(
+        long = "media-signing-secret",
+        env = "GIFDEX_CDN_MEDIA_SIGNING_SECRET"
+    )]
+    media_signing_secret: Option<String>,
+
+    /// Reject media and thumbnail requests that don't carry a valid,
+    /// unexpired `sig`/`exp` pair. Avatars stay public. Requires
+    /// `--media-signing-secret`.
+    #[arg(
+        long = "require-signed-urls",
+        env = "GIFDEX_CDN_REQUIRE_SIGNED_URLS"
+    )]
+    require_signed_urls: bool,
+
+    /// OTLP gRPC endpoint to export trace spans to. Span export is disabled
+    /// when unset; only available when built with the `otel` feature.
+    #[cfg(feature = "otel")]
+    #[arg(long = "otlp-endpoint", env = "GIFDEX_CDN_OTLP_ENDPOINT")]
+    otlp_endpoint: Option<String>,
+
+    /// Emit logs as newline-delimited JSON instead of human-readable text.
+    #[arg(long = "log-json", env = "GIFDEX_CDN_LOG_JSON")]
+    log_json: bool,
+
+    /// Comma-separated list of origins allowed to make cross-origin requests,
+    /// or `*` to allow any origin.
+    #[arg(
+        long = "cors-allow-origin",
+        env = "GIFDEX_CDN_CORS_ALLOW_ORIGIN",
+        value_delimiter = ',',
+        default_value = "*"
+    )]
+    cors_allow_origin: Vec<String>,
 }
 
 struct AppState {
     database: Database,
-    http_client: reqwest::Client,
+    https_only: bool,
+    blob_cache: Option<BlobCache>,
+    pds_fetch_timeout: Duration,
+    pds_fetch_concurrency: usize,
+    pds_fetch_retries: u32,
+    pds_fetch_retry_delay: Duration,
+    pds_fetch_limits: Mutex<HashMap<String, Arc<Semaphore>>>,
+    pds_fetch_max_redirects: usize,
+    blocked_pds_ranges: Vec<ipnet::IpNet>,
+    fetch_memory_budget: Semaphore,
+    origin_fetches: IntCounterVec,
+    media_mime_types: Vec<String>,
+    avatar_mime_types: Vec<String>,
+    blob_limits: BlobLimits,
+    cache_control: String,
+    takedown_cache_ttl: Duration,
+    takedown_cache: Mutex<HashMap<(String, String), (bool, Instant)>>,
+    media_signing_secret: Option<String>,
+    require_signed_urls: bool,
+    admin_auth: Option<String>,
 }
 
-#[tokio::main]
-async fn main() -> Result<()> {
-    dotenv().ok();
-    tracing_subscriber::fmt()
-        .with_env_filter(EnvFilter::try_from_default_env().unwrap_or(EnvFilter::new("info")))
-        .init();
-    let args = Arguments::parse();
-    let app_state = Arc::new(AppState {
-        database: Database::new(&args.database_url).await?,
-        http_client: reqwest::Client::builder()
-            .https_only(true)
+impl AppState {
+    /// Build a one-off client for a vetted PDS fetch. DNS is pinned to the
+    /// addresses that already passed the block-list check, closing the window
+    /// where the fetch's own resolution could return different (rebound)
+    /// addresses, and redirects are not followed - the fetch loop follows
+    /// them manually so every hop is vetted the same way.
+    fn pinned_pds_client(
+        &self,
+        host: &str,
+        addrs: &[std::net::SocketAddr],
+    ) -> reqwest::Result<reqwest::Client> {
+        reqwest::Client::builder()
+            .https_only(self.https_only)
+            .redirect(reqwest::redirect::Policy::none())
+            .resolve_to_addrs(host, addrs)
+            // Give up on unresponsive PDSes quickly - the per-request timeout
+            // only needs to cover the body transfer of a well-behaved peer.
+            .connect_timeout(Duration::from_secs(10))
             .user_agent(concat!(
                 env!("CARGO_PKG_NAME"),
                 "/",
                 env!("CARGO_PKG_VERSION")
             ))
-            .build()?,
+            .build()
+    }
+
+    /// Reserve a slot for an outbound blob fetch to the given PDS host,
+    /// capping how many connections we open to any single origin at once.
+    /// Returns `None` when the host is already at its limit.
+    fn try_acquire_pds_fetch(&self, host: &str) -> Option<OwnedSemaphorePermit> {
+        let semaphore = self
+            .pds_fetch_limits
+            .lock()
+            .unwrap()
+            .entry(host.to_owned())
+            .or_insert_with(|| Arc::new(Semaphore::new(self.pds_fetch_concurrency)))
+            .clone();
+        semaphore.try_acquire_owned().ok()
+    }
+
+    /// Reserve `bytes` of the global fetch memory budget, bounding how much
+    /// blob data all in-flight fetches can buffer at once. Returns `None`
+    /// when the budget is exhausted.
+    fn try_acquire_fetch_memory(&self, bytes: usize) -> Option<SemaphorePermit<'_>> {
+        self.fetch_memory_budget
+            .try_acquire_many(bytes.try_into().unwrap_or(u32::MAX))
+            .ok()
+    }
+
+    /// Look up a post's cached takedown status. Returns `None` when the
+    /// status has never been looked up or the cached result has aged out.
+    fn cached_takedown(&self, did: &str, rkey: &str) -> Option<bool> {
+        self.takedown_cache
+            .lock()
+            .unwrap()
+            .get(&(did.to_owned(), rkey.to_owned()))
+            .filter(|(_, at)| at.elapsed() < self.takedown_cache_ttl)
+            .map(|(taken_down, _)| *taken_down)
+    }
+
+    /// Cache a post's takedown status for [`Self::takedown_cache_ttl`].
+    /// Expired entries are pruned on insert so the cache can't grow without
+    /// bound across distinct posts.
+    fn cache_takedown(&self, did: &str, rkey: &str, taken_down: bool) {
+        let mut cache = self.takedown_cache.lock().unwrap();
+        cache.retain(|_, (_, at)| at.elapsed() < self.takedown_cache_ttl);
+        cache.insert((did.to_owned(), rkey.to_owned()), (taken_down, Instant::now()));
+    }
+
+    /// Count the outcome of a blob fetch against an upstream PDS.
+    fn record_origin_fetch(&self, outcome: &str) {
+        self.origin_fetches.with_label_values(&[outcome]).inc();
+    }
+
+    /// Whether `mime` is an accepted media type for post media blobs.
+    fn is_allowed_media_mime(&self, mime: &str) -> bool {
+        self.media_mime_types.iter().any(|allowed| allowed == mime)
+    }
+
+    /// Whether `mime` is an accepted media type for avatar blobs.
+    fn is_allowed_avatar_mime(&self, mime: &str) -> bool {
+        self.avatar_mime_types.iter().any(|allowed| allowed == mime)
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    dotenv().ok();
+    let args = Arguments::parse();
+    // Structured JSON output for log aggregators when requested, the default
+    // human-readable text format otherwise.
+    let log_filter = EnvFilter::try_from_default_env().unwrap_or(EnvFilter::new("info"));
+    #[cfg(feature = "otel")]
+    match &args.otlp_endpoint {
+        Some(endpoint) => init_otlp_tracing(endpoint, log_filter, args.log_json)?,
+        None => init_local_tracing(log_filter, args.log_json),
+    }
+    #[cfg(not(feature = "otel"))]
+    init_local_tracing(log_filter, args.log_json);
+    let metrics = Arc::new(HttpMetrics::new());
+    let origin_fetches = IntCounterVec::new(
+        Opts::new(
+            "cdn_origin_fetch_total",
+            "Number of blob fetches against upstream PDSes, by outcome.",
+        ),
+        &["outcome"],
+    )?;
+    metrics.registry().register(Box::new(origin_fetches.clone()))?;
+    anyhow::ensure!(
+        !args.require_signed_urls || args.media_signing_secret.is_some(),
+        "--require-signed-urls needs --media-signing-secret to verify against"
+    );
+    anyhow::ensure!(
+        args.fetch_memory_budget >= args.max_blob_size,
+        "--fetch-memory-budget must be at least --max-blob-size or no media fetch could ever proceed"
+    );
+    if args.allow_insecure_pds {
+        tracing::warn!(
+            "--allow-insecure-pds is set: blob fetches over plain http are allowed. \
+             This is only meant for local development - never run production like this."
+        );
+    }
+    let database_config = DatabaseConfig {
+        max_connections: args.database_max_connections,
+        acquire_timeout: Duration::from_secs(args.database_acquire_timeout),
+        idle_timeout: Duration::from_secs(args.database_idle_timeout),
+    };
+    let blob_cache = match args.cache_dir {
+        Some(dir) => Some(BlobCache::new(dir, args.cache_max_size).await?),
+        None => None,
+    };
+    let app_state = Arc::new(AppState {
+        database: Database::new(&args.database_url, &database_config).await?,
+        blob_cache,
+        https_only: !args.allow_insecure_pds,
+        pds_fetch_timeout: Duration::from_secs(args.pds_fetch_timeout),
+        pds_fetch_concurrency: args.pds_fetch_concurrency,
+        pds_fetch_retries: args.pds_fetch_retries,
+        pds_fetch_retry_delay: Duration::from_millis(args.pds_fetch_retry_delay),
+        pds_fetch_limits: Mutex::new(HashMap::new()),
+        pds_fetch_max_redirects: args.pds_fetch_max_redirects,
+        blocked_pds_ranges: args.blocked_pds_ranges,
+        fetch_memory_budget: Semaphore::new(args.fetch_memory_budget),
+        origin_fetches,
+        media_mime_types: args.media_mime_types,
+        avatar_mime_types: args.avatar_mime_types,
+        blob_limits: BlobLimits {
+            max_blob_size: args.max_blob_size,
+            max_avatar_size: args.max_avatar_size,
+        },
+        // Built once - every successful response carries the same directives.
+        cache_control: match args.cache_stale_while_revalidate {
+            0 => format!("public, max-age={}, immutable", args.cache_max_age),
+            swr => format!(
+                "public, max-age={}, immutable, stale-while-revalidate={swr}",
+                args.cache_max_age
+            ),
+        },
+        takedown_cache_ttl: Duration::from_secs(args.takedown_cache_ttl),
+        takedown_cache: Mutex::new(HashMap::new()),
+        media_signing_secret: args.media_signing_secret,
+        require_signed_urls: args.require_signed_urls,
+        // Precompute the expected Authorization header so each admin request
+        // is a straight string comparison.
+        admin_auth: args.admin_password.map(|password| {
+            use base64::Engine;
+            format!(
+                "Basic {}",
+                base64::engine::general_purpose::STANDARD.encode(format!("admin:{password}"))
+            )
+        }),
     });
 
+    // With an explicit allow-list the matching origin is echoed back
+    // per-request (and preflights answered accordingly) rather than the
+    // wildcard, so responses stay cacheable per-origin.
+    let cors = match args.cors_allow_origin.iter().any(|origin| origin == "*") {
+        true => CorsLayer::new().allow_origin("*".parse::<HeaderValue>().unwrap()),
+        false => CorsLayer::new().allow_origin(
+            args.cors_allow_origin
+                .iter()
+                .map(|origin| origin.parse::<HeaderValue>())
+                .collect::<Result<Vec<_>, _>>()
+                .context("invalid CORS origin provided")?,
+        ),
+    }
+    .allow_methods([Method::GET, Method::OPTIONS])
+    .allow_headers(Any);
+
     let router = Router::new()
         .route("/", get(async || "Gifdex CDN"))
         .route("/media/{did}/{rkey}", get(get_gif_handler))
         .route("/avatar/{did}/{cid}", get(get_avatar_handler))
+        .route("/thumb/{did}/{rkey}", get(get_thumb_handler))
+        .route("/admin/prefetch", post(prefetch_handler))
         .nest(
             "/xrpc",
-            Router::new().route("/", get(async || StatusCode::OK)),
+            Router::new()
+                .route("/", get(async || StatusCode::OK))
+                .route("/com.atproto.sync.getBlob", get(get_blob_handler)),
         )
+        // Echo the request's correlation id back on the response.
+        .layer(PropagateRequestIdLayer::x_request_id())
         .layer(
             TraceLayer::new_for_http()
-                .make_span_with(trace::DefaultMakeSpan::new().level(Level::INFO))
+                .make_span_with(|request: &Request| {
+                    let request_id = request
+                        .headers()
+                        .get("x-request-id")
+                        .and_then(|value| value.to_str().ok())
+                        .unwrap_or("unknown");
+                    tracing::info_span!(
+                        "request",
+                        method = %request.method(),
+                        path = %request.uri().path(),
+                        request_id,
+                    )
+                })
                 .on_request(DefaultOnRequest::default().level(Level::INFO))
-                .on_response(DefaultOnResponse::default().level(Level::INFO))
+                .on_response(
+                    |response: &axum::response::Response,
+                     latency: std::time::Duration,
+                     _span: &tracing::Span| {
+                        let upstream_host = response
+                            .headers()
+                            .get(routes::UPSTREAM_HOST_HEADER)
+                            .and_then(|value| value.to_str().ok());
+                        tracing::info!(
+                            status = response.status().as_u16(),
+                            latency_ms = latency.as_millis() as u64,
+                            upstream_host,
+                            "finished processing request"
+                        );
+                    },
+                )
                 .on_failure(DefaultOnFailure::default().level(Level::INFO)),
         )
+        .layer(axum_middleware::from_fn_with_state(
+            metrics.clone(),
+            track_http,
+        ))
         .layer(NormalizePathLayer::trim_trailing_slash())
         .layer(CatchPanicLayer::new())
+        .layer(cors)
         .layer(axum_middleware::from_fn(
             async |req: Request, next: Next| {
                 let mut res = next.run(req).await;
+                let is_error = res.status().is_client_error() || res.status().is_server_error();
                 let res_headers = res.headers_mut();
                 res_headers.insert(
                     header::SERVER,
                     HeaderValue::from_static(env!("CARGO_PKG_NAME")),
                 );
                 res_headers.insert("X-Robots-Tag", HeaderValue::from_static("none"));
-                res_headers.insert(
-                    header::ACCESS_CONTROL_ALLOW_ORIGIN,
-                    HeaderValue::from_static("*"),
-                );
+                // Never let an intermediary cache an error as if it were the
+                // media itself - a 404 for a not-yet-ingested post or a 502
+                // from a flaky PDS should be retried, not remembered.
+                if is_error {
+                    res_headers.insert(
+                        header::CACHE_CONTROL,
+                        HeaderValue::from_static("no-store"),
+                    );
+                }
                 res
             },
         ))
-        .with_state(app_state);
+        // Give every request a correlation id - an incoming `X-Request-Id`
+        // header is kept, otherwise a UUID is generated - before anything
+        // else sees the request.
+        .layer(SetRequestIdLayer::x_request_id(MakeRequestUuid))
+        .with_state(app_state)
+        // Scrapes stay outside the instrumented, CORS-allowed routes.
+        .merge(metrics_router(metrics));
 
     let tcp_listener = TcpListener::bind(args.address).await?;
     info!(
@@ -112,6 +608,54 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
+/// Initialise the plain local tracing subscriber.
+fn init_local_tracing(log_filter: EnvFilter, log_json: bool) {
+    match log_json {
+        true => tracing_subscriber::fmt()
+            .with_env_filter(log_filter)
+            .json()
+            .init(),
+        false => tracing_subscriber::fmt().with_env_filter(log_filter).init(),
+    }
+}
+
+/// Initialise tracing with an OTLP span exporter layered on top of the local
+/// log output, shipping spans to the collector at `endpoint`. The W3C trace
+/// context propagator is installed globally so outbound requests can carry
+/// the active trace.
+#[cfg(feature = "otel")]
+fn init_otlp_tracing(endpoint: &str, log_filter: EnvFilter, log_json: bool) -> Result<()> {
+    use opentelemetry::trace::TracerProvider as _;
+    use opentelemetry_otlp::WithExportConfig;
+    use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+    let exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_tonic()
+        .with_endpoint(endpoint)
+        .build()?;
+    let provider = opentelemetry_sdk::trace::SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(
+            opentelemetry_sdk::Resource::builder()
+                .with_service_name(env!("CARGO_PKG_NAME"))
+                .build(),
+        )
+        .build();
+    let tracer = provider.tracer(env!("CARGO_PKG_NAME"));
+    opentelemetry::global::set_tracer_provider(provider);
+    opentelemetry::global::set_text_map_propagator(
+        opentelemetry_sdk::propagation::TraceContextPropagator::new(),
+    );
+    let registry = tracing_subscriber::registry()
+        .with(log_filter)
+        .with(tracing_opentelemetry::layer().with_tracer(tracer));
+    match log_json {
+        true => registry.with(tracing_subscriber::fmt::layer().json()).init(),
+        false => registry.with(tracing_subscriber::fmt::layer()).init(),
+    }
+    Ok(())
+}
+
 // https://github.com/tokio-rs/axum/blob/15917c6dbcb4a48707a20e9cfd021992a279a662/examples/graceful-shutdown/src/main.rs#L55
 async fn shutdown_signal() {
     let ctrl_c = async {
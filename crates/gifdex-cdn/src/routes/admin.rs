@@ -0,0 +1,109 @@
+use crate::{AppState, routes::fetch_verified_blob};
+use axum::{
+    Json,
+    extract::State,
+    http::{HeaderMap, StatusCode, header},
+    response::IntoResponse,
+};
+use cid::Cid;
+use jacquard_common::types::did::Did;
+use std::sync::Arc;
+use subtle::ConstantTimeEq;
+use tracing::warn;
+
+/// A blob to warm, addressed either as a post - whose `rkey` carries the
+/// media CID - or directly by `cid` for avatars and other blobs.
+#[derive(serde::Deserialize)]
+pub struct PrefetchEntry {
+    did: String,
+    rkey: Option<String>,
+    cid: Option<String>,
+}
+
+/// The outcome of warming a single entry.
+#[derive(serde::Serialize)]
+pub struct PrefetchResult {
+    did: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rkey: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cid: Option<String>,
+    ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Fetch, verify and cache each listed blob without serving it, so operators
+/// can pre-warm popular content ahead of a traffic spike instead of relying
+/// on organic cache population. Guarded by the admin password.
+pub async fn prefetch_handler(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Json(entries): Json<Vec<PrefetchEntry>>,
+) -> impl IntoResponse {
+    // With no admin password configured the endpoint doesn't exist as far as
+    // callers are concerned.
+    let Some(expected) = &state.admin_auth else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    // Constant-time comparison so the response latency doesn't leak how much
+    // of the credential matched.
+    let authorized = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|presented| presented.as_bytes().ct_eq(expected.as_bytes()).into());
+    if !authorized {
+        warn!("rejected prefetch request with missing or invalid credentials");
+        return (
+            StatusCode::UNAUTHORIZED,
+            [(header::WWW_AUTHENTICATE, "Basic")],
+            "Invalid admin credentials",
+        )
+            .into_response();
+    }
+    if state.blob_cache.is_none() {
+        return (
+            StatusCode::CONFLICT,
+            "Prefetching requires a blob cache (--cache-dir)",
+        )
+            .into_response();
+    }
+
+    let mut results = Vec::with_capacity(entries.len());
+    for entry in entries {
+        let error = prefetch_entry(&state, &entry).await.err();
+        if let Some(error) = &error {
+            warn!("failed to prefetch blob for {}: {error}", entry.did);
+        }
+        results.push(PrefetchResult {
+            ok: error.is_none(),
+            error,
+            did: entry.did,
+            rkey: entry.rkey,
+            cid: entry.cid,
+        });
+    }
+    Json(results).into_response()
+}
+
+/// Resolve an entry's CID and run it through the shared verified fetch, which
+/// stores the blob in the local cache on success.
+async fn prefetch_entry(state: &AppState, entry: &PrefetchEntry) -> Result<(), String> {
+    let did = Did::new(&entry.did).map_err(|err| format!("invalid DID: {err:?}"))?;
+    let cid = match (&entry.cid, &entry.rkey) {
+        (Some(cid), _) => {
+            Cid::try_from(cid.as_str()).map_err(|err| format!("invalid CID: {err:?}"))?
+        }
+        (None, Some(rkey)) => {
+            let (_, cid) = rkey
+                .split_once(':')
+                .ok_or_else(|| "malformed rkey (expected tid:cid format)".to_owned())?;
+            Cid::try_from(cid).map_err(|err| format!("invalid CID in rkey: {err:?}"))?
+        }
+        (None, None) => return Err("entry needs either a cid or an rkey".to_owned()),
+    };
+    fetch_verified_blob(state, &did, &cid, state.blob_limits.max_blob_size)
+        .await
+        .map(|_| ())
+        .map_err(|err| err.describe())
+}
@@ -1,20 +1,23 @@
-use crate::{AppState, MAX_AVATAR_SIZE, routes::stream_with_limit};
+use crate::{
+    AppState,
+    routes::{UPSTREAM_HOST_HEADER, check_etag, fetch_verified_blob},
+};
 use axum::{
     body::Body,
     extract::{Path, State},
-    http::{Response, StatusCode, header},
+    http::{HeaderMap, Method, Response, StatusCode, header},
     response::IntoResponse,
 };
 use cid::Cid;
 use jacquard_common::types::did::Did;
-use multihash_codetable::{Code, MultihashDigest};
-use reqwest::Url;
 use sqlx::query;
 use std::sync::Arc;
 use tracing::warn;
 
 pub async fn get_avatar_handler(
+    method: Method,
     Path((did, cid)): Path<(String, String)>,
+    headers: HeaderMap,
     State(state): State<Arc<AppState>>,
 ) -> impl IntoResponse {
     // Strictly verify the received path types.
@@ -41,6 +44,11 @@ pub async fn get_avatar_handler(
         }
     };
 
+    let (etag, not_modified) = check_etag(&state, &headers, &cid);
+    if let Some(response) = not_modified {
+        return response;
+    }
+
     // Ensur the CID that's being requested matches our records.
     match query!(
         "SELECT EXISTS(SELECT 1 FROM accounts WHERE did = $1 AND avatar_blob_cid = $2)",
@@ -64,98 +72,57 @@ pub async fn get_avatar_handler(
         }
     };
 
-    // Get the user's PDS URL from our accounts data.
-    let pds_url = match query!("SELECT pds FROM accounts WHERE did = $1", did.as_str())
-        .fetch_optional(state.database.executor())
-        .await
-    {
-        Ok(Some(record)) if record.pds.is_some() => {
-            Url::parse(&format!("https://{}", record.pds.unwrap())).unwrap()
-        }
-        Ok(Some(_)) | Ok(None) => {
-            warn!("No PDS endpoint found for {did}");
-            return (
-                StatusCode::NOT_FOUND,
-                "No AtprotoPersonalDataServer service endpoint found in resolved DID document",
+    // HEAD requests are answered without transferring the blob. Avatar mime
+    // types aren't stored on the account record, so Content-Type and
+    // Content-Length are only included when the blob is already in the local
+    // cache.
+    if method == Method::HEAD {
+        let mut builder = Response::builder()
+            .status(StatusCode::OK)
+            .header(header::ETAG, &etag)
+            .header(
+                header::CONTENT_SECURITY_POLICY,
+                "default-src 'none'; sandbox",
             )
-                .into_response();
-        }
-        Err(err) => {
-            warn!("failed to resolve DID {did}: {err:?}");
-            return (StatusCode::BAD_GATEWAY, "Failed to resolve DID").into_response();
-        }
-    };
-
-    let blob_url = {
-        let mut url = match pds_url.join("/xrpc/com.atproto.sync.getBlob") {
-            Ok(url) => url,
-            Err(err) => {
-                warn!("failed to build XRPC URL: {err:?}");
-                return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+            .header(header::X_CONTENT_TYPE_OPTIONS, "nosniff")
+            .header(header::CACHE_CONTROL, state.cache_control.as_str());
+        if let Some(cache) = &state.blob_cache
+            && let Some(bytes) = cache.get(&did, &cid).await
+        {
+            if let Some(mime_type) = infer::get(&bytes) {
+                builder = builder.header(header::CONTENT_TYPE, mime_type.mime_type());
             }
-        };
-        url.set_query(Some(&format!("did={did}&cid={cid}")));
-        url
-    };
-
-    // Fetch the blob from the user's PDS
-    let response = match state.http_client.get(blob_url).send().await {
-        Ok(resp) => resp,
-        Err(err) => {
-            warn!("failed to fetch blob from PDS: {err:?}");
-            return (
-                StatusCode::BAD_GATEWAY,
-                "Failed to fetch blob from upstream PDS",
-            )
-                .into_response();
+            builder = builder.header(header::CONTENT_LENGTH, bytes.len());
         }
-    };
-    if !response.status().is_success() {
-        warn!("PDS returned error status: {}", response.status());
-        return (
-            StatusCode::BAD_GATEWAY,
-            "Failed to fetch blob from upstream PDS",
-        )
-            .into_response();
+        return builder.body(Body::empty()).unwrap().into_response();
     }
-    let bytes = match stream_with_limit(response, MAX_AVATAR_SIZE).await {
-        Ok(bytes) => bytes,
-        Err(status) => return status.into_response(),
-    };
 
-    // Strictly validate the blob, computing and comparing it's CID hash and best-guessing it's mime-type.
-    let computed_cid = match cid.hash().code() {
-        0x12 => Cid::new_v1(0x55, Code::Sha2_256.digest(&bytes)),
-        hash @ _ => {
-            warn!("unsupported hash algorithm: 0x{hash:x}");
-            return (
-                StatusCode::UNPROCESSABLE_ENTITY,
-                "Unsupported CID hash algorithm",
-            )
-                .into_response();
-        }
-    };
-    if computed_cid != cid {
-        warn!("CID mismatch: expected {cid}, computed {computed_cid}");
-        return StatusCode::BAD_GATEWAY.into_response();
-    }
-    let mime_type = match infer::get(&bytes).map(|t| t) {
-        Some(m) if matches!(m.mime_type(), "image/png" | "image/jpeg" | "image/webp") => m,
-        format @ _ => {
+    // Fetch the blob - from the local cache when possible, otherwise from the
+    // user's PDS with size limits and CID verification applied.
+    let (bytes, upstream_host) =
+        match fetch_verified_blob(&state, &did, &cid, state.blob_limits.max_avatar_size).await {
+            Ok(blob) => blob,
+            Err(err) => return err.into_response(),
+        };
+    let mime_type = match infer::get(&bytes) {
+        Some(m) if state.is_allowed_avatar_mime(m.mime_type()) => m,
+        format => {
             warn!("invalid or unsupported image format: {format:?}");
             return StatusCode::UNPROCESSABLE_ENTITY.into_response();
         }
     };
 
-    Response::builder()
+    let mut builder = Response::builder()
         .status(StatusCode::OK)
         .header(header::CONTENT_TYPE, mime_type.mime_type())
+        .header(header::ETAG, &etag)
         .header(
             header::CONTENT_SECURITY_POLICY,
             "default-src 'none'; sandbox",
         )
         .header(header::X_CONTENT_TYPE_OPTIONS, "nosniff")
-        .header(header::CACHE_CONTROL, "public, max-age=604800")
+        .header(header::CACHE_CONTROL, state.cache_control.as_str())
+        .header(header::CONTENT_LENGTH, bytes.len())
         .header(
             header::CONTENT_DISPOSITION,
             format!(
@@ -163,12 +130,9 @@ pub async fn get_avatar_handler(
                 "avatar",
                 mime_type.extension()
             ),
-        )
-        .header(
-            "Upstream-PDS",
-            format!(" {}", pds_url.host_str().unwrap_or("unknown")),
-        )
-        .body(Body::from(bytes))
-        .unwrap()
-        .into_response()
+        );
+    if let Some(host) = &upstream_host {
+        builder = builder.header(UPSTREAM_HOST_HEADER, host);
+    }
+    builder.body(Body::from(bytes)).unwrap().into_response()
 }
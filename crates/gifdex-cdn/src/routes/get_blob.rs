@@ -0,0 +1,121 @@
+use crate::{
+    AppState,
+    routes::{UPSTREAM_HOST_HEADER, check_etag, fetch_verified_blob},
+};
+use axum::{
+    body::Body,
+    extract::{Query, State},
+    http::{HeaderMap, Response, StatusCode, header},
+    response::IntoResponse,
+};
+use cid::Cid;
+use jacquard_common::types::did::Did;
+use sqlx::query;
+use std::sync::Arc;
+use tracing::warn;
+
+#[derive(serde::Deserialize)]
+pub struct GetBlobParams {
+    did: String,
+    cid: String,
+}
+
+/// `com.atproto.sync.getBlob` passthrough so atproto-native tooling can fetch
+/// blobs through the CDN without knowing its media/avatar URL scheme.
+pub async fn get_blob_handler(
+    Query(params): Query<GetBlobParams>,
+    headers: HeaderMap,
+    State(state): State<Arc<AppState>>,
+) -> impl IntoResponse {
+    // Strictly verify the received query types.
+    let did = match Did::new(&params.did) {
+        Ok(did) => did,
+        Err(err) => {
+            warn!("invalid DID '{}': {err:?}", params.did);
+            return (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "Invalid or unprocessable DID",
+            )
+                .into_response();
+        }
+    };
+    let cid = match Cid::try_from(params.cid.as_str()) {
+        Ok(cid) => cid,
+        Err(err) => {
+            warn!("invalid CID '{}': {err:?}", params.cid);
+            return (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "Invalid or unprocessable CID",
+            )
+                .into_response();
+        }
+    };
+
+    let (etag, not_modified) = check_etag(&state, &headers, &cid);
+    if let Some(response) = not_modified {
+        return response;
+    }
+
+    // Only relay blobs we actually host a record for - either a post's media
+    // blob or an account's avatar. The match also picks the size limit and
+    // mime allow-list to hold the blob to.
+    let referenced = match query!(
+        "SELECT \
+            EXISTS(SELECT 1 FROM posts \
+                WHERE did = $1 AND media_blob_cid = $2) as \"is_media!\", \
+            EXISTS(SELECT 1 FROM accounts \
+                WHERE did = $1 AND avatar_blob_cid = $2) as \"is_avatar!\"",
+        did.as_str(),
+        cid.to_string()
+    )
+    .fetch_one(state.database.executor())
+    .await
+    {
+        Ok(referenced) => referenced,
+        Err(err) => {
+            warn!("database error: {err:?}");
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+    let max_size = match (referenced.is_media, referenced.is_avatar) {
+        (true, _) => state.blob_limits.max_blob_size,
+        (_, true) => state.blob_limits.max_avatar_size,
+        (false, false) => {
+            return (StatusCode::NOT_FOUND, "Blob not found in records").into_response();
+        }
+    };
+
+    // Fetch the blob - from the local cache when possible, otherwise from the
+    // user's PDS with size limits and CID verification applied.
+    let (bytes, upstream_host) = match fetch_verified_blob(&state, &did, &cid, max_size).await {
+        Ok(blob) => blob,
+        Err(err) => return err.into_response(),
+    };
+    let allowed = |mime: &str| match referenced.is_media {
+        true => state.is_allowed_media_mime(mime),
+        false => state.is_allowed_avatar_mime(mime),
+    };
+    let mime_type = match infer::get(&bytes) {
+        Some(m) if allowed(m.mime_type()) => m,
+        format => {
+            warn!("invalid or unsupported image format: {format:?}");
+            return StatusCode::UNPROCESSABLE_ENTITY.into_response();
+        }
+    };
+
+    let mut builder = Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, mime_type.mime_type())
+        .header(header::ETAG, &etag)
+        .header(
+            header::CONTENT_SECURITY_POLICY,
+            "default-src 'none'; sandbox",
+        )
+        .header(header::X_CONTENT_TYPE_OPTIONS, "nosniff")
+        .header(header::CACHE_CONTROL, state.cache_control.as_str())
+        .header(header::CONTENT_LENGTH, bytes.len());
+    if let Some(host) = &upstream_host {
+        builder = builder.header(UPSTREAM_HOST_HEADER, host);
+    }
+    builder.body(Body::from(bytes)).unwrap().into_response()
+}
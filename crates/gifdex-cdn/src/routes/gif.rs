@@ -1,22 +1,205 @@
-use crate::{AppState, MAX_BLOB_SIZE, routes::stream_with_limit};
+use crate::{
+    AppState,
+    routes::{UPSTREAM_HOST_HEADER, check_etag, check_media_signature, check_takedown, fetch_verified_blob},
+};
 use axum::{
-    body::Body,
-    extract::{Path, State},
-    http::{Response, StatusCode, header},
+    body::{Body, Bytes},
+    extract::{Path, Query, State},
+    http::{HeaderMap, Method, Response, StatusCode, header},
     response::IntoResponse,
 };
 use cid::Cid;
 use jacquard_common::types::{did::Did, tid::Tid};
-use multihash_codetable::{Code, MultihashDigest};
-use reqwest::Url;
 use sqlx::query;
 use std::sync::Arc;
 use tracing::warn;
 
+#[derive(serde::Deserialize)]
+pub struct GifParams {
+    /// Requested output format. Only `webp` is recognised; anything else serves
+    /// the original blob.
+    format: Option<String>,
+    /// Expiry (epoch milliseconds) of a signed URL.
+    exp: Option<i64>,
+    /// HMAC signature over the media resource and `exp`.
+    sig: Option<String>,
+}
+
+/// Transcode an animated GIF into an animated WebP, preserving frame timing.
+fn transcode_gif_to_webp(bytes: &[u8]) -> anyhow::Result<Vec<u8>> {
+    use image::AnimationDecoder;
+    let decoder = image::codecs::gif::GifDecoder::new(std::io::Cursor::new(bytes))?;
+    let frames = decoder.into_frames().collect_frames()?;
+    anyhow::ensure!(!frames.is_empty(), "gif has no frames");
+
+    let (width, height) = frames[0].buffer().dimensions();
+    let mut config =
+        webp::WebPConfig::new().map_err(|()| anyhow::anyhow!("invalid webp config"))?;
+    config.quality = 80.0;
+    let mut encoder = webp::AnimEncoder::new(width, height, &config);
+    let mut timestamp = 0i32;
+    for frame in &frames {
+        encoder.add_frame(webp::AnimFrame::from_rgba(
+            frame.buffer().as_raw(),
+            width,
+            height,
+            timestamp,
+        ));
+        let (numerator, denominator) = frame.delay().numer_denom_ms();
+        timestamp += (numerator / denominator.max(1)) as i32;
+    }
+    let memory = encoder.encode();
+    anyhow::ensure!(!memory.is_empty(), "webp encoder produced no output");
+    Ok(memory.to_vec())
+}
+
+/// Transcode the blob to WebP if requested and applicable, falling back to the
+/// original bytes if the transcode fails.
+async fn maybe_transcode(
+    bytes: Bytes,
+    mime_type: &'static str,
+    wants_webp: bool,
+) -> (Bytes, &'static str) {
+    if !wants_webp || mime_type != "image/gif" {
+        return (bytes, mime_type);
+    }
+    let original = bytes.clone();
+    match tokio::task::spawn_blocking(move || transcode_gif_to_webp(&original)).await {
+        Ok(Ok(webp)) => (Bytes::from(webp), "image/webp"),
+        Ok(Err(err)) => {
+            warn!("failed to transcode gif to webp: {err:?}");
+            (bytes, mime_type)
+        }
+        Err(err) => {
+            warn!("gif to webp transcode task panicked: {err:?}");
+            (bytes, mime_type)
+        }
+    }
+}
+
+/// A parsed `Range: bytes=` header, before being resolved against the blob length.
+enum RequestedRange {
+    /// `bytes=start-` / `bytes=start-end`
+    FromStart { start: u64, end: Option<u64> },
+    /// `bytes=-suffix`
+    Suffix(u64),
+}
+
+fn parse_range_header(value: &str) -> Option<RequestedRange> {
+    let spec = value.strip_prefix("bytes=")?;
+    // Multi-range requests are valid per the spec but not worth supporting here.
+    if spec.contains(',') {
+        return None;
+    }
+    let (start, end) = spec.split_once('-')?;
+    if start.is_empty() {
+        Some(RequestedRange::Suffix(end.parse().ok()?))
+    } else {
+        let start = start.parse().ok()?;
+        let end = match end.is_empty() {
+            true => None,
+            false => Some(end.parse().ok()?),
+        };
+        if let Some(end) = end
+            && end < start
+        {
+            return None;
+        }
+        Some(RequestedRange::FromStart { start, end })
+    }
+}
+
+impl RequestedRange {
+    /// Resolve to inclusive byte offsets within a blob of `len` bytes, or `None`
+    /// if the range is unsatisfiable.
+    fn resolve(&self, len: u64) -> Option<(u64, u64)> {
+        match *self {
+            RequestedRange::FromStart { start, end } => match start < len {
+                true => Some((start, end.map_or(len - 1, |end| end.min(len - 1)))),
+                false => None,
+            },
+            RequestedRange::Suffix(suffix) => match suffix > 0 && len > 0 {
+                true => Some((len.saturating_sub(suffix), len - 1)),
+                false => None,
+            },
+        }
+    }
+}
+
+/// Build the gif response, serving the requested slice with `206 Partial Content`
+/// if a range was given and it is satisfiable against the blob.
+fn gif_response(
+    bytes: Bytes,
+    mime_type: &str,
+    title: &str,
+    etag: &str,
+    cache_control: &str,
+    range: Option<&RequestedRange>,
+    upstream_host: Option<&str>,
+) -> axum::response::Response {
+    let len = bytes.len() as u64;
+    let (status, body, content_range) = match range {
+        Some(range) => match range.resolve(len) {
+            Some((start, end)) => (
+                StatusCode::PARTIAL_CONTENT,
+                bytes.slice(start as usize..=end as usize),
+                Some(format!("bytes {start}-{end}/{len}")),
+            ),
+            None => {
+                warn!("unsatisfiable range for blob of {len} bytes");
+                return Response::builder()
+                    .status(StatusCode::RANGE_NOT_SATISFIABLE)
+                    .header(header::CONTENT_RANGE, format!("bytes */{len}"))
+                    .body(Body::empty())
+                    .unwrap()
+                    .into_response();
+            }
+        },
+        None => (StatusCode::OK, bytes, None),
+    };
+
+    let mut builder = Response::builder()
+        .status(status)
+        .header(header::CONTENT_TYPE, mime_type)
+        .header(header::ETAG, etag)
+        .header(header::ACCEPT_RANGES, "bytes")
+        .header(
+            header::CONTENT_SECURITY_POLICY,
+            "default-src 'none'; sandbox",
+        )
+        .header(header::X_CONTENT_TYPE_OPTIONS, "nosniff")
+        .header(header::CACHE_CONTROL, cache_control)
+        .header(header::CONTENT_LENGTH, body.len())
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{title}\""),
+        );
+    if let Some(content_range) = content_range {
+        builder = builder.header(header::CONTENT_RANGE, content_range);
+    }
+    if let Some(host) = upstream_host {
+        builder = builder.header(UPSTREAM_HOST_HEADER, host);
+    }
+    builder.body(Body::from(body)).unwrap().into_response()
+}
+
 pub async fn get_gif_handler(
+    method: Method,
     Path((did, rkey)): Path<(String, String)>,
+    Query(params): Query<GifParams>,
+    headers: HeaderMap,
     State(state): State<Arc<AppState>>,
 ) -> impl IntoResponse {
+    let wants_webp = params.format.as_deref() == Some("webp");
+
+    // When signed URL enforcement is on, requests without a valid signature
+    // are turned away before any other work happens.
+    if let Some(response) =
+        check_media_signature(&state, &did, &rkey, params.exp, params.sig.as_deref())
+    {
+        return response;
+    }
+
     // Strictly verify the received path types.
     let did = match Did::new(&did) {
         Ok(did) => did,
@@ -63,9 +246,23 @@ pub async fn get_gif_handler(
         }
     };
 
+    let (etag, not_modified) = check_etag(&state, &headers, &rkey_cid);
+    if let Some(response) = not_modified {
+        return response;
+    }
+
+    // Parse the Range header up front. Specs we can't parse or don't support
+    // (like multi-range requests) are ignored per RFC 9110 and answered with
+    // the full body - 416 is reserved for well-formed but unsatisfiable
+    // ranges, which `resolve` handles later.
+    let range = headers
+        .get(header::RANGE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(parse_range_header);
+
     // Ensure the post exists in our records.
     let post = match query!(
-        "SELECT title FROM posts WHERE did = $1 AND rkey = $2",
+        "SELECT title, media_blob_mime FROM posts WHERE did = $1 AND rkey = $2",
         did.as_str(),
         rkey
     )
@@ -82,107 +279,67 @@ pub async fn get_gif_handler(
         }
     };
 
-    // Get the user's PDS URL from our accounts data.
-    let pds_url = match query!("SELECT pds FROM accounts WHERE did = $1", did.as_str())
-        .fetch_optional(state.database.executor())
-        .await
-    {
-        Ok(Some(record)) if record.pds.is_some() => {
-            Url::parse(&format!("https://{}", record.pds.unwrap())).unwrap()
-        }
-        Ok(Some(_)) | Ok(None) => {
-            warn!("No PDS endpoint found for {did}");
-            return (
-                StatusCode::NOT_FOUND,
-                "No AtprotoPersonalDataServer service endpoint found in resolved DID document",
-            )
-                .into_response();
-        }
-        Err(err) => {
-            warn!("failed to resolve DID {did}: {err:?}");
-            return (StatusCode::BAD_GATEWAY, "Failed to resolve DID").into_response();
-        }
-    };
+    // A takedown label wins over everything else - the post stays in our
+    // records, but its media must not be served.
+    if let Some(response) = check_takedown(&state, did.as_str(), &rkey).await {
+        return response;
+    }
 
-    let blob_url = {
-        let mut url = match pds_url.join("/xrpc/com.atproto.sync.getBlob") {
-            Ok(url) => url,
-            Err(err) => {
-                warn!("failed to build XRPC URL: {err:?}");
-                return StatusCode::INTERNAL_SERVER_ERROR.into_response();
-            }
+    // HEAD requests are answered from our own records without transferring
+    // the blob. The mime type comes from the stored record; Content-Length is
+    // only known - and only included - when the original blob is already in
+    // the local cache and no transcode was requested.
+    if method == Method::HEAD {
+        let transcoded = wants_webp && post.media_blob_mime == "image/gif";
+        let mime_type = match transcoded {
+            true => "image/webp",
+            false => post.media_blob_mime.as_str(),
         };
-        url.set_query(Some(&format!("did={did}&cid={rkey_cid}")));
-        url
-    };
-
-    // Fetch the blob from the user's PDS
-    let response = match state.http_client.get(blob_url).send().await {
-        Ok(resp) => resp,
-        Err(err) => {
-            warn!("failed to fetch blob from PDS: {err:?}");
-            return (
-                StatusCode::BAD_GATEWAY,
-                "Failed to fetch blob from upstream PDS",
+        let cached_len = match &state.blob_cache {
+            Some(cache) => cache.get(&did, &rkey_cid).await.map(|bytes| bytes.len()),
+            None => None,
+        };
+        let mut builder = Response::builder()
+            .status(StatusCode::OK)
+            .header(header::CONTENT_TYPE, mime_type)
+            .header(header::ETAG, &etag)
+            .header(header::ACCEPT_RANGES, "bytes")
+            .header(
+                header::CONTENT_SECURITY_POLICY,
+                "default-src 'none'; sandbox",
             )
-                .into_response();
+            .header(header::X_CONTENT_TYPE_OPTIONS, "nosniff")
+            .header(header::CACHE_CONTROL, state.cache_control.as_str());
+        if let Some(len) = cached_len
+            && !transcoded
+        {
+            builder = builder.header(header::CONTENT_LENGTH, len);
         }
-    };
-    if !response.status().is_success() {
-        warn!("PDS returned error status: {}", response.status());
-        return (
-            StatusCode::BAD_GATEWAY,
-            "Failed to fetch blob from upstream PDS",
-        )
-            .into_response();
+        return builder.body(Body::empty()).unwrap().into_response();
     }
-    let bytes = match stream_with_limit(response, MAX_BLOB_SIZE).await {
-        Ok(bytes) => bytes,
-        Err(status) => return status.into_response(),
-    };
 
-    // Strictly validate the blob, computing and comparing its CID hash and validating its mime-type.
-    let computed_cid = match rkey_cid.hash().code() {
-        0x12 => Cid::new_v1(0x55, Code::Sha2_256.digest(&bytes)),
-        _ => {
-            warn!("unsupported hash algorithm: 0x{:x}", rkey_cid.hash().code());
-            return (
-                StatusCode::UNPROCESSABLE_ENTITY,
-                "Unsupported CID hash algorithm",
-            )
-                .into_response();
-        }
-    };
-    if computed_cid != rkey_cid {
-        warn!("CID mismatch: expected {rkey_cid}, computed {computed_cid}");
-        return StatusCode::BAD_GATEWAY.into_response();
-    }
+    // Fetch the blob - from the local cache when possible, otherwise from the
+    // user's PDS with size limits and CID verification applied.
+    let (bytes, upstream_host) =
+        match fetch_verified_blob(&state, &did, &rkey_cid, state.blob_limits.max_blob_size).await {
+            Ok(blob) => blob,
+            Err(err) => return err.into_response(),
+        };
     let mime_type = match infer::get(&bytes).map(|t| t.mime_type()) {
-        Some(m) if matches!(m, "image/gif" | "image/webp") => m,
+        Some(m) if state.is_allowed_media_mime(m) => m,
         _ => {
             warn!("invalid or unsupported image format");
             return StatusCode::UNPROCESSABLE_ENTITY.into_response();
         }
     };
-
-    Response::builder()
-        .status(StatusCode::OK)
-        .header(header::CONTENT_TYPE, mime_type)
-        .header(
-            header::CONTENT_SECURITY_POLICY,
-            "default-src 'none'; sandbox",
-        )
-        .header(header::X_CONTENT_TYPE_OPTIONS, "nosniff")
-        .header(header::CACHE_CONTROL, "public, max-age=604800")
-        .header(
-            header::CONTENT_DISPOSITION,
-            format!("attachment; filename=\"{}\"", post.title),
-        )
-        .header(
-            "Upstream-PDS",
-            format!(" {}", pds_url.host_str().unwrap_or("unknown")),
-        )
-        .body(Body::from(bytes))
-        .unwrap()
-        .into_response()
+    let (bytes, mime_type) = maybe_transcode(bytes, mime_type, wants_webp).await;
+    gif_response(
+        bytes,
+        mime_type,
+        &post.title,
+        &etag,
+        &state.cache_control,
+        range.as_ref(),
+        upstream_host.as_deref(),
+    )
 }
@@ -1,9 +1,178 @@
-use axum::body::Bytes;
+use crate::AppState;
+use axum::{
+    body::Bytes,
+    http::{HeaderMap, header},
+    response::IntoResponse,
+};
+use cid::Cid;
 use futures::StreamExt;
-use reqwest::StatusCode;
+use gifdex_lexicons::{net_gifdex, signing};
+use jacquard_common::{
+    chrono::Utc,
+    types::{collection::Collection, did::Did},
+};
+use multihash_codetable::{Code, MultihashDigest};
+use reqwest::{StatusCode, Url};
+use sqlx::query;
+use std::net::SocketAddr;
 
+pub mod admin;
 pub mod avatar;
+pub mod get_blob;
 pub mod gif;
+pub mod thumb;
+
+/// Debug header naming the PDS a relayed blob was fetched from. Shared so
+/// every route spells and formats it identically.
+pub(crate) const UPSTREAM_HOST_HEADER: &str = "Upstream-Host";
+
+/// Enforce signed media URLs when enabled, checking the `sig`/`exp` pair
+/// received for a `{did}/{rkey}` resource against the shared secret. Returns
+/// the `403` response to serve when the request is rejected.
+pub(crate) fn check_media_signature(
+    state: &AppState,
+    did: &str,
+    rkey: &str,
+    exp: Option<i64>,
+    sig: Option<&str>,
+) -> Option<axum::response::Response> {
+    if !state.require_signed_urls {
+        return None;
+    }
+    let secret = state.media_signing_secret.as_deref().unwrap_or_default();
+    let verified = match (exp, sig) {
+        (Some(exp), Some(sig)) => signing::verify_media(
+            secret.as_bytes(),
+            &format!("{did}/{rkey}"),
+            exp,
+            sig,
+            Utc::now().timestamp_millis(),
+        ),
+        _ => Err(signing::SignatureError::Invalid),
+    };
+    verified.err().map(|err| {
+        tracing::warn!("rejected media request for {did}/{rkey}: {err}");
+        (StatusCode::FORBIDDEN, "Invalid or expired media signature").into_response()
+    })
+}
+
+/// Check whether the post has an active takedown label and, if so, return the
+/// `451 Unavailable For Legal Reasons` response to serve instead of its media.
+/// Results are cached for the configured TTL so steady traffic to a post costs
+/// one label lookup per interval rather than one per request.
+pub(crate) async fn check_takedown(
+    state: &AppState,
+    did: &str,
+    rkey: &str,
+) -> Option<axum::response::Response> {
+    let taken_down = match state.cached_takedown(did, rkey) {
+        Some(taken_down) => taken_down,
+        None => {
+            let taken_down = match query!(
+                "SELECT EXISTS( \
+                    SELECT FROM labels l \
+                    INNER JOIN labeler_rules r ON r.did = l.rule_did AND r.rkey = l.rule_rkey \
+                    WHERE l.subject_did = $1 AND l.subject_rkey = $2 \
+                        AND l.subject_collection = $3 AND r.takedown \
+                        AND (l.expires_at IS NULL OR \
+                            l.expires_at > (extract(epoch from now()) * 1000)::BIGINT)) \
+                    as \"taken_down!\"",
+                did,
+                rkey,
+                net_gifdex::feed::post::Post::NSID
+            )
+            .fetch_one(state.database.executor())
+            .await
+            {
+                Ok(record) => record.taken_down,
+                Err(err) => {
+                    tracing::warn!("failed to look up takedown labels: {err:?}");
+                    return Some(StatusCode::INTERNAL_SERVER_ERROR.into_response());
+                }
+            };
+            state.cache_takedown(did, rkey, taken_down);
+            taken_down
+        }
+    };
+    taken_down.then(|| {
+        tracing::info!("suppressing media for taken down post {did}/{rkey}");
+        (
+            StatusCode::UNAVAILABLE_FOR_LEGAL_REASONS,
+            "Media unavailable due to a moderation action",
+        )
+            .into_response()
+    })
+}
+
+/// Resolve a PDS host, refusing hosts with an address in one of the
+/// configured blocked ranges. A DID document's PDS endpoint is
+/// attacker-controlled, so without this check a hostile account could point
+/// it at a loopback, private or link-local address and use our blob fetches
+/// to probe the CDN's network.
+///
+/// Returns the vetted addresses so the fetch can be pinned to exactly these,
+/// closing the window where a second resolution at connect time could return
+/// different (rebound) addresses.
+async fn resolve_pds_addrs(state: &AppState, url: &Url) -> Result<Vec<SocketAddr>, BlobError> {
+    let host = url.host_str().ok_or(BlobError::Internal)?;
+    let port = url.port_or_known_default().unwrap_or(443);
+    let addrs: Vec<SocketAddr> = match tokio::net::lookup_host((host, port)).await {
+        Ok(addrs) => addrs.collect(),
+        Err(err) => {
+            tracing::warn!("failed to resolve PDS host {host}: {err:?}");
+            return Err(BlobError::FetchFailed);
+        }
+    };
+    for addr in &addrs {
+        if let Some(range) = state
+            .blocked_pds_ranges
+            .iter()
+            .find(|range| range.contains(&addr.ip()))
+        {
+            tracing::warn!(
+                "refusing blob fetch: PDS host {host} resolves to {} in blocked range {range}",
+                addr.ip()
+            );
+            return Err(BlobError::BlockedPdsAddress);
+        }
+    }
+    Ok(addrs)
+}
+
+/// Check whether an `If-None-Match` header matches the blob's CID-derived ETag.
+fn if_none_match(headers: &HeaderMap, etag: &str) -> bool {
+    headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| {
+            value == "*"
+                || value
+                    .split(',')
+                    .any(|candidate| candidate.trim().trim_start_matches("W/") == etag)
+        })
+}
+
+/// The CID is a content hash, so it doubles as a strong ETag. Returns the
+/// blob's quoted ETag plus, when the request's `If-None-Match` header already
+/// matches it, the `304 Not Modified` response to serve - letting handlers
+/// revalidate conditional requests before touching the database or the PDS.
+pub(crate) fn check_etag(
+    state: &AppState,
+    headers: &HeaderMap,
+    cid: &Cid,
+) -> (String, Option<axum::response::Response>) {
+    let etag = format!("\"{cid}\"");
+    let not_modified = if_none_match(headers, &etag).then(|| {
+        axum::http::Response::builder()
+            .status(StatusCode::NOT_MODIFIED)
+            .header(header::ETAG, etag.as_str())
+            .header(header::CACHE_CONTROL, state.cache_control.as_str())
+            .body(axum::body::Body::empty())
+            .unwrap()
+            .into_response()
+    });
+    (etag, not_modified)
+}
 
 async fn stream_with_limit(
     response: reqwest::Response,
@@ -35,3 +204,341 @@ async fn stream_with_limit(
 
     Ok(Bytes::from(buffer))
 }
+
+/// Why a blob could not be fetched and verified from its owner's PDS.
+pub(crate) enum BlobError {
+    /// The account has no known PDS endpoint.
+    NoPdsEndpoint,
+    /// The account's PDS could not be looked up.
+    DidResolution,
+    /// The getBlob URL could not be constructed.
+    Internal,
+    /// The PDS host resolves into a blocked address range.
+    BlockedPdsAddress,
+    /// The per-PDS concurrent fetch limit was reached.
+    FetchLimitReached,
+    /// The global fetch memory budget is exhausted.
+    MemoryBudgetExhausted,
+    /// The PDS did not respond within the configured timeout.
+    FetchTimeout,
+    /// The fetch failed or the PDS returned an unexpected error status.
+    FetchFailed,
+    /// The PDS returned a status the client can act on, to be relayed as-is:
+    /// `404` and `410` for blobs the owner has deleted, `429` when the PDS is
+    /// rate limiting us (with its `Retry-After` passed through when present).
+    Upstream {
+        status: StatusCode,
+        retry_after: Option<String>,
+    },
+    /// The requested CID uses a multihash code we can't compute.
+    UnsupportedHash,
+    /// The fetched bytes don't hash to the requested CID.
+    CidMismatch,
+    /// The blob stream failed or exceeded the size limit.
+    Stream(StatusCode),
+}
+
+impl BlobError {
+    /// Short human-readable label, for contexts like the admin prefetch
+    /// report where the error is data rather than the response itself.
+    pub(crate) fn describe(&self) -> String {
+        match self {
+            BlobError::NoPdsEndpoint => "no PDS endpoint known for account".into(),
+            BlobError::DidResolution => "DID resolution failed".into(),
+            BlobError::Internal => "internal error".into(),
+            BlobError::BlockedPdsAddress => "PDS resolves to a blocked address".into(),
+            BlobError::FetchLimitReached => "per-PDS fetch limit reached".into(),
+            BlobError::MemoryBudgetExhausted => "fetch memory budget exhausted".into(),
+            BlobError::FetchTimeout => "fetch timed out".into(),
+            BlobError::FetchFailed => "fetch failed".into(),
+            BlobError::Upstream { status, .. } => format!("upstream PDS returned {status}"),
+            BlobError::UnsupportedHash => "unsupported CID hash algorithm".into(),
+            BlobError::CidMismatch => "fetched bytes don't match the CID".into(),
+            BlobError::Stream(status) => format!("blob stream failed: {status}"),
+        }
+    }
+}
+
+impl IntoResponse for BlobError {
+    fn into_response(self) -> axum::response::Response {
+        match self {
+            BlobError::NoPdsEndpoint => (
+                StatusCode::NOT_FOUND,
+                "No AtprotoPersonalDataServer service endpoint found in resolved DID document",
+            )
+                .into_response(),
+            BlobError::DidResolution => {
+                (StatusCode::BAD_GATEWAY, "Failed to resolve DID").into_response()
+            }
+            BlobError::Internal => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+            BlobError::BlockedPdsAddress => (
+                StatusCode::BAD_GATEWAY,
+                "PDS endpoint resolves to a blocked address",
+            )
+                .into_response(),
+            BlobError::FetchLimitReached => (
+                StatusCode::SERVICE_UNAVAILABLE,
+                [(header::RETRY_AFTER, "1")],
+                "Too many concurrent fetches to upstream PDS",
+            )
+                .into_response(),
+            BlobError::MemoryBudgetExhausted => (
+                StatusCode::SERVICE_UNAVAILABLE,
+                [(header::RETRY_AFTER, "1")],
+                "Too much blob data in flight",
+            )
+                .into_response(),
+            BlobError::FetchTimeout => (
+                StatusCode::GATEWAY_TIMEOUT,
+                "Timed out fetching blob from upstream PDS",
+            )
+                .into_response(),
+            BlobError::FetchFailed => (
+                StatusCode::BAD_GATEWAY,
+                "Failed to fetch blob from upstream PDS",
+            )
+                .into_response(),
+            BlobError::Upstream { status, retry_after } => {
+                let body = match status {
+                    StatusCode::NOT_FOUND => "Blob not found on upstream PDS",
+                    StatusCode::GONE => "Blob no longer available on upstream PDS",
+                    _ => "Upstream PDS is rate limiting blob fetches",
+                };
+                match retry_after {
+                    Some(retry_after) => {
+                        (status, [(header::RETRY_AFTER, retry_after)], body).into_response()
+                    }
+                    None => (status, body).into_response(),
+                }
+            }
+            BlobError::UnsupportedHash => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "Unsupported CID hash algorithm",
+            )
+                .into_response(),
+            BlobError::CidMismatch => StatusCode::BAD_GATEWAY.into_response(),
+            BlobError::Stream(status) => status.into_response(),
+        }
+    }
+}
+
+/// Fetch a blob by CID, serving from the local cache when possible and
+/// otherwise fetching it from the owner's PDS with the per-PDS concurrency
+/// cap, the global memory budget, the configured timeout, the size limit and
+/// CID verification applied uniformly.
+///
+/// Returns the blob bytes along with the upstream PDS host, ready for the
+/// [`UPSTREAM_HOST_HEADER`] response header, or `None` when the blob was
+/// served from the local cache.
+pub(crate) async fn fetch_verified_blob(
+    state: &AppState,
+    did: &Did<'_>,
+    cid: &Cid,
+    max_size: usize,
+) -> Result<(Bytes, Option<String>), BlobError> {
+    // Serve straight from the local blob cache if we have the blob, skipping
+    // the PDS round-trip entirely.
+    if let Some(ref cache) = state.blob_cache
+        && let Some(bytes) = cache.get(did, cid).await
+    {
+        return Ok((bytes, None));
+    }
+
+    // Get the user's PDS URL from our accounts data.
+    let pds_url = match query!("SELECT pds FROM accounts WHERE did = $1", did.as_str())
+        .fetch_optional(state.database.executor())
+        .await
+    {
+        Ok(Some(record)) if record.pds.is_some() => {
+            Url::parse(&format!("https://{}", record.pds.unwrap())).unwrap()
+        }
+        Ok(Some(_)) | Ok(None) => {
+            tracing::warn!("No PDS endpoint found for {did}");
+            return Err(BlobError::NoPdsEndpoint);
+        }
+        Err(err) => {
+            tracing::warn!("failed to resolve DID {did}: {err:?}");
+            return Err(BlobError::DidResolution);
+        }
+    };
+
+    let blob_url = {
+        let mut url = match pds_url.join("/xrpc/com.atproto.sync.getBlob") {
+            Ok(url) => url,
+            Err(err) => {
+                tracing::warn!("failed to build XRPC URL: {err:?}");
+                return Err(BlobError::Internal);
+            }
+        };
+        url.set_query(Some(&format!("did={did}&cid={cid}")));
+        url
+    };
+
+    // Cap concurrent fetches per upstream PDS so a burst of uncached requests
+    // can't open unbounded connections to a single origin.
+    let _pds_permit = match state.try_acquire_pds_fetch(pds_url.host_str().unwrap_or("unknown")) {
+        Some(permit) => permit,
+        None => {
+            tracing::warn!("concurrent fetch limit reached for PDS {pds_url}");
+            return Err(BlobError::FetchLimitReached);
+        }
+    };
+
+    // Reserve the blob's worst-case size against the global memory budget so
+    // that enough concurrent uncached requests can't buffer the process out
+    // of memory. The permit is held until the fetched bytes are returned.
+    let _memory_permit = match state.try_acquire_fetch_memory(max_size) {
+        Some(permit) => permit,
+        None => {
+            tracing::warn!("fetch memory budget exhausted, rejecting fetch for {did}");
+            return Err(BlobError::MemoryBudgetExhausted);
+        }
+    };
+
+    // Fetch the blob from the user's PDS, retrying transient failures with a
+    // short backoff. Connection errors and 5xx responses are usually a blip
+    // that a retry papers over; 4xx responses won't get better and a timeout
+    // already waited the full fetch budget.
+    //
+    // Redirects are followed manually in the outer loop - a hostile PDS can
+    // 302 the fetch anywhere, so every hop's host goes through the same
+    // address vetting as the first, with the connection pinned to the vetted
+    // addresses.
+    let mut fetch_url = blob_url;
+    let mut redirects = 0;
+    let result = loop {
+        let host = fetch_url
+            .host_str()
+            .ok_or(BlobError::Internal)?
+            .to_owned();
+        let addrs = resolve_pds_addrs(state, &fetch_url).await?;
+        let client = match state.pinned_pds_client(&host, &addrs) {
+            Ok(client) => client,
+            Err(err) => {
+                tracing::warn!("failed to build pinned client for {host}: {err:?}");
+                return Err(BlobError::Internal);
+            }
+        };
+        let mut attempt = 0;
+        let result = loop {
+            let request = client
+                .get(fetch_url.clone())
+                .timeout(state.pds_fetch_timeout);
+            // Propagate trace context so the fetch shows up as a child span of
+            // the serving request at the collector.
+            #[cfg(feature = "otel")]
+            let request = {
+                use tracing_opentelemetry::OpenTelemetrySpanExt;
+                let mut trace_headers = HeaderMap::new();
+                opentelemetry::global::get_text_map_propagator(|propagator| {
+                    propagator.inject_context(
+                        &tracing::Span::current().context(),
+                        &mut opentelemetry_http::HeaderInjector(&mut trace_headers),
+                    );
+                });
+                request.headers(trace_headers)
+            };
+            let result = request.send().await;
+            let retryable = match &result {
+                Ok(response) => response.status().is_server_error(),
+                Err(err) => !err.is_timeout(),
+            };
+            if retryable && attempt < state.pds_fetch_retries {
+                attempt += 1;
+                tracing::info!(
+                    "retrying blob fetch from {host} (attempt {attempt} of {})",
+                    state.pds_fetch_retries
+                );
+                tokio::time::sleep(state.pds_fetch_retry_delay * attempt).await;
+                continue;
+            }
+            break result;
+        };
+        if let Ok(response) = &result
+            && response.status().is_redirection()
+        {
+            let location = response
+                .headers()
+                .get(header::LOCATION)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|location| fetch_url.join(location).ok());
+            match location {
+                Some(next) if redirects < state.pds_fetch_max_redirects => {
+                    redirects += 1;
+                    fetch_url = next;
+                    continue;
+                }
+                Some(_) => {
+                    state.record_origin_fetch("upstream_error");
+                    tracing::warn!("redirect limit reached fetching blob from {host}");
+                    return Err(BlobError::FetchFailed);
+                }
+                None => {
+                    state.record_origin_fetch("upstream_error");
+                    tracing::warn!("unusable redirect location from {host}");
+                    return Err(BlobError::FetchFailed);
+                }
+            }
+        }
+        break result;
+    };
+    let response = match result {
+        Ok(resp) => resp,
+        Err(err) if err.is_timeout() => {
+            state.record_origin_fetch("timeout");
+            tracing::warn!("timed out fetching blob from PDS: {err:?}");
+            return Err(BlobError::FetchTimeout);
+        }
+        Err(err) => {
+            state.record_origin_fetch("error");
+            tracing::warn!("failed to fetch blob from PDS: {err:?}");
+            return Err(BlobError::FetchFailed);
+        }
+    };
+    if !response.status().is_success() {
+        state.record_origin_fetch("upstream_error");
+        let status = response.status();
+        tracing::warn!("PDS returned error status: {status}");
+        // Relay statuses the client can act on: a deleted blob is the
+        // client's 404/410, and an upstream rate limit is worth backing off
+        // from rather than reporting as a broken gateway.
+        return Err(match status {
+            StatusCode::NOT_FOUND | StatusCode::GONE => BlobError::Upstream {
+                status,
+                retry_after: None,
+            },
+            StatusCode::TOO_MANY_REQUESTS => BlobError::Upstream {
+                status,
+                retry_after: response
+                    .headers()
+                    .get(header::RETRY_AFTER)
+                    .and_then(|value| value.to_str().ok())
+                    .map(str::to_owned),
+            },
+            _ => BlobError::FetchFailed,
+        });
+    }
+    state.record_origin_fetch("success");
+    let bytes = stream_with_limit(response, max_size)
+        .await
+        .map_err(BlobError::Stream)?;
+
+    // Strictly validate the blob, computing and comparing its CID hash.
+    // Dispatch over the CID's multihash code so blobs hashed with any
+    // supported algorithm verify, not just SHA-256.
+    let computed_cid = match Code::try_from(cid.hash().code()) {
+        Ok(code) => Cid::new_v1(0x55, code.digest(&bytes)),
+        Err(_) => {
+            tracing::warn!("unsupported hash algorithm: 0x{:x}", cid.hash().code());
+            return Err(BlobError::UnsupportedHash);
+        }
+    };
+    if computed_cid != *cid {
+        tracing::warn!("CID mismatch: expected {cid}, computed {computed_cid}");
+        return Err(BlobError::CidMismatch);
+    }
+    if let Some(ref cache) = state.blob_cache {
+        cache.put(did, cid, &bytes).await;
+    }
+    Ok((bytes, pds_url.host_str().map(|host| host.to_owned())))
+}
@@ -0,0 +1,171 @@
+use crate::{
+    AppState,
+    routes::{check_media_signature, check_takedown, fetch_verified_blob},
+};
+use axum::{
+    body::Body,
+    extract::{Path, Query, State},
+    http::{Response, StatusCode, header},
+    response::IntoResponse,
+};
+use cid::Cid;
+use jacquard_common::types::{did::Did, tid::Tid};
+use sqlx::query;
+use std::sync::Arc;
+use tracing::warn;
+
+const DEFAULT_THUMB_WIDTH: u32 = 480;
+const MAX_THUMB_WIDTH: u32 = 1024;
+
+#[derive(serde::Deserialize)]
+pub struct ThumbParams {
+    /// Optional output width in pixels, capped at [`MAX_THUMB_WIDTH`].
+    w: Option<u32>,
+    /// Expiry (epoch milliseconds) of a signed URL.
+    exp: Option<i64>,
+    /// HMAC signature over the media resource and `exp`.
+    sig: Option<String>,
+}
+
+/// Decode the first frame of the blob and encode it as a PNG no wider than `width`.
+fn render_thumbnail(bytes: &[u8], width: u32) -> anyhow::Result<Vec<u8>> {
+    let image = image::load_from_memory(bytes)?;
+    let image = match image.width() > width {
+        true => image.thumbnail(width, u32::MAX),
+        false => image,
+    };
+    let mut out = Vec::new();
+    image.write_to(&mut std::io::Cursor::new(&mut out), image::ImageFormat::Png)?;
+    Ok(out)
+}
+
+pub async fn get_thumb_handler(
+    Path((did, rkey)): Path<(String, String)>,
+    Query(params): Query<ThumbParams>,
+    State(state): State<Arc<AppState>>,
+) -> impl IntoResponse {
+    let width = params.w.unwrap_or(DEFAULT_THUMB_WIDTH).clamp(1, MAX_THUMB_WIDTH);
+
+    // When signed URL enforcement is on, requests without a valid signature
+    // are turned away before any other work happens. The signature covers the
+    // `{did}/{rkey}` resource, so the one minted for the media URL also
+    // authorises its thumbnail.
+    if let Some(response) =
+        check_media_signature(&state, &did, &rkey, params.exp, params.sig.as_deref())
+    {
+        return response;
+    }
+
+    // Strictly verify the received path types.
+    let did = match Did::new(&did) {
+        Ok(did) => did,
+        Err(err) => {
+            warn!("invalid DID '{did}': {err:?}");
+            return (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "Invalid or unprocessable DID",
+            )
+                .into_response();
+        }
+    };
+
+    // Parse and validate rkey (format: tid:cid)
+    let rkey_cid = match rkey.split_once(':') {
+        Some((tid, cid)) => {
+            if Tid::new(tid).is_err() {
+                warn!("invalid TID in rkey");
+                return (
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    "Invalid or unprocessable rkey",
+                )
+                    .into_response();
+            }
+            match Cid::try_from(cid) {
+                Ok(cid) => cid,
+                Err(err) => {
+                    warn!("invalid CID in rkey: {err:?}");
+                    return (
+                        StatusCode::UNPROCESSABLE_ENTITY,
+                        "Invalid or unprocessable rkey",
+                    )
+                        .into_response();
+                }
+            }
+        }
+        None => {
+            warn!("malformed rkey (expected tid:cid format)");
+            return (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "Invalid or unprocessable rkey",
+            )
+                .into_response();
+        }
+    };
+
+    // Ensure the post exists in our records.
+    let post = match query!(
+        "SELECT title FROM posts WHERE did = $1 AND rkey = $2",
+        did.as_str(),
+        rkey
+    )
+    .fetch_optional(state.database.executor())
+    .await
+    {
+        Ok(Some(record)) => record,
+        Ok(None) => {
+            return (StatusCode::NOT_FOUND, "Post not found in records").into_response();
+        }
+        Err(err) => {
+            warn!("database error: {err:?}");
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    // A takedown label wins over everything else - the post stays in our
+    // records, but its media must not be served.
+    if let Some(response) = check_takedown(&state, did.as_str(), &rkey).await {
+        return response;
+    }
+
+    // Fetch the blob - from the local cache when possible, otherwise from the
+    // user's PDS with size limits and CID verification applied.
+    let bytes = match fetch_verified_blob(&state, &did, &rkey_cid, state.blob_limits.max_blob_size).await {
+        Ok((bytes, _)) => bytes,
+        Err(err) => return err.into_response(),
+    };
+    if !infer::get(&bytes).is_some_and(|t| state.is_allowed_media_mime(t.mime_type())) {
+        warn!("invalid or unsupported image format");
+        return StatusCode::UNPROCESSABLE_ENTITY.into_response();
+    }
+
+    // Extract the first frame and scale it down off the async runtime.
+    let thumbnail =
+        match tokio::task::spawn_blocking(move || render_thumbnail(&bytes, width)).await {
+            Ok(Ok(thumbnail)) => thumbnail,
+            Ok(Err(err)) => {
+                warn!("failed to render thumbnail: {err:?}");
+                return StatusCode::UNPROCESSABLE_ENTITY.into_response();
+            }
+            Err(err) => {
+                warn!("thumbnail render task panicked: {err:?}");
+                return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+            }
+        };
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "image/png")
+        .header(
+            header::CONTENT_SECURITY_POLICY,
+            "default-src 'none'; sandbox",
+        )
+        .header(header::X_CONTENT_TYPE_OPTIONS, "nosniff")
+        .header(header::CACHE_CONTROL, state.cache_control.as_str())
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{}.png\"", post.title),
+        )
+        .body(Body::from(thumbnail))
+        .unwrap()
+        .into_response()
+}
@@ -12,6 +12,10 @@ pub async fn handle_identity(
     // Completely purge data related to accounts that are deleted or takendown.
     // Note: this does not delete any labels applied to the account or their content.
     if matches!(identity.status.as_str(), "deleted" | "takendown") {
+        if state.dry_run {
+            info!("Dry run - would have removed all userdata for account");
+            return Ok(());
+        }
         if let Err(err) = query!("DELETE FROM accounts WHERE did = $1", identity.did.as_str())
             .execute(&mut **tx)
             .await
@@ -23,27 +27,45 @@ pub async fn handle_identity(
         return Ok(());
     }
 
-    let pds = state
-        .tap_client
-        .resolve_did(&identity.did)
-        .await?
+    let document = state.tap_client.resolve_did(&identity.did).await?;
+    let pds = document
         .pds_endpoint()
         .map(|pds| pds.host_str().unwrap().to_string());
 
+    // Optionally cross-check the event's handle against the DID document's
+    // alsoKnownAs entries - an unlisted handle is spoofable, so it is not
+    // stored and the account is marked unverified.
+    let handle_verified = state.verify_handles
+        && document
+            .handles()
+            .iter()
+            .any(|handle| handle.as_str() == identity.handle.as_str());
+    let handle = match state.verify_handles && !handle_verified {
+        true => None,
+        false => Some(identity.handle.as_str()),
+    };
+
+    if state.dry_run {
+        info!("Dry run - would have upserted stored account data into database");
+        return Ok(());
+    }
+
     // Update state of account incase of handle/status/is_active updates.
     match query!(
-        "INSERT INTO accounts (did, handle, pds, is_active, status, created_at) \
-         VALUES ($1, $2, $3, $4, $5, (extract(epoch from now()) * 1000)::BIGINT) \
+        "INSERT INTO accounts (did, handle, pds, is_active, status, handle_verified, created_at) \
+         VALUES ($1, $2, $3, $4, $5, $6, (extract(epoch from now()) * 1000)::BIGINT) \
          ON CONFLICT(did) DO UPDATE SET \
          handle = excluded.handle, \
+         handle_verified = excluded.handle_verified, \
          is_active = excluded.is_active, \
          pds = excluded.pds, \
          status = excluded.status",
         identity.did.as_str(),
-        identity.handle.as_str(),
+        handle,
         pds,
         identity.is_active,
-        identity.status
+        identity.status,
+        handle_verified
     )
     .execute(&mut **tx)
     .await
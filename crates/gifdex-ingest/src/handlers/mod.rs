@@ -2,6 +2,7 @@ mod identity;
 mod net_gifdex;
 
 use crate::AppState;
+use crate::RejectReason;
 use crate::handlers;
 use crate::handlers::net_gifdex::actor::handle_profile_create_event;
 use crate::handlers::net_gifdex::actor::handle_profile_delete_event;
@@ -14,9 +15,10 @@ use crate::handlers::net_gifdex::labeler::handle_label_delete_event;
 use crate::handlers::net_gifdex::labeler::handle_rule_create_event;
 use crate::handlers::net_gifdex::labeler::handle_rule_delete_event;
 use anyhow::bail;
-use floodgate::api::{EventData, RecordAction};
+use floodgate::api::{EventData, RecordAction, RecordEventData};
 use gifdex_lexicons::net_gifdex as gifdex_lexicons;
 use jacquard_common::types::collection::Collection;
+use jacquard_lexicon::schema::LexiconSchema;
 use sqlx::query;
 use std::sync::Arc;
 
@@ -73,100 +75,229 @@ use std::sync::Arc;
 )]
 pub async fn handle_event(state: Arc<AppState>, data: EventData<'static>) -> anyhow::Result<()> {
     match data {
-        EventData::Identity { identity } => {
+        EventData::Identity { identity, .. } => {
             let mut tx = state.database.transaction().await?;
             handlers::identity::handle_identity(&identity, &mut tx, &state).await?;
             tx.commit().await?;
             Ok(())
         }
-        EventData::Record { record } => {
-            let mut tx = state.database.transaction().await?;
-            match &record.action {
-                RecordAction::Create {
-                    record: payload, ..
+        EventData::Record { record, .. } => {
+            let key = (
+                record.did.to_string(),
+                record.collection.to_string(),
+                record.rkey.to_string(),
+            );
+            match handle_record_event(&record, &state).await {
+                Ok(()) => {
+                    state.dead_letter_attempts.lock().unwrap().remove(&key);
+                    Ok(())
                 }
-                | RecordAction::Update {
-                    record: payload, ..
-                } => match record.collection.as_str() {
-                    gifdex_lexicons::feed::post::Post::NSID => {
-                        let json_str = serde_json::to_string(&payload.raw())?;
-                        let post: gifdex_lexicons::feed::post::Post =
-                            serde_json::from_str(&json_str)?;
-                        handle_post_create(&record, &post, &mut tx, &state).await?
-                    }
-                    gifdex_lexicons::feed::favourite::Favourite::NSID => {
-                        let json_str = serde_json::to_string(&payload.raw())?;
-                        let favourite: gifdex_lexicons::feed::favourite::Favourite =
-                            serde_json::from_str(&json_str)?;
-                        handle_favourite_create_event(&record, &favourite, &mut tx, &state).await?
-                    }
-                    gifdex_lexicons::actor::profile::Profile::NSID => {
-                        let json_str = serde_json::to_string(&payload.raw())?;
-                        let profile: gifdex_lexicons::actor::profile::Profile =
-                            serde_json::from_str(&json_str)?;
-                        handle_profile_create_event(&record, &profile, &mut tx, &state).await?
-                    }
-                    gifdex_lexicons::labeler::label::Label::NSID => {
-                        let json_str = serde_json::to_string(&payload.raw())?;
-                        let label: gifdex_lexicons::labeler::label::Label =
-                            serde_json::from_str(&json_str)?;
-                        handle_label_create_event(&record, &label, &mut tx, &state).await?
-                    }
-                    gifdex_lexicons::labeler::rule::Rule::NSID => {
-                        let json_str = serde_json::to_string(&payload.raw())?;
-                        let rule: gifdex_lexicons::labeler::rule::Rule =
-                            serde_json::from_str(&json_str)?;
-                        handle_rule_create_event(&record, &rule, &mut tx, &state).await?
-                    }
-                    collection @ _ => {
-                        tracing::error!(
-                            "No record create/update handler for collection {collection} - please ensure tap is sending the correct records."
-                        );
-                        bail!("No registered create/update handler for record");
-                    }
-                },
-
-                RecordAction::Delete => match record.collection.as_str() {
-                    gifdex_lexicons::feed::post::Post::NSID => {
-                        handle_post_delete(&record, &mut tx, &state).await?
-                    }
-                    gifdex_lexicons::feed::favourite::Favourite::NSID => {
-                        handle_favourite_delete_event(&record, &mut tx, &state).await?
-                    }
-                    gifdex_lexicons::actor::profile::Profile::NSID => {
-                        handle_profile_delete_event(&record, &mut tx, &state).await?
-                    }
-                    gifdex_lexicons::labeler::label::Label::NSID => {
-                        handle_label_delete_event(&record, &mut tx, &state).await?
+                Err(err) if state.dead_letter_retries > 0 => {
+                    // Track how many times this event has failed; once it
+                    // exhausts its retries, park it in the dead_letter table
+                    // and ack it so the stream can progress past it.
+                    let attempts = {
+                        let mut attempts = state.dead_letter_attempts.lock().unwrap();
+                        let count = attempts.entry(key.clone()).or_insert(0);
+                        *count += 1;
+                        *count
+                    };
+                    if attempts < state.dead_letter_retries {
+                        return Err(err);
                     }
-                    gifdex_lexicons::labeler::rule::Rule::NSID => {
-                        handle_rule_delete_event(&record, &mut tx, &state).await?
-                    }
-                    collection @ _ => {
-                        tracing::error!(
-                            "No record delete handler for collection {collection} - please ensure tap is sending the correct records."
-                        );
-                        bail!("No registered delete handler for record");
-                    }
-                },
+                    tracing::warn!(
+                        "Dead-lettering event after {attempts} failed attempts: {err:?}"
+                    );
+                    let payload = match &record.action {
+                        RecordAction::Create {
+                            record: payload, ..
+                        }
+                        | RecordAction::Update {
+                            record: payload, ..
+                        } => Some(payload.raw().clone()),
+                        RecordAction::Delete => None,
+                    };
+                    query!(
+                        "INSERT INTO dead_letter (did, collection, rkey, payload, error) \
+                         VALUES ($1, $2, $3, $4, $5)",
+                        record.did.as_str(),
+                        record.collection.as_str(),
+                        record.rkey.as_str(),
+                        payload,
+                        format!("{err:?}"),
+                    )
+                    .execute(state.database.executor())
+                    .await?;
+                    state.dead_letter_attempts.lock().unwrap().remove(&key);
+                    Ok(())
+                }
+                Err(err) => Err(err),
             }
+        }
+        // Forward compatibility: floodgate may grow event types this version
+        // doesn't know about yet. Skip them with a warning - returning `Ok`
+        // so they're acked and don't block the stream - instead of taking
+        // down the handler task.
+        etype => {
+            tracing::warn!("Skipping unknown event data type: {etype:?}");
+            state.record_unknown_event();
+            Ok(())
+        }
+    }
+}
 
-            // Update repository revision.
-            tracing::debug!("updated repository revision to {}", record.rev);
-            query!(
-                "UPDATE accounts SET rev = $2 WHERE did = $1",
-                record.did.as_str(),
-                record.rev.as_str(),
-            )
-            .execute(&mut *tx)
-            .await?;
-
-            tx.commit().await?;
+/// Optional strict pass enforcing the constraints a record's lexicon schema
+/// declares but the database doesn't - title length bounds, tag count and
+/// length limits, and so on. Out-of-bounds records are dropped with a warning
+/// rather than stored where they'd later break the appview. Language codes
+/// are already BCP-47-validated during deserialization.
+///
+/// Returns `false` when the record should be rejected.
+fn strict_validate(state: &AppState, record: &impl LexiconSchema) -> bool {
+    if !state.strict_validation {
+        return true;
+    }
+    match record.validate() {
+        Ok(()) => true,
+        Err(err) => {
+            tracing::warn!("Rejected record: failed strict lexicon validation: {err:?}");
+            state.record_rejection(RejectReason::StrictValidation);
+            false
+        }
+    }
+}
 
-            Ok(())
+/// Deserialize a record payload against its lexicon type `T`, counting and
+/// dropping records that don't conform with a warning. Returns `None` for
+/// those so the caller can ack the event instead of failing it.
+fn deserialize_record<'de, T: serde::Deserialize<'de>>(
+    state: &AppState,
+    payload: &'de serde_json::Value,
+) -> Option<T> {
+    match T::deserialize(payload) {
+        Ok(value) => Some(value),
+        Err(err) => {
+            tracing::warn!("Rejected record: failed to deserialize: {err}");
+            state.record_rejection(RejectReason::Deserialize);
+            None
         }
-        etype @ _ => {
-            panic!("unknown event data type: {etype:?}");
+    }
+}
+
+async fn handle_record_event(
+    record: &RecordEventData<'_>,
+    state: &AppState,
+) -> anyhow::Result<()> {
+    let mut tx = state.database.transaction().await?;
+    match &record.action {
+        RecordAction::Create {
+            record: payload, ..
         }
+        | RecordAction::Update {
+            record: payload, ..
+        } => match record.collection.as_str() {
+            // Records that fail to deserialize against their lexicon
+            // (malformed datetimes, missing fields, ...) are bad
+            // records rather than bad events - drop them with a
+            // warning instead of failing the event.
+            gifdex_lexicons::feed::post::Post::NSID => {
+                let Some(post) = deserialize_record::<gifdex_lexicons::feed::post::Post>(state, payload.raw()) else {
+                    return Ok(());
+                };
+                state.record_ingest_lag(record.live, post.created_at.as_ref().timestamp_millis());
+                if !strict_validate(state, &post) || !strict_validate(state, &post.media) {
+                    return Ok(());
+                }
+                handle_post_create(record, &post, &record.action, &mut tx, state).await?
+            }
+            gifdex_lexicons::feed::favourite::Favourite::NSID => {
+                let Some(favourite) = deserialize_record::<gifdex_lexicons::feed::favourite::Favourite>(state, payload.raw()) else {
+                    return Ok(());
+                };
+                state.record_ingest_lag(record.live, favourite.created_at.as_ref().timestamp_millis());
+                if !strict_validate(state, &favourite) {
+                    return Ok(());
+                }
+                handle_favourite_create_event(record, &favourite, &mut tx, state).await?
+            }
+            gifdex_lexicons::actor::profile::Profile::NSID => {
+                let Some(profile) = deserialize_record::<gifdex_lexicons::actor::profile::Profile>(state, payload.raw()) else {
+                    return Ok(());
+                };
+                state.record_ingest_lag(record.live, profile.created_at.as_ref().timestamp_millis());
+                if !strict_validate(state, &profile) {
+                    return Ok(());
+                }
+                handle_profile_create_event(record, &profile, &mut tx, state).await?
+            }
+            gifdex_lexicons::labeler::label::Label::NSID => {
+                let Some(label) = deserialize_record::<gifdex_lexicons::labeler::label::Label>(state, payload.raw()) else {
+                    return Ok(());
+                };
+                state.record_ingest_lag(record.live, label.created_at.as_ref().timestamp_millis());
+                if !strict_validate(state, &label) {
+                    return Ok(());
+                }
+                handle_label_create_event(record, &label, &mut tx, state).await?
+            }
+            gifdex_lexicons::labeler::rule::Rule::NSID => {
+                let Some(rule) = deserialize_record::<gifdex_lexicons::labeler::rule::Rule>(state, payload.raw()) else {
+                    return Ok(());
+                };
+                state.record_ingest_lag(record.live, rule.created_at.as_ref().timestamp_millis());
+                if !strict_validate(state, &rule) {
+                    return Ok(());
+                }
+                handle_rule_create_event(record, &rule, &mut tx, state).await?
+            }
+            collection => {
+                tracing::error!(
+                    "No record create/update handler for collection {collection} - please ensure tap is sending the correct records."
+                );
+                bail!("No registered create/update handler for record");
+            }
+        },
+
+        RecordAction::Delete => match record.collection.as_str() {
+            gifdex_lexicons::feed::post::Post::NSID => {
+                handle_post_delete(record, &mut tx, state).await?
+            }
+            gifdex_lexicons::feed::favourite::Favourite::NSID => {
+                handle_favourite_delete_event(record, &mut tx, state).await?
+            }
+            gifdex_lexicons::actor::profile::Profile::NSID => {
+                handle_profile_delete_event(record, &mut tx, state).await?
+            }
+            gifdex_lexicons::labeler::label::Label::NSID => {
+                handle_label_delete_event(record, &mut tx, state).await?
+            }
+            gifdex_lexicons::labeler::rule::Rule::NSID => {
+                handle_rule_delete_event(record, &mut tx, state).await?
+            }
+            collection => {
+                tracing::error!(
+                    "No record delete handler for collection {collection} - please ensure tap is sending the correct records."
+                );
+                bail!("No registered delete handler for record");
+            }
+        },
+    }
+
+    // Update repository revision. Skipped in dry-run mode alongside every
+    // other write.
+    if state.dry_run {
+        return Ok(());
     }
+    tracing::debug!("updated repository revision to {}", record.rev);
+    query!(
+        "UPDATE accounts SET rev = $2 WHERE did = $1 AND (rev IS NULL OR rev < $2)",
+        record.did.as_str(),
+        record.rev.as_str(),
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(())
 }
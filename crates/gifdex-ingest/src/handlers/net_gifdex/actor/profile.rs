@@ -4,19 +4,20 @@ use gifdex_lexicons::net_gifdex;
 use sqlx::{PgTransaction, query};
 use tracing::{error, info, warn};
 
-use crate::AppState;
+use crate::{AppState, RejectReason};
 
 pub async fn handle_profile_create_event(
     record_data: &RecordEventData<'_>,
     data: &net_gifdex::actor::profile::Profile<'_>,
     tx: &mut PgTransaction<'_>,
-    _state: &AppState,
+    state: &AppState,
 ) -> Result<()> {
     // Ensure the record rkey is a valid exactly 'self'.
     if record_data.rkey.as_str() != "self" {
         warn!(
             "Rejected record: actor profile record is invalid as it does not use the rkey 'self'"
         );
+        state.record_rejection(RejectReason::InvalidRkey);
         return Ok(());
     }
 
@@ -25,31 +26,44 @@ pub async fn handle_profile_create_event(
     if let Some(avatar) = &data.avatar {
         if !avatar.blob().cid().is_valid() {
             warn!("Rejected record: invalid blob CID in for avatar");
+            state.record_rejection(RejectReason::InvalidBlobCid);
             return Ok(());
         };
-        if !matches!(avatar.blob().mime_type.as_str(), "image/png" | "image/jpeg") {
+        if !state.is_allowed_avatar_mime(avatar.blob().mime_type.as_str()) {
             warn!("Rejected record: blob isn't a valid mimetype");
+            state.record_rejection(RejectReason::InvalidMime);
             return Ok(());
         }
-        if avatar.blob().size == 3 * 1024 * 1024 {
+        if avatar.blob().size > state.blob_limits.max_avatar_size {
             warn!("Rejected record: blob is above maximum size");
+            state.record_rejection(RejectReason::Oversize);
             return Ok(());
         }
     }
 
+    if state.dry_run {
+        info!("Dry run - would have upserted user-defined actor profile fields into database");
+        return Ok(());
+    }
+
+    // Guard the update on the repo revision so a late-arriving older profile
+    // revision can't overwrite newer data.
     match query!(
         "INSERT INTO accounts (did, display_name, pronouns, \
-         avatar_blob_cid, created_at) \
-         VALUES ($1, $2, $3, $4, $5) \
+         avatar_blob_cid, rev, created_at) \
+         VALUES ($1, $2, $3, $4, $5, $6) \
          ON CONFLICT(did) DO UPDATE SET \
          display_name = excluded.display_name, \
          pronouns = excluded.pronouns, \
          avatar_blob_cid = excluded.avatar_blob_cid, \
-         created_at = excluded.created_at",
+         rev = excluded.rev, \
+         created_at = excluded.created_at \
+         WHERE accounts.rev IS NULL OR accounts.rev < excluded.rev",
         record_data.did.as_str(),
         data.display_name.as_deref(),
         data.pronouns.as_deref(),
         data.avatar.as_ref().map(|s| s.blob().cid().as_str()),
+        record_data.rev.as_str(),
         data.created_at.as_ref().timestamp_millis()
     )
     .execute(&mut **tx)
@@ -69,14 +83,20 @@ pub async fn handle_profile_create_event(
 pub async fn handle_profile_delete_event(
     record_data: &RecordEventData<'_>,
     tx: &mut PgTransaction<'_>,
-    _state: &AppState,
+    state: &AppState,
 ) -> Result<()> {
     if record_data.rkey.as_str() != "self" {
         warn!(
             "Rejected record: actor profile record is invalid as it does not use the rkey 'self'"
         );
+        state.record_rejection(RejectReason::InvalidRkey);
         return Ok(());
     }
+    if state.dry_run {
+        info!("Dry run - would have cleared all user-defined actor profile fields from database");
+        return Ok(());
+    }
+
     match query!(
         "UPDATE accounts SET \
          display_name = NULL, \
@@ -1,4 +1,4 @@
-use crate::AppState;
+use crate::{AppState, RejectReason};
 use anyhow::Result;
 use floodgate::api::RecordEventData;
 use gifdex_lexicons::net_gifdex;
@@ -10,11 +10,12 @@ pub async fn handle_favourite_create_event(
     record_data: &RecordEventData<'_>,
     data: &net_gifdex::feed::favourite::Favourite<'_>,
     tx: &mut PgTransaction<'_>,
-    _state: &AppState,
+    state: &AppState,
 ) -> Result<()> {
     // Ensure the record rkey is a valid TID .
     if Tid::new(&record_data.rkey).is_err() {
         tracing::warn!("Rejected record: invalid rkey");
+        state.record_rejection(RejectReason::InvalidRkey);
         return Ok(());
     }
     // Ensure the record's referenced subject is a post.
@@ -29,22 +30,26 @@ pub async fn handle_favourite_create_event(
                 Some((tid, cid)) => {
                     if Tid::new(tid).is_err() {
                         tracing::warn!("Rejected record: invalid TID in rkey");
+                        state.record_rejection(RejectReason::InvalidSubject);
                         return Ok(());
                     }
                     if !Cid::str(cid).is_valid() {
                         tracing::warn!("Rejected record: invalid CID in rkey");
+                        state.record_rejection(RejectReason::InvalidSubject);
                         return Ok(());
                     };
                 }
                 None => {
                     tracing::warn!("Rejected record: rkey doesn't match tid:cid format");
+                    state.record_rejection(RejectReason::InvalidSubject);
                     return Ok(());
                 }
             };
             (did, collection, rkey)
         }
-        at_uri @ _ => {
+        at_uri => {
             tracing::warn!("Rejected record: invalid subject at-uri ({at_uri:?})");
+            state.record_rejection(RejectReason::InvalidSubject);
             return Ok(());
         }
     };
@@ -53,13 +58,19 @@ pub async fn handle_favourite_create_event(
             "Rejected record: subject at-uri referenced a collection that was not {}",
             net_gifdex::feed::post::Post::NSID
         );
+        state.record_rejection(RejectReason::InvalidSubject);
         return Ok(());
     }
 
-    match query!(
+    if state.dry_run {
+        info!("Dry run - would have upserted feed post favourite into database");
+        return Ok(());
+    }
+
+    let result = match query!(
         "INSERT INTO post_favourites (did, rkey, post_did, \
          post_rkey, created_at, indexed_at) \
-         VALUES ($1, $2, $3, $4, $5, extract(epoch from now())::BIGINT) \
+         VALUES ($1, $2, $3, $4, $5, (extract(epoch from now()) * 1000)::BIGINT) \
          ON CONFLICT (did, post_did, post_rkey) DO NOTHING",
         record_data.did.as_str(),
         record_data.rkey.as_str(),
@@ -70,37 +81,229 @@ pub async fn handle_favourite_create_event(
     .execute(&mut **tx)
     .await
     {
-        Ok(_) => {
-            info!("Upserted feed post favourite into database");
-            Ok(())
-        }
+        Ok(result) => result,
         Err(err) => {
             error!("Failed to upsert feed post favourite into database: {err:?}");
-            Err(err.into())
+            return Err(err.into());
         }
+    };
+
+    // ON CONFLICT DO NOTHING reports zero affected rows for a replayed
+    // favourite, which keeps the denormalized counter from double-counting.
+    if result.rows_affected() > 0
+        && let Err(err) = query!(
+            "UPDATE posts SET favourite_count = favourite_count + 1 \
+             WHERE did = $1 AND rkey = $2",
+            post_did.as_str(),
+            post_rkey.as_ref()
+        )
+        .execute(&mut **tx)
+        .await
+    {
+        error!("Failed to increment post favourite count: {err:?}");
+        return Err(err.into());
     }
+
+    info!("Upserted feed post favourite into database");
+    Ok(())
 }
 
 pub async fn handle_favourite_delete_event(
     record_data: &RecordEventData<'_>,
     tx: &mut PgTransaction<'_>,
-    _state: &AppState,
+    state: &AppState,
 ) -> Result<()> {
-    match query!(
-        "DELETE FROM post_favourites WHERE did = $1 AND rkey = $2",
+    if state.dry_run {
+        info!("Dry run - would have deleted post favourite from database");
+        return Ok(());
+    }
+
+    let deleted = match query!(
+        "DELETE FROM post_favourites WHERE did = $1 AND rkey = $2 \
+         RETURNING post_did, post_rkey",
         record_data.did.as_str(),
         record_data.rkey.as_str()
     )
-    .execute(&mut **tx)
+    .fetch_optional(&mut **tx)
     .await
     {
-        Ok(_) => {
-            info!("Deleted post favourite from database");
-            Ok(())
-        }
+        Ok(deleted) => deleted,
         Err(err) => {
             error!("Failed to delete post favourite from database: {err:?}");
-            Err(err.into())
+            return Err(err.into());
         }
+    };
+
+    // Only decrement when a favourite was actually removed, and never below
+    // zero in case the counter ever drifts.
+    if let Some(deleted) = deleted
+        && let Err(err) = query!(
+            "UPDATE posts SET favourite_count = GREATEST(favourite_count - 1, 0) \
+             WHERE did = $1 AND rkey = $2",
+            deleted.post_did,
+            deleted.post_rkey
+        )
+        .execute(&mut **tx)
+        .await
+    {
+        error!("Failed to decrement post favourite count: {err:?}");
+        return Err(err.into());
+    }
+
+    info!("Deleted post favourite from database");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+    use serde_json::json;
+
+    const AUTHOR: &str = "did:plc:ewvi7nxzyoun6zhxrhs64oiz";
+    const FAVOURITER: &str = "did:plc:yk4dd2qkboz2yv6tpubpc6co";
+
+    /// End-to-end create against the real schema: the favourite row stores
+    /// `created_at` in epoch milliseconds and bumps the denormalized counter
+    /// exactly once, even when the event is redelivered.
+    #[tokio::test]
+    #[ignore = "requires a container runtime"]
+    async fn favourite_create_counts_once_and_stores_milliseconds() {
+        let (_postgres, state) = test_support::test_state().await;
+        let post_rkey = format!("3jzfcijpj2z2a:{}", gifdex_testing::TEST_CID);
+        let executor = state.database.executor();
+        gifdex_testing::seed_account(executor, AUTHOR, Some("author.test"), 1_700_000_000_000)
+            .await;
+        gifdex_testing::seed_account(executor, FAVOURITER, None, 1_700_000_000_000).await;
+        gifdex_testing::seed_post(executor, AUTHOR, &post_rkey, "A post", 1_700_000_000_000).await;
+
+        let event_json = json!({
+            "live": true,
+            "did": FAVOURITER,
+            "rev": "3jzfcijpj2z2b",
+            "collection": "net.gifdex.feed.favourite",
+            "rkey": "3jzfcijpj2z2c",
+            "action": "create",
+            "record": {},
+            "cid": gifdex_testing::TEST_CID,
+        })
+        .to_string();
+        let record_json = json!({
+            "createdAt": "2026-08-20T12:00:00Z",
+            "subject": format!(
+                "at://{AUTHOR}/{}/{post_rkey}",
+                net_gifdex::feed::post::Post::NSID
+            ),
+        })
+        .to_string();
+
+        // Deliver the same create twice - the second is a redelivery and
+        // must not double-count.
+        for _ in 0..2 {
+            let event: RecordEventData = serde_json::from_str(&event_json).unwrap();
+            let record: net_gifdex::feed::favourite::Favourite =
+                serde_json::from_str(&record_json).unwrap();
+            let mut tx = state.database.transaction().await.unwrap();
+            handle_favourite_create_event(&event, &record, &mut tx, &state)
+                .await
+                .unwrap();
+            tx.commit().await.unwrap();
+        }
+
+        let created_at: i64 =
+            sqlx::query_scalar("SELECT created_at FROM post_favourites WHERE did = $1")
+                .bind(FAVOURITER)
+                .fetch_one(executor)
+                .await
+                .unwrap();
+        // 2026-08-20T12:00:00Z, in epoch milliseconds like every other
+        // timestamp column.
+        assert_eq!(created_at, 1_787_227_200_000);
+
+        let favourite_count: i64 =
+            sqlx::query_scalar("SELECT favourite_count FROM posts WHERE did = $1 AND rkey = $2")
+                .bind(AUTHOR)
+                .bind(&post_rkey)
+                .fetch_one(executor)
+                .await
+                .unwrap();
+        assert_eq!(favourite_count, 1);
+    }
+
+    /// Deleting one user's favourite must only remove that user's row and
+    /// decrement the counter once.
+    #[tokio::test]
+    #[ignore = "requires a container runtime"]
+    async fn favourite_delete_only_removes_own_row() {
+        let (_postgres, state) = test_support::test_state().await;
+        let post_rkey = format!("3jzfcijpj2z2a:{}", gifdex_testing::TEST_CID);
+        let executor = state.database.executor();
+        gifdex_testing::seed_account(executor, AUTHOR, Some("author.test"), 1_700_000_000_000)
+            .await;
+        gifdex_testing::seed_account(executor, FAVOURITER, None, 1_700_000_000_000).await;
+        gifdex_testing::seed_post(executor, AUTHOR, &post_rkey, "A post", 1_700_000_000_000).await;
+        // Both users favourite the post, each from their own repo.
+        for (did, rkey) in [(AUTHOR, "3jzfcijpj2z2d"), (FAVOURITER, "3jzfcijpj2z2e")] {
+            let event_json = json!({
+                "live": true,
+                "did": did,
+                "rev": "3jzfcijpj2z2b",
+                "collection": "net.gifdex.feed.favourite",
+                "rkey": rkey,
+                "action": "create",
+                "record": {},
+                "cid": gifdex_testing::TEST_CID,
+            })
+            .to_string();
+            let event: RecordEventData = serde_json::from_str(&event_json).unwrap();
+            let record_json = json!({
+                "createdAt": "2026-08-20T12:00:00Z",
+                "subject": format!(
+                    "at://{AUTHOR}/{}/{post_rkey}",
+                    net_gifdex::feed::post::Post::NSID
+                ),
+            })
+            .to_string();
+            let record: net_gifdex::feed::favourite::Favourite =
+                serde_json::from_str(&record_json).unwrap();
+            let mut tx = state.database.transaction().await.unwrap();
+            handle_favourite_create_event(&event, &record, &mut tx, &state)
+                .await
+                .unwrap();
+            tx.commit().await.unwrap();
+        }
+
+        let event_json = json!({
+            "live": true,
+            "did": FAVOURITER,
+            "rev": "3jzfcijpj2z2c",
+            "collection": "net.gifdex.feed.favourite",
+            "rkey": "3jzfcijpj2z2e",
+            "action": "delete",
+        })
+        .to_string();
+        let event: RecordEventData = serde_json::from_str(&event_json).unwrap();
+        let mut tx = state.database.transaction().await.unwrap();
+        handle_favourite_delete_event(&event, &mut tx, &state)
+            .await
+            .unwrap();
+        tx.commit().await.unwrap();
+
+        let remaining: Vec<String> =
+            sqlx::query_scalar("SELECT did FROM post_favourites WHERE post_did = $1")
+                .bind(AUTHOR)
+                .fetch_all(executor)
+                .await
+                .unwrap();
+        assert_eq!(remaining, vec![AUTHOR.to_string()]);
+
+        let favourite_count: i64 =
+            sqlx::query_scalar("SELECT favourite_count FROM posts WHERE did = $1 AND rkey = $2")
+                .bind(AUTHOR)
+                .bind(&post_rkey)
+                .fetch_one(executor)
+                .await
+                .unwrap();
+        assert_eq!(favourite_count, 1);
     }
 }
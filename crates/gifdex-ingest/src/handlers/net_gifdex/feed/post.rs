@@ -1,6 +1,6 @@
-use crate::AppState;
+use crate::{AppState, OverLimitPolicy, RejectReason};
 use anyhow::{Context, Result, bail};
-use floodgate::api::RecordEventData;
+use floodgate::api::{RecordAction, RecordEventData};
 use gifdex_lexicons::net_gifdex;
 use jacquard_common::types::{cid::Cid, tid::Tid};
 use sqlx::{PgTransaction, query};
@@ -11,6 +11,7 @@ use url::Url;
 pub async fn handle_post_create(
     record_data: &RecordEventData<'_>,
     data: &net_gifdex::feed::post::Post<'_>,
+    action: &RecordAction<'_>,
     tx: &mut PgTransaction<'_>,
     state: &AppState,
 ) -> Result<()> {
@@ -19,58 +20,93 @@ pub async fn handle_post_create(
         Some((tid_str, cid_str)) => {
             if Tid::new(tid_str).is_err() {
                 warn!("Rejected record: invalid TID in rkey");
+                state.record_rejection(RejectReason::InvalidRkey);
                 return Ok(());
             }
             let cid = Cid::str(cid_str);
             if !cid.is_valid() {
                 warn!("Rejected record: invalid CID in rkey");
+                state.record_rejection(RejectReason::InvalidRkey);
                 return Ok(());
             }
             // Validate rkey CID matches blob CID
             if cid != *data.media.blob.blob().cid() {
                 warn!("Rejected record: rkey CID doesn't match blob CID");
+                state.record_rejection(RejectReason::CidMismatch);
                 return Ok(());
             }
         }
         None => {
             warn!("Rejected record: rkey doesn't match tid:cid format");
+            state.record_rejection(RejectReason::InvalidRkey);
             return Ok(());
         }
     };
 
     // Loosely-validate the provided blob's mimetype + size.
-    if !matches!(
-        data.media.blob.blob().mime_type.as_str(),
-        "image/gif" | "image/webp"
-    ) {
+    if !state.is_allowed_media_mime(data.media.blob.blob().mime_type.as_str()) {
         warn!("Rejected record: blob isn't a valid mimetype");
+        state.record_rejection(RejectReason::InvalidMime);
         return Ok(());
     }
-    if data.media.blob.blob().size == 10 * 1024 * 1024 {
+    if data.media.blob.blob().size > state.blob_limits.max_blob_size {
         warn!("Rejected record: blob is above maximum size");
+        state.record_rejection(RejectReason::Oversize);
         return Ok(());
     }
 
-    // Extract tag/lang data.
-    let tags_array = data
-        .tags
-        .as_ref()
-        .filter(|tags| !tags.is_empty())
-        .map(|tags| {
-            tags.iter()
-                .map(|cow| cow.to_string())
-                .collect::<Vec<String>>()
-        });
-    let languages_array = data
+    // Extract tag/lang data, capping both lists before they reach the
+    // database - the appview serializes them into every feed item, so an
+    // unbounded list in one record would bloat whole feed pages.
+    let tags_array = match data.tags.as_ref().filter(|tags| !tags.is_empty()).map(|tags| {
+        tags.iter()
+            .map(|cow| cow.to_string())
+            .collect::<Vec<String>>()
+    }) {
+        Some(tags) => match apply_list_limits(
+            tags,
+            state.max_post_tags,
+            state.max_tag_length,
+            state.over_limit_policy,
+            "tags",
+        ) {
+            Some(tags) => Some(tags),
+            None => {
+                state.record_rejection(RejectReason::OverLimit);
+                return Ok(());
+            }
+        },
+        None => None,
+    };
+    // Language tags are already BCP-47-validated when the record is
+    // deserialized (the lexicon declares the `language` format), so only
+    // casing is normalized here - stored values should compare cleanly for
+    // client-side filtering regardless of how the writing client cased them.
+    let languages_array = match data
         .languages
         .as_ref()
         .filter(|langs| !langs.is_empty())
         .map(|langs| {
             langs
                 .iter()
-                .map(|cow| cow.to_string())
+                .map(|lang| normalize_language_tag(lang.as_str()))
                 .collect::<Vec<String>>()
-        });
+        }) {
+        Some(languages) => match apply_list_limits(
+            languages,
+            state.max_post_languages,
+            state.max_tag_length,
+            state.over_limit_policy,
+            "languages",
+        ) {
+            Some(languages) => Some(languages),
+            None => {
+                state.record_rejection(RejectReason::OverLimit);
+                return Ok(());
+            }
+        },
+        None => None,
+    };
 
     let pds = state
         .tap_client
@@ -78,41 +114,93 @@ pub async fn handle_post_create(
         .await?
         .pds_endpoint()
         .unwrap();
-    let response = validate_gif_or_webp(
-        &pds.join(&format!(
-            "/xrpc/com.atproto.sync.getBlob?did={}&cid={}",
-            record_data.did,
-            record_data.rkey.split_once(":").unwrap().1
-        ))?,
-        &state.http_client,
-    )
-    .await?;
+    let blob_url = pds.join(&format!(
+        "/xrpc/com.atproto.sync.getBlob?did={}&cid={}",
+        record_data.did,
+        record_data.rkey.split_once(":").unwrap().1
+    ))?;
+    let response = validate_media(&blob_url, state).await?;
     println!("{response:?}");
 
-    match query!(
-        "INSERT INTO posts (did, rkey, title, media_blob_cid, media_blob_mime, \
-         media_blob_alt, media_blob_width, media_blob_height, tags, languages, created_at) \
-         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11) \
-         ON CONFLICT(did, rkey) DO UPDATE SET \
-         title = excluded.title, \
-         media_blob_alt = excluded.media_blob_alt, \
-         tags = excluded.tags, \
-         edited_at = extract(epoch from now())::BIGINT",
-        record_data.did.as_str(),
-        record_data.rkey.as_str(),
-        data.title.as_str(),
-        data.media.blob.blob().cid().as_str(),
-        response.mime_type,
-        data.media.alt.as_ref().map(|v| v.as_str()),
-        response.width as i64,
-        response.height as i64,
-        tags_array.as_deref(),
-        languages_array.as_deref(),
-        data.created_at.as_ref().timestamp_millis()
-    )
-    .execute(&mut **tx)
-    .await
-    {
+    // Compute a blurhash placeholder from the media's first frame. This is
+    // best-effort - a failure leaves the column null rather than rejecting the
+    // record.
+    let blurhash = match compute_blurhash(&blob_url, &state.http_client).await {
+        Ok(hash) => Some(hash),
+        Err(err) => {
+            warn!("Failed to compute blurhash: {err:?}");
+            None
+        }
+    };
+
+    if state.dry_run {
+        info!("Dry run - would have upserted post into database");
+        return Ok(());
+    }
+
+    // Creates and updates take different conflict paths: a redelivered or
+    // out-of-order create must never clobber a row a later update has already
+    // written, and updates are guarded on the repo revision so a stale update
+    // can't overwrite fresher state.
+    let result = match action {
+        RecordAction::Update { .. } => {
+            query!(
+                "INSERT INTO posts (did, rkey, title, media_blob_cid, media_blob_mime, \
+                 media_blob_alt, media_blob_width, media_blob_height, tags, languages, blurhash, \
+                 rev, created_at, indexed_at) \
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, \
+                 (extract(epoch from now()) * 1000)::BIGINT) \
+                 ON CONFLICT(did, rkey) DO UPDATE SET \
+                 title = excluded.title, \
+                 media_blob_alt = excluded.media_blob_alt, \
+                 tags = excluded.tags, \
+                 rev = excluded.rev, \
+                 edited_at = (extract(epoch from now()) * 1000)::BIGINT \
+                 WHERE posts.rev IS NULL OR posts.rev < excluded.rev",
+                record_data.did.as_str(),
+                record_data.rkey.as_str(),
+                data.title.as_str(),
+                data.media.blob.blob().cid().as_str(),
+                response.mime_type,
+                data.media.alt.as_ref().map(|v| v.as_str()),
+                response.width as i64,
+                response.height as i64,
+                tags_array.as_deref(),
+                languages_array.as_deref(),
+                blurhash.as_deref(),
+                record_data.rev.as_str(),
+                data.created_at.as_ref().timestamp_millis()
+            )
+            .execute(&mut **tx)
+            .await
+        }
+        _ => {
+            query!(
+                "INSERT INTO posts (did, rkey, title, media_blob_cid, media_blob_mime, \
+                 media_blob_alt, media_blob_width, media_blob_height, tags, languages, blurhash, \
+                 rev, created_at, indexed_at) \
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, \
+                 (extract(epoch from now()) * 1000)::BIGINT) \
+                 ON CONFLICT(did, rkey) DO NOTHING",
+                record_data.did.as_str(),
+                record_data.rkey.as_str(),
+                data.title.as_str(),
+                data.media.blob.blob().cid().as_str(),
+                response.mime_type,
+                data.media.alt.as_ref().map(|v| v.as_str()),
+                response.width as i64,
+                response.height as i64,
+                tags_array.as_deref(),
+                languages_array.as_deref(),
+                blurhash.as_deref(),
+                record_data.rev.as_str(),
+                data.created_at.as_ref().timestamp_millis()
+            )
+            .execute(&mut **tx)
+            .await
+        }
+    };
+    match result {
         Ok(_) => {
             info!("Upserted post into database");
             Ok(())
@@ -124,11 +212,73 @@ pub async fn handle_post_create(
     }
 }
 
+/// Normalize a BCP-47 language tag to conventional casing - lowercase
+/// language, titlecase script, uppercase region - without changing its
+/// contents. `en-us` and `EN-US` both normalize to `en-US`.
+fn normalize_language_tag(tag: &str) -> String {
+    tag.split('-')
+        .enumerate()
+        .map(|(position, subtag)| match (position, subtag.len()) {
+            (0, _) => subtag.to_ascii_lowercase(),
+            (_, 4) => {
+                let mut script = subtag.to_ascii_lowercase();
+                script[..1].make_ascii_uppercase();
+                script
+            }
+            (_, 2) => subtag.to_ascii_uppercase(),
+            _ => subtag.to_ascii_lowercase(),
+        })
+        .collect::<Vec<String>>()
+        .join("-")
+}
+
+/// Apply the configured item-count and item-length limits to a post's tag or
+/// language list. Returns `None` when the record should be rejected outright,
+/// otherwise the (possibly truncated) list.
+fn apply_list_limits(
+    list: Vec<String>,
+    max_items: usize,
+    max_item_length: usize,
+    policy: OverLimitPolicy,
+    what: &str,
+) -> Option<Vec<String>> {
+    let over_limit = list.len() > max_items
+        || list
+            .iter()
+            .any(|item| item.chars().count() > max_item_length);
+    if !over_limit {
+        return Some(list);
+    }
+    match policy {
+        OverLimitPolicy::Reject => {
+            warn!("Rejected record: {what} exceed the configured limits");
+            None
+        }
+        OverLimitPolicy::Truncate => {
+            warn!("Truncating over-limit {what}");
+            Some(
+                list.into_iter()
+                    .take(max_items)
+                    .map(|item| match item.chars().count() > max_item_length {
+                        true => item.chars().take(max_item_length).collect(),
+                        false => item,
+                    })
+                    .collect(),
+            )
+        }
+    }
+}
+
 pub async fn handle_post_delete(
     record_data: &RecordEventData<'_>,
     tx: &mut PgTransaction<'_>,
-    _state: &AppState,
+    state: &AppState,
 ) -> Result<()> {
+    if state.dry_run {
+        info!("Dry run - would have deleted post from database");
+        return Ok(());
+    }
+
     match query!(
         "DELETE FROM posts WHERE did = $1 AND rkey = $2",
         record_data.did.as_str(),
@@ -148,9 +298,36 @@ pub async fn handle_post_delete(
     }
 }
 
-async fn validate_gif_or_webp(url: &Url, http_client: &reqwest::Client) -> Result<ImageInfo> {
+/// Fetch the blob and compute a blurhash placeholder string from its first frame.
+async fn compute_blurhash(url: &Url, http_client: &reqwest::Client) -> Result<String> {
+    let bytes = http_client
+        .get(url.as_str())
+        .timeout(Duration::from_secs(30))
+        .send()
+        .await
+        .context("Failed to fetch blob")?
+        .bytes()
+        .await
+        .context("Failed to read blob body")?;
+    tokio::task::spawn_blocking(move || {
+        // Blurhashes only encode a handful of components, so work on a small
+        // thumbnail of the first frame instead of the full-size image.
+        let image = image::load_from_memory(&bytes)
+            .context("Failed to decode image")?
+            .thumbnail(64, 64)
+            .to_rgba8();
+        let (width, height) = image.dimensions();
+        blurhash::encode(4, 3, width, height, image.as_raw())
+            .context("Failed to encode blurhash")
+    })
+    .await
+    .context("Blurhash task panicked")?
+}
+
+async fn validate_media(url: &Url, state: &AppState) -> Result<ImageInfo> {
     let mut buffer = Vec::new();
-    let mut response = http_client
+    let mut response = state
+        .http_client
         .get(url.as_str())
         .timeout(Duration::from_secs(10))
         .send()
@@ -165,7 +342,7 @@ async fn validate_gif_or_webp(url: &Url, http_client: &reqwest::Client) -> Resul
             // Validate MIME type first (fast check)
             if let Some(kind) = infer::get(&buffer) {
                 let mime = kind.mime_type();
-                if mime != "image/gif" && mime != "image/webp" {
+                if !state.is_allowed_media_mime(mime) {
                     bail!("Unsupported format: {}", mime);
                 }
 
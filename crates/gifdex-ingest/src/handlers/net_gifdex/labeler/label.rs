@@ -1,4 +1,4 @@
-use crate::AppState;
+use crate::{AppState, RejectReason};
 use anyhow::Result;
 use floodgate::api::RecordEventData;
 use gifdex_lexicons::net_gifdex::{self, labeler::rule::Rule};
@@ -10,7 +10,7 @@ pub async fn handle_label_create_event(
     record_data: &RecordEventData<'_>,
     data: &net_gifdex::labeler::label::Label<'_>,
     tx: &mut PgTransaction<'_>,
-    _state: &AppState,
+    state: &AppState,
 ) -> Result<()> {
     let (subject_did, subject_collection, subject_rkey) = (
         data.subject.authority().as_str(),
@@ -28,6 +28,7 @@ pub async fn handle_label_create_event(
                 rule_uri = data.rule.as_str(),
                 "Rejected record: rule must be a complete AT-URI with collection and rkey"
             );
+            state.record_rejection(RejectReason::InvalidRule);
             return Ok(());
         }
         (_, Some(collection), _) if collection != Rule::NSID => {
@@ -36,6 +37,7 @@ pub async fn handle_label_create_event(
                 "Rejected record: rule must reference the {} collection",
                 Rule::NSID
             );
+            state.record_rejection(RejectReason::InvalidRule);
             return Ok(());
         }
         (rule_did, _, _) => {
@@ -44,10 +46,16 @@ pub async fn handle_label_create_event(
                 labeler_did = record_data.did.as_str(),
                 "Rejected record: labeler can only apply their own rules"
             );
+            state.record_rejection(RejectReason::InvalidRule);
             return Ok(());
         }
     };
 
+    if state.dry_run {
+        info!("Dry run - would have upserted label application");
+        return Ok(());
+    }
+
     match query!(
         "INSERT INTO labels (\
              rkey, did, rule_did, rule_rkey, \
@@ -64,7 +72,7 @@ pub async fn handle_label_create_event(
          subject_rkey = excluded.subject_rkey, \
          reason = excluded.reason, \
          created_at = excluded.created_at, \
-         edited_at = extract(epoch from now())::BIGINT, \
+         edited_at = (extract(epoch from now()) * 1000)::BIGINT, \
          expires_at = excluded.expires_at",
         record_data.rkey.as_str(),
         record_data.did.as_str(),
@@ -77,7 +85,7 @@ pub async fn handle_label_create_event(
         data.created_at.as_ref().timestamp_millis(),
         data.expires_at
             .as_ref()
-            .map(|expiry| expiry.as_ref().timestamp_micros())
+            .map(|expiry| expiry.as_ref().timestamp_millis())
     )
     .execute(&mut **tx)
     .await
@@ -96,8 +104,13 @@ pub async fn handle_label_create_event(
 pub async fn handle_label_delete_event(
     record_data: &RecordEventData<'_>,
     tx: &mut PgTransaction<'_>,
-    _state: &AppState,
+    state: &AppState,
 ) -> Result<()> {
+    if state.dry_run {
+        info!("Dry run - would have deleted label application");
+        return Ok(());
+    }
+
     match query!(
         "DELETE FROM labels WHERE did = $1 AND rkey = $2",
         record_data.did.as_str(),
@@ -1,4 +1,4 @@
-use crate::AppState;
+use crate::{AppState, RejectReason};
 use anyhow::Result;
 use floodgate::api::RecordEventData;
 use gifdex_lexicons::net_gifdex;
@@ -9,7 +9,7 @@ pub async fn handle_rule_create_event(
     record_data: &RecordEventData<'_>,
     data: &net_gifdex::labeler::rule::Rule<'_>,
     tx: &mut PgTransaction<'_>,
-    _state: &AppState,
+    state: &AppState,
 ) -> Result<()> {
     // Determine behaviour type and extract fields based on behaviour variant.
     let (behaviour, default_setting, adult_content, takedown) = match &data.behaviour {
@@ -22,12 +22,18 @@ pub async fn handle_rule_create_event(
         net_gifdex::labeler::rule::RuleBehaviour::Moderate(moderate) => {
             ("moderate", None, None, Some(moderate.takedown))
         }
-        behaviour @ _ => {
+        behaviour => {
             tracing::warn!("Rejected record: unknown rule_behaviour: {behaviour:?}");
+            state.record_rejection(RejectReason::InvalidRule);
             return Ok(());
         }
     };
 
+    if state.dry_run {
+        info!("Dry run - would have upserted labeler rule");
+        return Ok(());
+    }
+
     match query!(
         r#"INSERT INTO labeler_rules (
             rkey, did, name, description, behaviour,
@@ -35,7 +41,7 @@ pub async fn handle_rule_create_event(
             created_at, indexed_at
         ) VALUES (
             $1, $2, $3, $4, $5::TEXT::labeler_behaviour, $6::TEXT::labeler_behaviour_setting, $7, $8,
-            $9, extract(epoch from now())::BIGINT
+            $9, (extract(epoch from now()) * 1000)::BIGINT
         )
         ON CONFLICT(did, rkey) DO UPDATE SET
             name = excluded.name,
@@ -45,7 +51,7 @@ pub async fn handle_rule_create_event(
             adult_content = excluded.adult_content,
             takedown = excluded.takedown,
             created_at = excluded.created_at,
-            edited_at = extract(epoch from now())::BIGINT"#,
+            edited_at = (extract(epoch from now()) * 1000)::BIGINT"#,
         record_data.rkey.as_str(),
         record_data.did.as_str(),
         data.name.as_str(),
@@ -73,8 +79,13 @@ pub async fn handle_rule_create_event(
 pub async fn handle_rule_delete_event(
     record_data: &RecordEventData<'_>,
     tx: &mut PgTransaction<'_>,
-    _state: &AppState,
+    state: &AppState,
 ) -> Result<()> {
+    if state.dry_run {
+        info!("Dry run - would have deleted labeler rule");
+        return Ok(());
+    }
+
     match query!(
         "DELETE FROM labeler_rules WHERE did = $1 AND rkey = $2",
         record_data.did.as_str(),
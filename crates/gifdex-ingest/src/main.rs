@@ -1,12 +1,40 @@
 mod database;
 mod handlers;
+#[cfg(test)]
+mod test_support;
 
-use crate::{database::Database, handlers::handle_event};
+use crate::{
+    database::{Database, DatabaseConfig},
+    handlers::handle_event,
+};
 use anyhow::{Context, Result};
-use clap::Parser;
+use clap::{Parser, Subcommand, ValueEnum};
 use dotenvy::dotenv;
-use floodgate::client::TapClient;
-use std::{num::NonZero, sync::Arc, time::Duration};
+use floodgate::{client::TapClient, extern_types::CancellationToken};
+use gifdex_lexicons::{
+    limits::{
+        BlobLimits, DEFAULT_AVATAR_MIME_TYPES, DEFAULT_MAX_AVATAR_SIZE, DEFAULT_MAX_BLOB_SIZE,
+        DEFAULT_MAX_POST_LANGUAGES, DEFAULT_MAX_POST_TAGS, DEFAULT_MAX_TAG_LENGTH,
+        DEFAULT_MEDIA_MIME_TYPES,
+    },
+    net_gifdex,
+};
+use jacquard_common::{
+    IntoStatic,
+    types::{collection::Collection, did::Did},
+};
+use sqlx::query;
+use std::{
+    collections::HashMap,
+    io::Write,
+    num::NonZero,
+    path::{Path, PathBuf},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
 use tracing_subscriber::EnvFilter;
 use url::Url;
 
@@ -17,6 +45,30 @@ struct Arguments {
     #[clap(long = "database-url", env = "DATABASE_URL")]
     database_url: String,
 
+    /// Maximum number of connections in the database pool.
+    #[clap(
+        long = "database-max-connections",
+        env = "GIFDEX_INGEST_DATABASE_MAX_CONNECTIONS",
+        default_value_t = 20
+    )]
+    database_max_connections: u32,
+
+    /// Seconds to wait for a free database connection before failing a query.
+    #[clap(
+        long = "database-acquire-timeout",
+        env = "GIFDEX_INGEST_DATABASE_ACQUIRE_TIMEOUT",
+        default_value_t = 30
+    )]
+    database_acquire_timeout: u64,
+
+    /// Seconds an idle database connection is kept around before being closed.
+    #[clap(
+        long = "database-idle-timeout",
+        env = "GIFDEX_INGEST_DATABASE_IDLE_TIMEOUT",
+        default_value_t = 600
+    )]
+    database_idle_timeout: u64,
+
     #[clap(long = "tap-url", env = "GIFDEX_INGEST_TAP_URL")]
     tap_url: Url,
 
@@ -25,12 +77,317 @@ struct Arguments {
 
     #[clap(long = "concurrent-messages", env = "GIFDEX_CONCURRENT_MESSAGES")]
     concurrent_messages: NonZero<usize>,
+
+    /// Number of failed delivery attempts before a record event is parked in
+    /// the dead_letter table and acked. Set to 0 to retry forever.
+    #[clap(
+        long = "dead-letter-retries",
+        env = "GIFDEX_INGEST_DEAD_LETTER_RETRIES",
+        default_value_t = 5
+    )]
+    dead_letter_retries: u32,
+
+    /// Reconcile tap's tracked repositories with the accounts table on
+    /// startup, subscribing it to every DID we already know about.
+    #[clap(long = "sync-repos", env = "GIFDEX_INGEST_SYNC_REPOS")]
+    sync_repos: bool,
+
+    /// Maximum number of tags stored per post.
+    #[clap(
+        long = "max-post-tags",
+        env = "GIFDEX_INGEST_MAX_POST_TAGS",
+        default_value_t = DEFAULT_MAX_POST_TAGS
+    )]
+    max_post_tags: usize,
+
+    /// Maximum length in characters of a single post tag.
+    #[clap(
+        long = "max-tag-length",
+        env = "GIFDEX_INGEST_MAX_TAG_LENGTH",
+        default_value_t = DEFAULT_MAX_TAG_LENGTH
+    )]
+    max_tag_length: usize,
+
+    /// Maximum number of declared languages stored per post.
+    #[clap(
+        long = "max-post-languages",
+        env = "GIFDEX_INGEST_MAX_POST_LANGUAGES",
+        default_value_t = DEFAULT_MAX_POST_LANGUAGES
+    )]
+    max_post_languages: usize,
+
+    /// What to do with a post whose tags or languages exceed the configured
+    /// limits.
+    #[clap(
+        long = "over-limit-policy",
+        env = "GIFDEX_INGEST_OVER_LIMIT_POLICY",
+        value_enum,
+        default_value_t = OverLimitPolicy::Truncate
+    )]
+    over_limit_policy: OverLimitPolicy,
+
+    /// Reject records that fail strict lexicon validation (title length
+    /// bounds, tag count and length limits, ...) instead of only the spot
+    /// checks the handlers always run.
+    #[clap(long = "strict-validation", env = "GIFDEX_INGEST_STRICT_VALIDATION")]
+    strict_validation: bool,
+
+    /// Cross-check each identity event's handle against the DID document's
+    /// alsoKnownAs entries, storing the handle only when it is listed there.
+    #[clap(long = "verify-handles", env = "GIFDEX_INGEST_VERIFY_HANDLES")]
+    verify_handles: bool,
+
+    /// Run every handler's validation logic but skip all database writes,
+    /// logging what would have been written instead.
+    #[clap(long = "dry-run", env = "GIFDEX_INGEST_DRY_RUN")]
+    dry_run: bool,
+
+    /// Comma-separated media types accepted for post media blobs.
+    ///
+    /// Should match the CDN's allow-list so the CDN never refuses to serve
+    /// media that was accepted at ingest time.
+    #[clap(
+        long = "media-mime-types",
+        env = "GIFDEX_INGEST_MEDIA_MIME_TYPES",
+        value_delimiter = ',',
+        default_values_t = DEFAULT_MEDIA_MIME_TYPES.iter().map(|mime| mime.to_string())
+    )]
+    media_mime_types: Vec<String>,
+
+    /// Comma-separated media types accepted for avatar blobs.
+    #[clap(
+        long = "avatar-mime-types",
+        env = "GIFDEX_INGEST_AVATAR_MIME_TYPES",
+        value_delimiter = ',',
+        default_values_t = DEFAULT_AVATAR_MIME_TYPES.iter().map(|mime| mime.to_string())
+    )]
+    avatar_mime_types: Vec<String>,
+
+    /// Maximum accepted size in bytes of a post's media blob.
+    ///
+    /// Should match the CDN's limit so the CDN never refuses to serve media
+    /// that was accepted at ingest time.
+    #[clap(
+        long = "max-blob-size",
+        env = "GIFDEX_INGEST_MAX_BLOB_SIZE",
+        default_value_t = DEFAULT_MAX_BLOB_SIZE
+    )]
+    max_blob_size: usize,
+
+    /// Maximum accepted size in bytes of a profile avatar blob.
+    #[clap(
+        long = "max-avatar-size",
+        env = "GIFDEX_INGEST_MAX_AVATAR_SIZE",
+        default_value_t = DEFAULT_MAX_AVATAR_SIZE
+    )]
+    max_avatar_size: usize,
+
+    /// Seconds between log lines reporting the rolling average and maximum
+    /// ingest lag.
+    #[clap(
+        long = "lag-report-interval",
+        env = "GIFDEX_INGEST_LAG_REPORT_INTERVAL",
+        default_value_t = 60
+    )]
+    lag_report_interval: u64,
+
+    /// Seconds between background sweeps deleting labels whose expiry has
+    /// passed.
+    #[clap(
+        long = "label-reap-interval",
+        env = "GIFDEX_INGEST_LABEL_REAP_INTERVAL",
+        default_value_t = 3600
+    )]
+    label_reap_interval: u64,
+
+    /// OTLP gRPC endpoint to export trace spans to. Span export is disabled
+    /// when unset; only available when built with the `otel` feature.
+    #[cfg(feature = "otel")]
+    #[clap(long = "otlp-endpoint", env = "GIFDEX_INGEST_OTLP_ENDPOINT")]
+    otlp_endpoint: Option<String>,
+
+    /// Append every incoming event's raw JSON to this file, one event per
+    /// line, for later offline reprocessing with the `replay` subcommand.
+    #[clap(long = "record-to", env = "GIFDEX_INGEST_RECORD_TO")]
+    record_to: Option<PathBuf>,
+
+    /// Emit logs as newline-delimited JSON instead of human-readable text.
+    #[clap(long = "log-json", env = "GIFDEX_INGEST_LOG_JSON")]
+    log_json: bool,
+
+    #[clap(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Replay the full contents of existing repositories through tap.
+    ///
+    /// The replayed records arrive on the regular event channel and are
+    /// indexed through the same upserts as live traffic, so backfilling a
+    /// repository more than once is harmless.
+    Backfill {
+        /// DIDs of the repositories to backfill.
+        dids: Vec<String>,
+    },
+
+    /// Feed events captured with --record-to through the regular event
+    /// handlers, exactly as if they had arrived on the tap channel.
+    ///
+    /// Useful for reprocessing a window of traffic offline - after a handler
+    /// bugfix, or to reproduce an ingest problem without a live tap.
+    Replay {
+        /// Path to a newline-delimited JSON event file written by --record-to.
+        file: PathBuf,
+    },
+}
+
+/// What to do with a post whose tag or language lists exceed the configured
+/// limits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum OverLimitPolicy {
+    /// Drop the whole record with a warning.
+    Reject,
+    /// Keep the record, cutting the offending lists and values down to the
+    /// limits.
+    Truncate,
+}
+
+/// Why a record was rejected instead of stored. Rejections are counted per
+/// reason and reported alongside the ingest lag, so a spike in any single
+/// reason - say a client minting rkeys whose CID doesn't match the blob -
+/// shows up as signal rather than scattered log lines.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum RejectReason {
+    /// The record payload failed to deserialize against its lexicon.
+    Deserialize,
+    /// The record failed strict lexicon validation.
+    StrictValidation,
+    /// The record's rkey is malformed for its collection.
+    InvalidRkey,
+    /// The post's rkey CID doesn't match its media blob's CID.
+    CidMismatch,
+    /// A referenced blob's CID is malformed.
+    InvalidBlobCid,
+    /// A blob's media type isn't in the configured allow-list.
+    InvalidMime,
+    /// A blob is above the configured maximum size.
+    Oversize,
+    /// A tag or language list exceeded the limits under the reject policy.
+    OverLimit,
+    /// A favourite's subject AT-URI is malformed or not a post.
+    InvalidSubject,
+    /// A label or rule record references or declares an unusable rule.
+    InvalidRule,
+}
+
+impl RejectReason {
+    /// Stable snake_case key used in the periodic rejection report.
+    fn as_str(&self) -> &'static str {
+        match self {
+            RejectReason::Deserialize => "deserialize",
+            RejectReason::StrictValidation => "strict_validation",
+            RejectReason::InvalidRkey => "invalid_rkey",
+            RejectReason::CidMismatch => "cid_mismatch",
+            RejectReason::InvalidBlobCid => "invalid_blob_cid",
+            RejectReason::InvalidMime => "invalid_mime",
+            RejectReason::Oversize => "oversize",
+            RejectReason::OverLimit => "over_limit",
+            RejectReason::InvalidSubject => "invalid_subject",
+            RejectReason::InvalidRule => "invalid_rule",
+        }
+    }
+}
+
+/// Rolling window of ingest lag samples - the delta between a record's
+/// `created_at` and the time the ingester finished processing it.
+#[derive(Default)]
+struct IngestLag {
+    sum_ms: u64,
+    max_ms: u64,
+    samples: u64,
 }
 
 struct AppState {
     database: Database,
     tap_client: TapClient,
     http_client: reqwest::Client,
+    dead_letter_retries: u32,
+    dead_letter_attempts: Mutex<HashMap<(String, String, String), u32>>,
+    strict_validation: bool,
+    verify_handles: bool,
+    max_post_tags: usize,
+    max_tag_length: usize,
+    max_post_languages: usize,
+    over_limit_policy: OverLimitPolicy,
+    dry_run: bool,
+    media_mime_types: Vec<String>,
+    avatar_mime_types: Vec<String>,
+    blob_limits: BlobLimits,
+    ingest_lag: Mutex<IngestLag>,
+    unknown_events: AtomicU64,
+    rejections: Mutex<HashMap<RejectReason, u64>>,
+}
+
+impl AppState {
+    /// Sample how far behind real-time a just-processed record was, measured
+    /// from its `created_at`. Backfilled (non-live) records are skipped so
+    /// historic replays don't skew the numbers.
+    fn record_ingest_lag(&self, live: bool, created_at_ms: i64) {
+        if !live {
+            return;
+        }
+        let now_ms = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as i64;
+        let lag_ms = now_ms.saturating_sub(created_at_ms).max(0) as u64;
+        let mut lag = self.ingest_lag.lock().unwrap();
+        lag.sum_ms += lag_ms;
+        lag.max_ms = lag.max_ms.max(lag_ms);
+        lag.samples += 1;
+    }
+
+    /// Average and maximum lag observed since the last call, resetting the
+    /// window. `None` when no live records were processed in the window.
+    fn take_ingest_lag(&self) -> Option<(u64, u64)> {
+        let mut lag = self.ingest_lag.lock().unwrap();
+        let snapshot = (lag.samples > 0).then(|| (lag.sum_ms / lag.samples, lag.max_ms));
+        *lag = IngestLag::default();
+        snapshot
+    }
+
+    /// Count an event of a type this version of the ingester doesn't
+    /// recognise and therefore skipped.
+    pub(crate) fn record_unknown_event(&self) {
+        self.unknown_events.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Number of unknown events skipped since the last call, resetting the
+    /// counter.
+    fn take_unknown_events(&self) -> u64 {
+        self.unknown_events.swap(0, Ordering::Relaxed)
+    }
+
+    /// Count a record rejected for `reason`.
+    pub(crate) fn record_rejection(&self, reason: RejectReason) {
+        *self.rejections.lock().unwrap().entry(reason).or_insert(0) += 1;
+    }
+
+    /// Rejection counts per reason since the last call, resetting the window.
+    fn take_rejections(&self) -> HashMap<RejectReason, u64> {
+        std::mem::take(&mut *self.rejections.lock().unwrap())
+    }
+
+    /// Whether `mime` is an accepted media type for post media blobs.
+    fn is_allowed_media_mime(&self, mime: &str) -> bool {
+        self.media_mime_types.iter().any(|allowed| allowed == mime)
+    }
+
+    /// Whether `mime` is an accepted media type for avatar blobs.
+    fn is_allowed_avatar_mime(&self, mime: &str) -> bool {
+        self.avatar_mime_types.iter().any(|allowed| allowed == mime)
+    }
 }
 
 #[tokio::main]
@@ -39,10 +396,17 @@ async fn main() -> Result<()> {
         .install_default()
         .expect("Failed to install default rustls crypto provider");
     dotenv().ok();
-    tracing_subscriber::fmt()
-        .with_env_filter(EnvFilter::try_from_default_env().unwrap_or(EnvFilter::new("info")))
-        .init();
     let args = Arguments::parse();
+    // Structured JSON output for log aggregators when requested, the default
+    // human-readable text format otherwise.
+    let log_filter = EnvFilter::try_from_default_env().unwrap_or(EnvFilter::new("info"));
+    #[cfg(feature = "otel")]
+    match &args.otlp_endpoint {
+        Some(endpoint) => init_otlp_tracing(endpoint, log_filter, args.log_json)?,
+        None => init_local_tracing(log_filter, args.log_json),
+    }
+    #[cfg(not(feature = "otel"))]
+    init_local_tracing(log_filter, args.log_json);
 
     // Initialise application state.
     let tap_client = TapClient::builder(args.tap_url.clone())
@@ -52,6 +416,14 @@ async fn main() -> Result<()> {
     let tap_channel = tap_client
         .channel()
         .max_concurrent(args.concurrent_messages)
+        .retain_raw_json(args.record_to.is_some())
+        .collections(&[
+            net_gifdex::feed::post::Post::nsid(),
+            net_gifdex::feed::favourite::Favourite::nsid(),
+            net_gifdex::actor::profile::Profile::nsid(),
+            net_gifdex::labeler::label::Label::nsid(),
+            net_gifdex::labeler::rule::Rule::nsid(),
+        ])
         .build()
         .context("failed to construct tap channel")?;
     let http_client = reqwest::Client::builder()
@@ -63,7 +435,12 @@ async fn main() -> Result<()> {
         .build()
         .context("failed to initialise http client")?;
 
-    let database = Database::new(&args.database_url)
+    let database_config = DatabaseConfig {
+        max_connections: args.database_max_connections,
+        acquire_timeout: Duration::from_secs(args.database_acquire_timeout),
+        idle_timeout: Duration::from_secs(args.database_idle_timeout),
+    };
+    let database = Database::new(&args.database_url, &database_config)
         .await
         .context("failed to initialise database")?;
 
@@ -72,29 +449,312 @@ async fn main() -> Result<()> {
         database,
         tap_client,
         http_client,
+        dead_letter_retries: args.dead_letter_retries,
+        dead_letter_attempts: Mutex::new(HashMap::new()),
+        strict_validation: args.strict_validation,
+        verify_handles: args.verify_handles,
+        max_post_tags: args.max_post_tags,
+        max_tag_length: args.max_tag_length,
+        max_post_languages: args.max_post_languages,
+        over_limit_policy: args.over_limit_policy,
+        dry_run: args.dry_run,
+        media_mime_types: args.media_mime_types,
+        avatar_mime_types: args.avatar_mime_types,
+        blob_limits: BlobLimits {
+            max_blob_size: args.max_blob_size,
+            max_avatar_size: args.max_avatar_size,
+        },
+        ingest_lag: Mutex::new(IngestLag::default()),
+        unknown_events: AtomicU64::new(0),
+        rejections: Mutex::new(HashMap::new()),
+    });
+    if args.sync_repos {
+        sync_repos(&state)
+            .await
+            .context("failed to sync tracked repositories with tap")?;
+    }
+    if let Some(Command::Backfill { dids }) = &args.command {
+        return backfill(&state, dids)
+            .await
+            .context("failed to backfill repositories");
+    }
+    if let Some(Command::Replay { file }) = &args.command {
+        return replay(&state, file)
+            .await
+            .context("failed to replay recorded events");
+    }
+
+    // When recording, the channel retains each event's raw wire frame and the
+    // handler wrapper below appends it to the capture file verbatim, so a
+    // replay sees byte-for-byte what the channel saw.
+    let recorder = match &args.record_to {
+        Some(path) => {
+            let file = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .with_context(|| format!("failed to open record file {}", path.display()))?;
+            Some(Arc::new(Mutex::new(file)))
+        }
+        None => None,
+    };
+
+    // Periodically report how far behind real-time the ingester is running.
+    tokio::spawn(report_ingest_lag(
+        state.clone(),
+        Duration::from_secs(args.lag_report_interval),
+    ));
+    // Reap expired labels in the background for as long as the ingester runs.
+    tokio::spawn(reap_expired_labels(
+        state.clone(),
+        Duration::from_secs(args.label_reap_interval),
+    ));
+
+    // Cancel the channel on Ctrl+C/SIGTERM so in-flight handler tasks drain
+    // and their acks flush before the process exits.
+    let shutdown = CancellationToken::new();
+    tokio::spawn({
+        let shutdown = shutdown.clone();
+        async move {
+            shutdown_signal().await;
+            tracing::info!("Shutdown signal received - draining in-flight events");
+            shutdown.cancel();
+        }
     });
     loop {
-        const TAP_RECONNECT_INTERVAL: Duration = Duration::from_secs(30);
         let state = state.clone();
-        let connection = match tap_channel.connect().await {
-            Ok(r) => r,
+        let recorder = recorder.clone();
+        let connection = tokio::select! {
+            _ = shutdown.cancelled() => break,
+            connection = tap_channel.connect_with_retry() => connection,
+        };
+        connection
+            .handler_with_shutdown(
+                move |data| {
+                    let state = state.clone();
+                    let recorder = recorder.clone();
+                    async move {
+                        if let Some(recorder) = &recorder
+                            && let Some(raw) = data.raw()
+                            && let Err(err) = writeln!(recorder.lock().unwrap(), "{raw}")
+                        {
+                            tracing::warn!("Failed to record event: {err:?}");
+                        }
+                        handle_event(state, data).await
+                    }
+                },
+                shutdown.clone(),
+            )
+            .await;
+        if shutdown.is_cancelled() {
+            break;
+        }
+        tracing::info!("Tap channel was closed while handling events - reconnecting automatically");
+    }
+    tracing::info!("In-flight events drained - exiting");
+    Ok(())
+}
+
+/// Initialise the plain local tracing subscriber.
+fn init_local_tracing(log_filter: EnvFilter, log_json: bool) {
+    match log_json {
+        true => tracing_subscriber::fmt()
+            .with_env_filter(log_filter)
+            .json()
+            .init(),
+        false => tracing_subscriber::fmt().with_env_filter(log_filter).init(),
+    }
+}
+
+/// Initialise tracing with an OTLP span exporter layered on top of the local
+/// log output, shipping spans to the collector at `endpoint`. The W3C trace
+/// context propagator is installed globally so outbound requests can carry
+/// the active trace.
+#[cfg(feature = "otel")]
+fn init_otlp_tracing(endpoint: &str, log_filter: EnvFilter, log_json: bool) -> Result<()> {
+    use opentelemetry::trace::TracerProvider as _;
+    use opentelemetry_otlp::WithExportConfig;
+    use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+    let exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_tonic()
+        .with_endpoint(endpoint)
+        .build()?;
+    let provider = opentelemetry_sdk::trace::SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(
+            opentelemetry_sdk::Resource::builder()
+                .with_service_name(env!("CARGO_PKG_NAME"))
+                .build(),
+        )
+        .build();
+    let tracer = provider.tracer(env!("CARGO_PKG_NAME"));
+    opentelemetry::global::set_tracer_provider(provider);
+    opentelemetry::global::set_text_map_propagator(
+        opentelemetry_sdk::propagation::TraceContextPropagator::new(),
+    );
+    let registry = tracing_subscriber::registry()
+        .with(log_filter)
+        .with(tracing_opentelemetry::layer().with_tracer(tracer));
+    match log_json {
+        true => registry.with(tracing_subscriber::fmt::layer().json()).init(),
+        false => registry.with(tracing_subscriber::fmt::layer()).init(),
+    }
+    Ok(())
+}
+
+// https://github.com/tokio-rs/axum/blob/15917c6dbcb4a48707a20e9cfd021992a279a662/examples/graceful-shutdown/src/main.rs#L55
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("failed to install Ctrl+C handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("failed to install signal handler")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+}
+
+/// Periodically log the rolling average and maximum ingest lag, giving
+/// operators a picture of how far behind real-time the ingester is without
+/// needing external tooling.
+async fn report_ingest_lag(state: Arc<AppState>, interval: Duration) {
+    let mut ticker = tokio::time::interval(interval);
+    loop {
+        ticker.tick().await;
+        if let Some((avg_ms, max_ms)) = state.take_ingest_lag() {
+            tracing::info!(avg_ms, max_ms, "Ingest lag over the last reporting window");
+        }
+        let skipped = state.take_unknown_events();
+        if skipped > 0 {
+            tracing::info!(skipped, "Skipped unknown event types over the last reporting window");
+        }
+        let mut rejections: Vec<_> = state.take_rejections().into_iter().collect();
+        rejections.sort_by_key(|(reason, _)| reason.as_str());
+        for (reason, count) in rejections {
+            tracing::info!(
+                reason = reason.as_str(),
+                count,
+                "Rejected records over the last reporting window"
+            );
+        }
+    }
+}
+
+/// Periodically delete labels whose expiry has passed. The appview already
+/// filters expired labels out of reads, so reaping only exists to keep the
+/// label-join cost on feed queries bounded.
+async fn reap_expired_labels(state: Arc<AppState>, interval: Duration) {
+    let mut ticker = tokio::time::interval(interval);
+    loop {
+        ticker.tick().await;
+        if state.dry_run {
+            tracing::info!("Dry run - would have reaped expired labels");
+            continue;
+        }
+        match query!(
+            "DELETE FROM labels WHERE expires_at IS NOT NULL \
+             AND expires_at < (extract(epoch from now()) * 1000)::BIGINT"
+        )
+        .execute(state.database.executor())
+        .await
+        {
+            Ok(result) if result.rows_affected() > 0 => {
+                tracing::info!("Reaped {} expired labels", result.rows_affected());
+            }
+            Ok(_) => {}
+            Err(err) => tracing::warn!("Failed to reap expired labels: {err:?}"),
+        }
+    }
+}
+
+/// Subscribe the tap to every account already in the database, chunking the
+/// DID list to keep request bodies reasonably sized.
+async fn sync_repos(state: &AppState) -> Result<()> {
+    let dids: Vec<Did> = query!("SELECT did FROM accounts")
+        .fetch_all(state.database.executor())
+        .await?
+        .into_iter()
+        .filter_map(|row| Did::new_owned(row.did).ok())
+        .collect();
+    tracing::info!("Syncing {} known repositories to tap", dids.len());
+    for chunk in dids.chunks(500) {
+        state.tap_client.add_repos(chunk).await?;
+    }
+    Ok(())
+}
+
+/// Subscribe tap to the given repositories and request a full resync. The
+/// replayed records are delivered on the regular event channel and picked up
+/// by a running ingest instance.
+async fn backfill(state: &AppState, dids: &[String]) -> Result<()> {
+    let dids: Vec<Did> = dids
+        .iter()
+        .map(|did| Did::new_owned(did.clone()).map_err(|_| anyhow::anyhow!("invalid DID: {did}")))
+        .collect::<Result<_>>()?;
+    state.tap_client.add_repos(&dids).await?;
+    state.tap_client.resync_repos(&dids).await?;
+    for did in &dids {
+        match state.tap_client.repo_info(did).await {
+            Ok(info) => tracing::info!(
+                "Queued backfill of {did}: {} records at rev {}",
+                info.records,
+                info.rev.as_str()
+            ),
+            Err(err) => {
+                tracing::warn!("Queued backfill of {did}, but fetching repo info failed: {err:?}")
+            }
+        }
+    }
+    tracing::info!("Resync requested for {} repositories", dids.len());
+    Ok(())
+}
+
+/// Feed each line of a file captured with `--record-to` through the regular
+/// event handlers. Lines that fail to parse or whose handler errors are
+/// logged and skipped so one broken event doesn't abort the whole replay.
+async fn replay(state: &Arc<AppState>, file: &Path) -> Result<()> {
+    use std::io::BufRead;
+
+    let reader = std::io::BufReader::new(
+        std::fs::File::open(file)
+            .with_context(|| format!("failed to open replay file {}", file.display()))?,
+    );
+    let (mut replayed, mut skipped) = (0u64, 0u64);
+    for (index, line) in reader.lines().enumerate() {
+        let line = line.context("failed to read replay file")?;
+        if line.is_empty() {
+            continue;
+        }
+        let event = match serde_json::from_str::<floodgate::api::Event>(&line) {
+            Ok(event) => event.into_static(),
             Err(err) => {
-                tracing::error!(
-                    "Unable to connect to tap channel - retrying in {TAP_RECONNECT_INTERVAL:?}: {err:?}"
-                );
-                tokio::time::sleep(TAP_RECONNECT_INTERVAL).await;
+                tracing::warn!("Skipping unparseable event on line {}: {err}", index + 1);
+                skipped += 1;
                 continue;
             }
         };
-        connection
-            .handler(move |data| {
-                let state = state.clone();
-                handle_event(state, data)
-            })
-            .await;
-        tracing::info!(
-            "Tap channel was closed while handling events -  reconnecting automatically in {TAP_RECONNECT_INTERVAL:?}: "
-        );
-        tokio::time::sleep(TAP_RECONNECT_INTERVAL).await;
+        match handle_event(state.clone(), event.data).await {
+            Ok(()) => replayed += 1,
+            Err(err) => {
+                tracing::warn!("Handler failed for event on line {}: {err:?}", index + 1);
+                skipped += 1;
+            }
+        }
     }
+    tracing::info!("Replayed {replayed} events ({skipped} skipped)");
+    Ok(())
 }
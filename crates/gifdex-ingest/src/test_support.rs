@@ -0,0 +1,65 @@
+//! Support code for handler tests: a containerised Postgres from
+//! [`gifdex_testing`] plus an [`AppState`] wired with the same defaults the
+//! binary uses.
+
+use crate::{
+    AppState, IngestLag, OverLimitPolicy,
+    database::{Database, DatabaseConfig},
+};
+use floodgate::client::TapClient;
+use gifdex_lexicons::limits::{
+    BlobLimits, DEFAULT_AVATAR_MIME_TYPES, DEFAULT_MAX_POST_LANGUAGES, DEFAULT_MAX_POST_TAGS,
+    DEFAULT_MAX_TAG_LENGTH, DEFAULT_MEDIA_MIME_TYPES,
+};
+use gifdex_testing::TestPostgres;
+use std::{collections::HashMap, sync::Mutex, time::Duration};
+use url::Url;
+
+/// Spin up a disposable Postgres, apply the workspace migrations to it and
+/// build an [`AppState`] around it with default configuration. The tap
+/// client points at an unroutable address, so only handlers that stay off
+/// the network can run against this state.
+pub async fn test_state() -> (TestPostgres, AppState) {
+    let postgres = gifdex_testing::postgres().await;
+    let database = Database::new(
+        &postgres.url,
+        &DatabaseConfig {
+            max_connections: 5,
+            acquire_timeout: Duration::from_secs(30),
+            idle_timeout: Duration::from_secs(600),
+        },
+    )
+    .await
+    .expect("failed to connect to test database");
+    let tap_client = TapClient::builder(Url::parse("http://tap.invalid").unwrap())
+        .password(None::<String>)
+        .build()
+        .expect("failed to build tap client");
+    let state = AppState {
+        database,
+        tap_client,
+        http_client: reqwest::Client::new(),
+        dead_letter_retries: 5,
+        dead_letter_attempts: Mutex::new(HashMap::new()),
+        strict_validation: false,
+        verify_handles: false,
+        max_post_tags: DEFAULT_MAX_POST_TAGS,
+        max_tag_length: DEFAULT_MAX_TAG_LENGTH,
+        max_post_languages: DEFAULT_MAX_POST_LANGUAGES,
+        over_limit_policy: OverLimitPolicy::Truncate,
+        dry_run: false,
+        media_mime_types: DEFAULT_MEDIA_MIME_TYPES
+            .iter()
+            .map(|mime| mime.to_string())
+            .collect(),
+        avatar_mime_types: DEFAULT_AVATAR_MIME_TYPES
+            .iter()
+            .map(|mime| mime.to_string())
+            .collect(),
+        blob_limits: BlobLimits::default(),
+        ingest_lag: Mutex::new(IngestLag::default()),
+        unknown_events: std::sync::atomic::AtomicU64::new(0),
+        rejections: std::sync::Mutex::new(std::collections::HashMap::new()),
+    };
+    (postgres, state)
+}
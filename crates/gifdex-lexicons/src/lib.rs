@@ -4,6 +4,8 @@
 // Any manual changes will be overwritten on the next regeneration.
 
 pub mod builder_types;
+pub mod limits;
+pub mod signing;
 
 #[cfg(feature = "net_gifdex")]
 pub mod net_gifdex;
\ No newline at end of file
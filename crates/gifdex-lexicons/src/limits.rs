@@ -0,0 +1,53 @@
+// Hand-written - not generated from a Lexicon schema.
+//
+// Blob size limits shared between the ingester (which rejects oversized
+// records) and the CDN (which refuses to proxy oversized blobs). Keeping them
+// in one place stops the two services drifting apart.
+
+/// Maximum accepted size in bytes of a post's media blob unless overridden
+/// by service configuration.
+pub const DEFAULT_MAX_BLOB_SIZE: usize = 10 * 1024 * 1024; // 10MB
+
+/// Maximum accepted size in bytes of a profile avatar blob unless overridden
+/// by service configuration.
+pub const DEFAULT_MAX_AVATAR_SIZE: usize = 3 * 1024 * 1024; // 3MB
+
+/// Blob size limits as configured for a running service. Both the ingester
+/// and the CDN carry one of these in their state so the same limits apply
+/// at ingest time and at serving time.
+#[derive(Debug, Clone, Copy)]
+pub struct BlobLimits {
+    /// Maximum accepted size in bytes of a post's media blob.
+    pub max_blob_size: usize,
+    /// Maximum accepted size in bytes of a profile avatar blob.
+    pub max_avatar_size: usize,
+}
+
+impl Default for BlobLimits {
+    fn default() -> Self {
+        BlobLimits {
+            max_blob_size: DEFAULT_MAX_BLOB_SIZE,
+            max_avatar_size: DEFAULT_MAX_AVATAR_SIZE,
+        }
+    }
+}
+
+/// Maximum number of tags stored on a post unless overridden by service
+/// configuration. Matches the `net.gifdex.feed.post` lexicon bound.
+pub const DEFAULT_MAX_POST_TAGS: usize = 5;
+
+/// Maximum length in characters of a single post tag unless overridden by
+/// service configuration. Matches the `net.gifdex.feed.post` lexicon bound.
+pub const DEFAULT_MAX_TAG_LENGTH: usize = 40;
+
+/// Maximum number of declared languages stored on a post unless overridden
+/// by service configuration. Matches the `net.gifdex.feed.post` lexicon
+/// bound.
+pub const DEFAULT_MAX_POST_LANGUAGES: usize = 3;
+
+/// Media types accepted for post media blobs unless overridden by service
+/// configuration.
+pub const DEFAULT_MEDIA_MIME_TYPES: &[&str] = &["image/gif", "image/webp"];
+/// Media types accepted for avatar blobs unless overridden by service
+/// configuration.
+pub const DEFAULT_AVATAR_MIME_TYPES: &[&str] = &["image/png", "image/jpeg", "image/webp"];
@@ -8,6 +8,7 @@
 pub mod get_profile;
 pub mod get_profiles;
 pub mod profile;
+pub mod search_actors;
 
 /// A full representation of an actor's profile, ideal for profile pages.
 #[jacquard_derive::lexicon]
@@ -33,6 +34,9 @@ pub struct ProfileView<'a> {
     #[serde(skip_serializing_if = "std::option::Option::is_none")]
     #[serde(borrow)]
     pub handle: std::option::Option<jacquard_common::types::string::Handle<'a>>,
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    #[serde(borrow)]
+    pub labels: std::option::Option<Vec<crate::net_gifdex::labeler::LabelView<'a>>>,
     pub post_count: i64,
     #[serde(skip_serializing_if = "std::option::Option::is_none")]
     #[serde(borrow)]
@@ -91,6 +95,7 @@ pub struct ProfileViewBuilder<'a, S: profile_view_state::State> {
         ::core::option::Option<jacquard_common::types::string::Did<'a>>,
         ::core::option::Option<jacquard_common::CowStr<'a>>,
         ::core::option::Option<jacquard_common::types::string::Handle<'a>>,
+        ::core::option::Option<Vec<crate::net_gifdex::labeler::LabelView<'a>>>,
         ::core::option::Option<i64>,
         ::core::option::Option<jacquard_common::CowStr<'a>>,
     ),
@@ -109,7 +114,7 @@ impl<'a> ProfileViewBuilder<'a, profile_view_state::Empty> {
     pub fn new() -> Self {
         ProfileViewBuilder {
             _phantom_state: ::core::marker::PhantomData,
-            __unsafe_private_named: (None, None, None, None, None, None),
+            __unsafe_private_named: (None, None, None, None, None, None, None),
             _phantom: ::core::marker::PhantomData,
         }
     }
@@ -191,6 +196,25 @@ impl<'a, S: profile_view_state::State> ProfileViewBuilder<'a, S> {
     }
 }
 
+impl<'a, S: profile_view_state::State> ProfileViewBuilder<'a, S> {
+    /// Set the `labels` field (optional)
+    pub fn labels(
+        mut self,
+        value: impl Into<Option<Vec<crate::net_gifdex::labeler::LabelView<'a>>>>,
+    ) -> Self {
+        self.__unsafe_private_named.4 = value.into();
+        self
+    }
+    /// Set the `labels` field to an Option value (optional)
+    pub fn maybe_labels(
+        mut self,
+        value: Option<Vec<crate::net_gifdex::labeler::LabelView<'a>>>,
+    ) -> Self {
+        self.__unsafe_private_named.4 = value;
+        self
+    }
+}
+
 impl<'a, S> ProfileViewBuilder<'a, S>
 where
     S: profile_view_state::State,
@@ -201,7 +225,7 @@ where
         mut self,
         value: impl Into<i64>,
     ) -> ProfileViewBuilder<'a, profile_view_state::SetPostCount<S>> {
-        self.__unsafe_private_named.4 = ::core::option::Option::Some(value.into());
+        self.__unsafe_private_named.5 = ::core::option::Option::Some(value.into());
         ProfileViewBuilder {
             _phantom_state: ::core::marker::PhantomData,
             __unsafe_private_named: self.__unsafe_private_named,
@@ -216,12 +240,12 @@ impl<'a, S: profile_view_state::State> ProfileViewBuilder<'a, S> {
         mut self,
         value: impl Into<Option<jacquard_common::CowStr<'a>>>,
     ) -> Self {
-        self.__unsafe_private_named.5 = value.into();
+        self.__unsafe_private_named.6 = value.into();
         self
     }
     /// Set the `pronouns` field to an Option value (optional)
     pub fn maybe_pronouns(mut self, value: Option<jacquard_common::CowStr<'a>>) -> Self {
-        self.__unsafe_private_named.5 = value;
+        self.__unsafe_private_named.6 = value;
         self
     }
 }
@@ -239,8 +263,9 @@ where
             did: self.__unsafe_private_named.1.unwrap(),
             display_name: self.__unsafe_private_named.2,
             handle: self.__unsafe_private_named.3,
-            post_count: self.__unsafe_private_named.4.unwrap(),
-            pronouns: self.__unsafe_private_named.5,
+            labels: self.__unsafe_private_named.4,
+            post_count: self.__unsafe_private_named.5.unwrap(),
+            pronouns: self.__unsafe_private_named.6,
             extra_data: Default::default(),
         }
     }
@@ -257,8 +282,9 @@ where
             did: self.__unsafe_private_named.1.unwrap(),
             display_name: self.__unsafe_private_named.2,
             handle: self.__unsafe_private_named.3,
-            post_count: self.__unsafe_private_named.4.unwrap(),
-            pronouns: self.__unsafe_private_named.5,
+            labels: self.__unsafe_private_named.4,
+            post_count: self.__unsafe_private_named.5.unwrap(),
+            pronouns: self.__unsafe_private_named.6,
             extra_data: Some(extra_data),
         }
     }
@@ -360,6 +386,20 @@ fn lexicon_doc_net_gifdex_actor_defs() -> ::jacquard_lexicon::lexicon::LexiconDo
                                 known_values: None,
                             }),
                         );
+                        map.insert(
+                            ::jacquard_common::smol_str::SmolStr::new_static("labels"),
+                            ::jacquard_lexicon::lexicon::LexObjectProperty::Array(::jacquard_lexicon::lexicon::LexArray {
+                                description: None,
+                                items: ::jacquard_lexicon::lexicon::LexArrayItem::Ref(::jacquard_lexicon::lexicon::LexRef {
+                                    description: None,
+                                    r#ref: ::jacquard_common::CowStr::new_static(
+                                        "net.gifdex.labeler.defs#labelView",
+                                    ),
+                                }),
+                                min_length: None,
+                                max_length: None,
+                            }),
+                        );
                         map.insert(
                             ::jacquard_common::smol_str::SmolStr::new_static(
                                 "postCount",
@@ -17,7 +17,7 @@
 #[serde(rename_all = "camelCase")]
 pub struct GetProfile<'a> {
     #[serde(borrow)]
-    pub actor: jacquard_common::types::string::Did<'a>,
+    pub actor: jacquard_common::types::ident::AtIdentifier<'a>,
 }
 
 pub mod get_profile_state {
@@ -56,7 +56,7 @@ pub mod get_profile_state {
 pub struct GetProfileBuilder<'a, S: get_profile_state::State> {
     _phantom_state: ::core::marker::PhantomData<fn() -> S>,
     __unsafe_private_named: (
-        ::core::option::Option<jacquard_common::types::string::Did<'a>>,
+        ::core::option::Option<jacquard_common::types::ident::AtIdentifier<'a>>,
     ),
     _phantom: ::core::marker::PhantomData<&'a ()>,
 }
@@ -87,7 +87,7 @@ where
     /// Set the `actor` field (required)
     pub fn actor(
         mut self,
-        value: impl Into<jacquard_common::types::string::Did<'a>>,
+        value: impl Into<jacquard_common::types::ident::AtIdentifier<'a>>,
     ) -> GetProfileBuilder<'a, get_profile_state::SetActor<S>> {
         self.__unsafe_private_named.0 = ::core::option::Option::Some(value.into());
         GetProfileBuilder {
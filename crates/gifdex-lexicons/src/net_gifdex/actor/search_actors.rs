@@ -0,0 +1,231 @@
+// @generated by jacquard-lexicon. DO NOT EDIT.
+//
+// Lexicon: net.gifdex.actor.searchActors
+//
+// This file was automatically generated from Lexicon schemas.
+// Any manual changes will be overwritten on the next regeneration.
+
+#[derive(
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    jacquard_derive::IntoStatic
+)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchActors<'a> {
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    #[serde(borrow)]
+    pub cursor: std::option::Option<jacquard_common::CowStr<'a>>,
+    ///(min: 1, max: 100)
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    pub limit: std::option::Option<i64>,
+    #[serde(borrow)]
+    pub q: jacquard_common::CowStr<'a>,
+}
+
+pub mod search_actors_state {
+
+    pub use crate::builder_types::{Set, Unset, IsSet, IsUnset};
+    #[allow(unused)]
+    use ::core::marker::PhantomData;
+    mod sealed {
+        pub trait Sealed {}
+    }
+    /// State trait tracking which required fields have been set
+    pub trait State: sealed::Sealed {
+        type Q;
+    }
+    /// Empty state - all required fields are unset
+    pub struct Empty(());
+    impl sealed::Sealed for Empty {}
+    impl State for Empty {
+        type Q = Unset;
+    }
+    ///State transition - sets the `q` field to Set
+    pub struct SetQ<S: State = Empty>(PhantomData<fn() -> S>);
+    impl<S: State> sealed::Sealed for SetQ<S> {}
+    impl<S: State> State for SetQ<S> {
+        type Q = Set<members::q>;
+    }
+    /// Marker types for field names
+    #[allow(non_camel_case_types)]
+    pub mod members {
+        ///Marker type for the `q` field
+        pub struct q(());
+    }
+}
+
+/// Builder for constructing an instance of this type
+pub struct SearchActorsBuilder<'a, S: search_actors_state::State> {
+    _phantom_state: ::core::marker::PhantomData<fn() -> S>,
+    __unsafe_private_named: (
+        ::core::option::Option<jacquard_common::CowStr<'a>>,
+        ::core::option::Option<i64>,
+        ::core::option::Option<jacquard_common::CowStr<'a>>,
+    ),
+    _phantom: ::core::marker::PhantomData<&'a ()>,
+}
+
+impl<'a> SearchActors<'a> {
+    /// Create a new builder for this type
+    pub fn new() -> SearchActorsBuilder<'a, search_actors_state::Empty> {
+        SearchActorsBuilder::new()
+    }
+}
+
+impl<'a> SearchActorsBuilder<'a, search_actors_state::Empty> {
+    /// Create a new builder with all fields unset
+    pub fn new() -> Self {
+        SearchActorsBuilder {
+            _phantom_state: ::core::marker::PhantomData,
+            __unsafe_private_named: (None, None, None),
+            _phantom: ::core::marker::PhantomData,
+        }
+    }
+}
+
+impl<'a, S: search_actors_state::State> SearchActorsBuilder<'a, S> {
+    /// Set the `cursor` field (optional)
+    pub fn cursor(
+        mut self,
+        value: impl Into<Option<jacquard_common::CowStr<'a>>>,
+    ) -> Self {
+        self.__unsafe_private_named.0 = value.into();
+        self
+    }
+    /// Set the `cursor` field to an Option value (optional)
+    pub fn maybe_cursor(mut self, value: Option<jacquard_common::CowStr<'a>>) -> Self {
+        self.__unsafe_private_named.0 = value;
+        self
+    }
+}
+
+impl<'a, S: search_actors_state::State> SearchActorsBuilder<'a, S> {
+    /// Set the `limit` field (optional)
+    pub fn limit(mut self, value: impl Into<Option<i64>>) -> Self {
+        self.__unsafe_private_named.1 = value.into();
+        self
+    }
+    /// Set the `limit` field to an Option value (optional)
+    pub fn maybe_limit(mut self, value: Option<i64>) -> Self {
+        self.__unsafe_private_named.1 = value;
+        self
+    }
+}
+
+impl<'a, S> SearchActorsBuilder<'a, S>
+where
+    S: search_actors_state::State,
+    S::Q: search_actors_state::IsUnset,
+{
+    /// Set the `q` field (required)
+    pub fn q(
+        mut self,
+        value: impl Into<jacquard_common::CowStr<'a>>,
+    ) -> SearchActorsBuilder<'a, search_actors_state::SetQ<S>> {
+        self.__unsafe_private_named.2 = ::core::option::Option::Some(value.into());
+        SearchActorsBuilder {
+            _phantom_state: ::core::marker::PhantomData,
+            __unsafe_private_named: self.__unsafe_private_named,
+            _phantom: ::core::marker::PhantomData,
+        }
+    }
+}
+
+impl<'a, S> SearchActorsBuilder<'a, S>
+where
+    S: search_actors_state::State,
+    S::Q: search_actors_state::IsSet,
+{
+    /// Build the final struct
+    pub fn build(self) -> SearchActors<'a> {
+        SearchActors {
+            cursor: self.__unsafe_private_named.0,
+            limit: self.__unsafe_private_named.1,
+            q: self.__unsafe_private_named.2.unwrap(),
+        }
+    }
+}
+
+#[jacquard_derive::lexicon]
+#[derive(
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    jacquard_derive::IntoStatic
+)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchActorsOutput<'a> {
+    #[serde(borrow)]
+    pub actors: Vec<crate::net_gifdex::actor::ProfileView<'a>>,
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    #[serde(borrow)]
+    pub cursor: std::option::Option<jacquard_common::CowStr<'a>>,
+}
+
+#[jacquard_derive::open_union]
+#[derive(
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    thiserror::Error,
+    miette::Diagnostic,
+    jacquard_derive::IntoStatic
+)]
+#[serde(tag = "error", content = "message")]
+#[serde(bound(deserialize = "'de: 'a"))]
+pub enum SearchActorsError<'a> {
+    /// The search query is empty or otherwise malformed.
+    #[serde(rename = "InvalidQuery")]
+    InvalidQuery(std::option::Option<jacquard_common::CowStr<'a>>),
+}
+
+impl std::fmt::Display for SearchActorsError<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidQuery(msg) => {
+                write!(f, "InvalidQuery")?;
+                if let Some(msg) = msg {
+                    write!(f, ": {}", msg)?;
+                }
+                Ok(())
+            }
+            Self::Unknown(err) => write!(f, "Unknown error: {:?}", err),
+        }
+    }
+}
+
+/// Response type for
+///net.gifdex.actor.searchActors
+pub struct SearchActorsResponse;
+impl jacquard_common::xrpc::XrpcResp for SearchActorsResponse {
+    const NSID: &'static str = "net.gifdex.actor.searchActors";
+    const ENCODING: &'static str = "application/json";
+    type Output<'de> = SearchActorsOutput<'de>;
+    type Err<'de> = SearchActorsError<'de>;
+}
+
+impl<'a> jacquard_common::xrpc::XrpcRequest for SearchActors<'a> {
+    const NSID: &'static str = "net.gifdex.actor.searchActors";
+    const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
+    type Response = SearchActorsResponse;
+}
+
+/// Endpoint type for
+///net.gifdex.actor.searchActors
+pub struct SearchActorsRequest;
+impl jacquard_common::xrpc::XrpcEndpoint for SearchActorsRequest {
+    const PATH: &'static str = "/xrpc/net.gifdex.actor.searchActors";
+    const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
+    type Request<'de> = SearchActors<'de>;
+    type Response = SearchActorsResponse;
+}
@@ -6,10 +6,17 @@
 // Any manual changes will be overwritten on the next regeneration.
 
 pub mod favourite;
+pub mod get_actor_favourites;
+pub mod get_feed_skeleton;
 pub mod get_post;
+pub mod get_post_favourites;
+pub mod get_posts;
 pub mod get_posts_by_actor;
 pub mod get_posts_by_query;
+pub mod get_posts_by_tag;
+pub mod get_trending;
 pub mod post;
+pub mod search_posts;
 
 /// Feed-optimized view of a post with all metadata needed for display in timelines.
 #[jacquard_derive::lexicon]
@@ -33,6 +40,9 @@ pub struct PostFeedView<'a> {
     pub indexed_at: jacquard_common::types::string::Datetime,
     #[serde(skip_serializing_if = "std::option::Option::is_none")]
     #[serde(borrow)]
+    pub labels: std::option::Option<Vec<crate::net_gifdex::labeler::LabelView<'a>>>,
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    #[serde(borrow)]
     pub languages: std::option::Option<Vec<jacquard_common::CowStr<'a>>>,
     #[serde(borrow)]
     pub media: crate::net_gifdex::feed::PostViewMedia<'a>,
@@ -214,6 +224,7 @@ pub struct PostFeedViewBuilder<'a, S: post_feed_view_state::State> {
         ::core::option::Option<jacquard_common::types::string::Datetime>,
         ::core::option::Option<i64>,
         ::core::option::Option<jacquard_common::types::string::Datetime>,
+        ::core::option::Option<Vec<crate::net_gifdex::labeler::LabelView<'a>>>,
         ::core::option::Option<Vec<jacquard_common::CowStr<'a>>>,
         ::core::option::Option<crate::net_gifdex::feed::PostViewMedia<'a>>,
         ::core::option::Option<Vec<jacquard_common::CowStr<'a>>>,
@@ -248,6 +259,7 @@ impl<'a> PostFeedViewBuilder<'a, post_feed_view_state::Empty> {
                 None,
                 None,
                 None,
+                None,
             ),
             _phantom: ::core::marker::PhantomData,
         }
@@ -349,13 +361,32 @@ where
     }
 }
 
+impl<'a, S: post_feed_view_state::State> PostFeedViewBuilder<'a, S> {
+    /// Set the `labels` field (optional)
+    pub fn labels(
+        mut self,
+        value: impl Into<Option<Vec<crate::net_gifdex::labeler::LabelView<'a>>>>,
+    ) -> Self {
+        self.__unsafe_private_named.5 = value.into();
+        self
+    }
+    /// Set the `labels` field to an Option value (optional)
+    pub fn maybe_labels(
+        mut self,
+        value: Option<Vec<crate::net_gifdex::labeler::LabelView<'a>>>,
+    ) -> Self {
+        self.__unsafe_private_named.5 = value;
+        self
+    }
+}
+
 impl<'a, S: post_feed_view_state::State> PostFeedViewBuilder<'a, S> {
     /// Set the `languages` field (optional)
     pub fn languages(
         mut self,
         value: impl Into<Option<Vec<jacquard_common::CowStr<'a>>>>,
     ) -> Self {
-        self.__unsafe_private_named.5 = value.into();
+        self.__unsafe_private_named.6 = value.into();
         self
     }
     /// Set the `languages` field to an Option value (optional)
@@ -363,7 +394,7 @@ impl<'a, S: post_feed_view_state::State> PostFeedViewBuilder<'a, S> {
         mut self,
         value: Option<Vec<jacquard_common::CowStr<'a>>>,
     ) -> Self {
-        self.__unsafe_private_named.5 = value;
+        self.__unsafe_private_named.6 = value;
         self
     }
 }
@@ -378,7 +409,7 @@ where
         mut self,
         value: impl Into<crate::net_gifdex::feed::PostViewMedia<'a>>,
     ) -> PostFeedViewBuilder<'a, post_feed_view_state::SetMedia<S>> {
-        self.__unsafe_private_named.6 = ::core::option::Option::Some(value.into());
+        self.__unsafe_private_named.7 = ::core::option::Option::Some(value.into());
         PostFeedViewBuilder {
             _phantom_state: ::core::marker::PhantomData,
             __unsafe_private_named: self.__unsafe_private_named,
@@ -393,7 +424,7 @@ impl<'a, S: post_feed_view_state::State> PostFeedViewBuilder<'a, S> {
         mut self,
         value: impl Into<Option<Vec<jacquard_common::CowStr<'a>>>>,
     ) -> Self {
-        self.__unsafe_private_named.7 = value.into();
+        self.__unsafe_private_named.8 = value.into();
         self
     }
     /// Set the `tags` field to an Option value (optional)
@@ -401,7 +432,7 @@ impl<'a, S: post_feed_view_state::State> PostFeedViewBuilder<'a, S> {
         mut self,
         value: Option<Vec<jacquard_common::CowStr<'a>>>,
     ) -> Self {
-        self.__unsafe_private_named.7 = value;
+        self.__unsafe_private_named.8 = value;
         self
     }
 }
@@ -416,7 +447,7 @@ where
         mut self,
         value: impl Into<jacquard_common::CowStr<'a>>,
     ) -> PostFeedViewBuilder<'a, post_feed_view_state::SetTitle<S>> {
-        self.__unsafe_private_named.8 = ::core::option::Option::Some(value.into());
+        self.__unsafe_private_named.9 = ::core::option::Option::Some(value.into());
         PostFeedViewBuilder {
             _phantom_state: ::core::marker::PhantomData,
             __unsafe_private_named: self.__unsafe_private_named,
@@ -435,7 +466,7 @@ where
         mut self,
         value: impl Into<jacquard_common::types::string::AtUri<'a>>,
     ) -> PostFeedViewBuilder<'a, post_feed_view_state::SetUri<S>> {
-        self.__unsafe_private_named.9 = ::core::option::Option::Some(value.into());
+        self.__unsafe_private_named.10 = ::core::option::Option::Some(value.into());
         PostFeedViewBuilder {
             _phantom_state: ::core::marker::PhantomData,
             __unsafe_private_named: self.__unsafe_private_named,
@@ -454,7 +485,7 @@ where
         mut self,
         value: impl Into<crate::net_gifdex::feed::ViewerState<'a>>,
     ) -> PostFeedViewBuilder<'a, post_feed_view_state::SetViewer<S>> {
-        self.__unsafe_private_named.10 = ::core::option::Option::Some(value.into());
+        self.__unsafe_private_named.11 = ::core::option::Option::Some(value.into());
         PostFeedViewBuilder {
             _phantom_state: ::core::marker::PhantomData,
             __unsafe_private_named: self.__unsafe_private_named,
@@ -483,12 +514,13 @@ where
             edited_at: self.__unsafe_private_named.2,
             favourite_count: self.__unsafe_private_named.3.unwrap(),
             indexed_at: self.__unsafe_private_named.4.unwrap(),
-            languages: self.__unsafe_private_named.5,
-            media: self.__unsafe_private_named.6.unwrap(),
-            tags: self.__unsafe_private_named.7,
-            title: self.__unsafe_private_named.8.unwrap(),
-            uri: self.__unsafe_private_named.9.unwrap(),
-            viewer: self.__unsafe_private_named.10.unwrap(),
+            labels: self.__unsafe_private_named.5,
+            languages: self.__unsafe_private_named.6,
+            media: self.__unsafe_private_named.7.unwrap(),
+            tags: self.__unsafe_private_named.8,
+            title: self.__unsafe_private_named.9.unwrap(),
+            uri: self.__unsafe_private_named.10.unwrap(),
+            viewer: self.__unsafe_private_named.11.unwrap(),
             extra_data: Default::default(),
         }
     }
@@ -506,12 +538,13 @@ where
             edited_at: self.__unsafe_private_named.2,
             favourite_count: self.__unsafe_private_named.3.unwrap(),
             indexed_at: self.__unsafe_private_named.4.unwrap(),
-            languages: self.__unsafe_private_named.5,
-            media: self.__unsafe_private_named.6.unwrap(),
-            tags: self.__unsafe_private_named.7,
-            title: self.__unsafe_private_named.8.unwrap(),
-            uri: self.__unsafe_private_named.9.unwrap(),
-            viewer: self.__unsafe_private_named.10.unwrap(),
+            labels: self.__unsafe_private_named.5,
+            languages: self.__unsafe_private_named.6,
+            media: self.__unsafe_private_named.7.unwrap(),
+            tags: self.__unsafe_private_named.8,
+            title: self.__unsafe_private_named.9.unwrap(),
+            uri: self.__unsafe_private_named.10.unwrap(),
+            viewer: self.__unsafe_private_named.11.unwrap(),
             extra_data: Some(extra_data),
         }
     }
@@ -628,6 +661,20 @@ fn lexicon_doc_net_gifdex_feed_defs() -> ::jacquard_lexicon::lexicon::LexiconDoc
                                 known_values: None,
                             }),
                         );
+                        map.insert(
+                            ::jacquard_common::smol_str::SmolStr::new_static("labels"),
+                            ::jacquard_lexicon::lexicon::LexObjectProperty::Array(::jacquard_lexicon::lexicon::LexArray {
+                                description: None,
+                                items: ::jacquard_lexicon::lexicon::LexArrayItem::Ref(::jacquard_lexicon::lexicon::LexRef {
+                                    description: None,
+                                    r#ref: ::jacquard_common::CowStr::new_static(
+                                        "net.gifdex.labeler.defs#labelView",
+                                    ),
+                                }),
+                                min_length: None,
+                                max_length: None,
+                            }),
+                        );
                         map.insert(
                             ::jacquard_common::smol_str::SmolStr::new_static(
                                 "languages",
@@ -952,6 +999,21 @@ fn lexicon_doc_net_gifdex_feed_defs() -> ::jacquard_lexicon::lexicon::LexiconDoc
                                 known_values: None,
                             }),
                         );
+                        map.insert(
+                            ::jacquard_common::smol_str::SmolStr::new_static("blurhash"),
+                            ::jacquard_lexicon::lexicon::LexObjectProperty::String(::jacquard_lexicon::lexicon::LexString {
+                                description: None,
+                                format: None,
+                                default: None,
+                                min_length: None,
+                                max_length: None,
+                                min_graphemes: None,
+                                max_graphemes: None,
+                                r#enum: None,
+                                r#const: None,
+                                known_values: None,
+                            }),
+                        );
                         map.insert(
                             ::jacquard_common::smol_str::SmolStr::new_static(
                                 "dimensions",
@@ -1663,6 +1725,9 @@ pub struct PostViewMedia<'a> {
     #[serde(skip_serializing_if = "std::option::Option::is_none")]
     #[serde(borrow)]
     pub alt: std::option::Option<jacquard_common::CowStr<'a>>,
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    #[serde(borrow)]
+    pub blurhash: std::option::Option<jacquard_common::CowStr<'a>>,
     #[serde(borrow)]
     pub dimensions: crate::net_gifdex::feed::PostViewMediaDimensions<'a>,
     #[serde(borrow)]
@@ -1751,6 +1816,7 @@ pub mod post_view_media_state {
 pub struct PostViewMediaBuilder<'a, S: post_view_media_state::State> {
     _phantom_state: ::core::marker::PhantomData<fn() -> S>,
     __unsafe_private_named: (
+        ::core::option::Option<jacquard_common::CowStr<'a>>,
         ::core::option::Option<jacquard_common::CowStr<'a>>,
         ::core::option::Option<crate::net_gifdex::feed::PostViewMediaDimensions<'a>>,
         ::core::option::Option<jacquard_common::types::string::Uri<'a>>,
@@ -1772,7 +1838,7 @@ impl<'a> PostViewMediaBuilder<'a, post_view_media_state::Empty> {
     pub fn new() -> Self {
         PostViewMediaBuilder {
             _phantom_state: ::core::marker::PhantomData,
-            __unsafe_private_named: (None, None, None, None, None),
+            __unsafe_private_named: (None, None, None, None, None, None),
             _phantom: ::core::marker::PhantomData,
         }
     }
@@ -1791,6 +1857,19 @@ impl<'a, S: post_view_media_state::State> PostViewMediaBuilder<'a, S> {
     }
 }
 
+impl<'a, S: post_view_media_state::State> PostViewMediaBuilder<'a, S> {
+    /// Set the `blurhash` field (optional)
+    pub fn blurhash(mut self, value: impl Into<Option<jacquard_common::CowStr<'a>>>) -> Self {
+        self.__unsafe_private_named.1 = value.into();
+        self
+    }
+    /// Set the `blurhash` field to an Option value (optional)
+    pub fn maybe_blurhash(mut self, value: Option<jacquard_common::CowStr<'a>>) -> Self {
+        self.__unsafe_private_named.1 = value;
+        self
+    }
+}
+
 impl<'a, S> PostViewMediaBuilder<'a, S>
 where
     S: post_view_media_state::State,
@@ -1801,7 +1880,7 @@ where
         mut self,
         value: impl Into<crate::net_gifdex::feed::PostViewMediaDimensions<'a>>,
     ) -> PostViewMediaBuilder<'a, post_view_media_state::SetDimensions<S>> {
-        self.__unsafe_private_named.1 = ::core::option::Option::Some(value.into());
+        self.__unsafe_private_named.2 = ::core::option::Option::Some(value.into());
         PostViewMediaBuilder {
             _phantom_state: ::core::marker::PhantomData,
             __unsafe_private_named: self.__unsafe_private_named,
@@ -1820,7 +1899,7 @@ where
         mut self,
         value: impl Into<jacquard_common::types::string::Uri<'a>>,
     ) -> PostViewMediaBuilder<'a, post_view_media_state::SetFullsizeUrl<S>> {
-        self.__unsafe_private_named.2 = ::core::option::Option::Some(value.into());
+        self.__unsafe_private_named.3 = ::core::option::Option::Some(value.into());
         PostViewMediaBuilder {
             _phantom_state: ::core::marker::PhantomData,
             __unsafe_private_named: self.__unsafe_private_named,
@@ -1839,7 +1918,7 @@ where
         mut self,
         value: impl Into<jacquard_common::CowStr<'a>>,
     ) -> PostViewMediaBuilder<'a, post_view_media_state::SetMimeType<S>> {
-        self.__unsafe_private_named.3 = ::core::option::Option::Some(value.into());
+        self.__unsafe_private_named.4 = ::core::option::Option::Some(value.into());
         PostViewMediaBuilder {
             _phantom_state: ::core::marker::PhantomData,
             __unsafe_private_named: self.__unsafe_private_named,
@@ -1858,7 +1937,7 @@ where
         mut self,
         value: impl Into<jacquard_common::types::string::Uri<'a>>,
     ) -> PostViewMediaBuilder<'a, post_view_media_state::SetThumbnailUrl<S>> {
-        self.__unsafe_private_named.4 = ::core::option::Option::Some(value.into());
+        self.__unsafe_private_named.5 = ::core::option::Option::Some(value.into());
         PostViewMediaBuilder {
             _phantom_state: ::core::marker::PhantomData,
             __unsafe_private_named: self.__unsafe_private_named,
@@ -1879,10 +1958,11 @@ where
     pub fn build(self) -> PostViewMedia<'a> {
         PostViewMedia {
             alt: self.__unsafe_private_named.0,
-            dimensions: self.__unsafe_private_named.1.unwrap(),
-            fullsize_url: self.__unsafe_private_named.2.unwrap(),
-            mime_type: self.__unsafe_private_named.3.unwrap(),
-            thumbnail_url: self.__unsafe_private_named.4.unwrap(),
+            blurhash: self.__unsafe_private_named.1,
+            dimensions: self.__unsafe_private_named.2.unwrap(),
+            fullsize_url: self.__unsafe_private_named.3.unwrap(),
+            mime_type: self.__unsafe_private_named.4.unwrap(),
+            thumbnail_url: self.__unsafe_private_named.5.unwrap(),
             extra_data: Default::default(),
         }
     }
@@ -1896,10 +1976,11 @@ where
     ) -> PostViewMedia<'a> {
         PostViewMedia {
             alt: self.__unsafe_private_named.0,
-            dimensions: self.__unsafe_private_named.1.unwrap(),
-            fullsize_url: self.__unsafe_private_named.2.unwrap(),
-            mime_type: self.__unsafe_private_named.3.unwrap(),
-            thumbnail_url: self.__unsafe_private_named.4.unwrap(),
+            blurhash: self.__unsafe_private_named.1,
+            dimensions: self.__unsafe_private_named.2.unwrap(),
+            fullsize_url: self.__unsafe_private_named.3.unwrap(),
+            mime_type: self.__unsafe_private_named.4.unwrap(),
+            thumbnail_url: self.__unsafe_private_named.5.unwrap(),
             extra_data: Some(extra_data),
         }
     }
@@ -2105,6 +2186,128 @@ impl<'a> ::jacquard_lexicon::schema::LexiconSchema for PostViewMediaDimensions<'
     }
 }
 
+/// A post reference in a feed skeleton, to be hydrated by the client.
+#[jacquard_derive::lexicon]
+#[derive(
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    jacquard_derive::IntoStatic
+)]
+#[serde(rename_all = "camelCase")]
+pub struct SkeletonFeedPost<'a> {
+    #[serde(borrow)]
+    pub post: jacquard_common::types::string::AtUri<'a>,
+}
+
+pub mod skeleton_feed_post_state {
+
+    pub use crate::builder_types::{Set, Unset, IsSet, IsUnset};
+    #[allow(unused)]
+    use ::core::marker::PhantomData;
+    mod sealed {
+        pub trait Sealed {}
+    }
+    /// State trait tracking which required fields have been set
+    pub trait State: sealed::Sealed {
+        type Post;
+    }
+    /// Empty state - all required fields are unset
+    pub struct Empty(());
+    impl sealed::Sealed for Empty {}
+    impl State for Empty {
+        type Post = Unset;
+    }
+    ///State transition - sets the `post` field to Set
+    pub struct SetPost<S: State = Empty>(PhantomData<fn() -> S>);
+    impl<S: State> sealed::Sealed for SetPost<S> {}
+    impl<S: State> State for SetPost<S> {
+        type Post = Set<members::post>;
+    }
+    /// Marker types for field names
+    #[allow(non_camel_case_types)]
+    pub mod members {
+        ///Marker type for the `post` field
+        pub struct post(());
+    }
+}
+
+/// Builder for constructing an instance of this type
+pub struct SkeletonFeedPostBuilder<'a, S: skeleton_feed_post_state::State> {
+    _phantom_state: ::core::marker::PhantomData<fn() -> S>,
+    __unsafe_private_named: (
+        ::core::option::Option<jacquard_common::types::string::AtUri<'a>>,
+    ),
+    _phantom: ::core::marker::PhantomData<&'a ()>,
+}
+
+impl<'a> SkeletonFeedPost<'a> {
+    /// Create a new builder for this type
+    pub fn new() -> SkeletonFeedPostBuilder<'a, skeleton_feed_post_state::Empty> {
+        SkeletonFeedPostBuilder::new()
+    }
+}
+
+impl<'a> SkeletonFeedPostBuilder<'a, skeleton_feed_post_state::Empty> {
+    /// Create a new builder with all fields unset
+    pub fn new() -> Self {
+        SkeletonFeedPostBuilder {
+            _phantom_state: ::core::marker::PhantomData,
+            __unsafe_private_named: (None,),
+            _phantom: ::core::marker::PhantomData,
+        }
+    }
+}
+
+impl<'a, S> SkeletonFeedPostBuilder<'a, S>
+where
+    S: skeleton_feed_post_state::State,
+    S::Post: skeleton_feed_post_state::IsUnset,
+{
+    /// Set the `post` field (required)
+    pub fn post(
+        mut self,
+        value: impl Into<jacquard_common::types::string::AtUri<'a>>,
+    ) -> SkeletonFeedPostBuilder<'a, skeleton_feed_post_state::SetPost<S>> {
+        self.__unsafe_private_named.0 = ::core::option::Option::Some(value.into());
+        SkeletonFeedPostBuilder {
+            _phantom_state: ::core::marker::PhantomData,
+            __unsafe_private_named: self.__unsafe_private_named,
+            _phantom: ::core::marker::PhantomData,
+        }
+    }
+}
+
+impl<'a, S> SkeletonFeedPostBuilder<'a, S>
+where
+    S: skeleton_feed_post_state::State,
+    S::Post: skeleton_feed_post_state::IsSet,
+{
+    /// Build the final struct
+    pub fn build(self) -> SkeletonFeedPost<'a> {
+        SkeletonFeedPost {
+            post: self.__unsafe_private_named.0.unwrap(),
+            extra_data: Default::default(),
+        }
+    }
+    /// Build the final struct with custom extra_data
+    pub fn build_with_data(
+        self,
+        extra_data: std::collections::BTreeMap<
+            jacquard_common::smol_str::SmolStr,
+            jacquard_common::types::value::Data<'a>,
+        >,
+    ) -> SkeletonFeedPost<'a> {
+        SkeletonFeedPost {
+            post: self.__unsafe_private_named.0.unwrap(),
+            extra_data: Some(extra_data),
+        }
+    }
+}
+
 /// Metadata about the requesting account's relationship with the subject content. Only has meaningful content for authed requests.
 #[jacquard_derive::lexicon]
 #[derive(
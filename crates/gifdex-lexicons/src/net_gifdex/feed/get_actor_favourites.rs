@@ -0,0 +1,231 @@
+// @generated by jacquard-lexicon. DO NOT EDIT.
+//
+// Lexicon: net.gifdex.feed.getActorFavourites
+//
+// This file was automatically generated from Lexicon schemas.
+// Any manual changes will be overwritten on the next regeneration.
+
+#[derive(
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    jacquard_derive::IntoStatic
+)]
+#[serde(rename_all = "camelCase")]
+pub struct GetActorFavourites<'a> {
+    #[serde(borrow)]
+    pub actor: jacquard_common::types::string::Did<'a>,
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    #[serde(borrow)]
+    pub cursor: std::option::Option<jacquard_common::CowStr<'a>>,
+    ///(min: 1, max: 100)
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    pub limit: std::option::Option<i64>,
+}
+
+pub mod get_actor_favourites_state {
+
+    pub use crate::builder_types::{Set, Unset, IsSet, IsUnset};
+    #[allow(unused)]
+    use ::core::marker::PhantomData;
+    mod sealed {
+        pub trait Sealed {}
+    }
+    /// State trait tracking which required fields have been set
+    pub trait State: sealed::Sealed {
+        type Actor;
+    }
+    /// Empty state - all required fields are unset
+    pub struct Empty(());
+    impl sealed::Sealed for Empty {}
+    impl State for Empty {
+        type Actor = Unset;
+    }
+    ///State transition - sets the `actor` field to Set
+    pub struct SetActor<S: State = Empty>(PhantomData<fn() -> S>);
+    impl<S: State> sealed::Sealed for SetActor<S> {}
+    impl<S: State> State for SetActor<S> {
+        type Actor = Set<members::actor>;
+    }
+    /// Marker types for field names
+    #[allow(non_camel_case_types)]
+    pub mod members {
+        ///Marker type for the `actor` field
+        pub struct actor(());
+    }
+}
+
+/// Builder for constructing an instance of this type
+pub struct GetActorFavouritesBuilder<'a, S: get_actor_favourites_state::State> {
+    _phantom_state: ::core::marker::PhantomData<fn() -> S>,
+    __unsafe_private_named: (
+        ::core::option::Option<jacquard_common::types::string::Did<'a>>,
+        ::core::option::Option<jacquard_common::CowStr<'a>>,
+        ::core::option::Option<i64>,
+    ),
+    _phantom: ::core::marker::PhantomData<&'a ()>,
+}
+
+impl<'a> GetActorFavourites<'a> {
+    /// Create a new builder for this type
+    pub fn new() -> GetActorFavouritesBuilder<'a, get_actor_favourites_state::Empty> {
+        GetActorFavouritesBuilder::new()
+    }
+}
+
+impl<'a> GetActorFavouritesBuilder<'a, get_actor_favourites_state::Empty> {
+    /// Create a new builder with all fields unset
+    pub fn new() -> Self {
+        GetActorFavouritesBuilder {
+            _phantom_state: ::core::marker::PhantomData,
+            __unsafe_private_named: (None, None, None),
+            _phantom: ::core::marker::PhantomData,
+        }
+    }
+}
+
+impl<'a, S> GetActorFavouritesBuilder<'a, S>
+where
+    S: get_actor_favourites_state::State,
+    S::Actor: get_actor_favourites_state::IsUnset,
+{
+    /// Set the `actor` field (required)
+    pub fn actor(
+        mut self,
+        value: impl Into<jacquard_common::types::string::Did<'a>>,
+    ) -> GetActorFavouritesBuilder<'a, get_actor_favourites_state::SetActor<S>> {
+        self.__unsafe_private_named.0 = ::core::option::Option::Some(value.into());
+        GetActorFavouritesBuilder {
+            _phantom_state: ::core::marker::PhantomData,
+            __unsafe_private_named: self.__unsafe_private_named,
+            _phantom: ::core::marker::PhantomData,
+        }
+    }
+}
+
+impl<'a, S: get_actor_favourites_state::State> GetActorFavouritesBuilder<'a, S> {
+    /// Set the `cursor` field (optional)
+    pub fn cursor(
+        mut self,
+        value: impl Into<Option<jacquard_common::CowStr<'a>>>,
+    ) -> Self {
+        self.__unsafe_private_named.1 = value.into();
+        self
+    }
+    /// Set the `cursor` field to an Option value (optional)
+    pub fn maybe_cursor(mut self, value: Option<jacquard_common::CowStr<'a>>) -> Self {
+        self.__unsafe_private_named.1 = value;
+        self
+    }
+}
+
+impl<'a, S: get_actor_favourites_state::State> GetActorFavouritesBuilder<'a, S> {
+    /// Set the `limit` field (optional)
+    pub fn limit(mut self, value: impl Into<Option<i64>>) -> Self {
+        self.__unsafe_private_named.2 = value.into();
+        self
+    }
+    /// Set the `limit` field to an Option value (optional)
+    pub fn maybe_limit(mut self, value: Option<i64>) -> Self {
+        self.__unsafe_private_named.2 = value;
+        self
+    }
+}
+
+impl<'a, S> GetActorFavouritesBuilder<'a, S>
+where
+    S: get_actor_favourites_state::State,
+    S::Actor: get_actor_favourites_state::IsSet,
+{
+    /// Build the final struct
+    pub fn build(self) -> GetActorFavourites<'a> {
+        GetActorFavourites {
+            actor: self.__unsafe_private_named.0.unwrap(),
+            cursor: self.__unsafe_private_named.1,
+            limit: self.__unsafe_private_named.2,
+        }
+    }
+}
+
+#[jacquard_derive::lexicon]
+#[derive(
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    jacquard_derive::IntoStatic
+)]
+#[serde(rename_all = "camelCase")]
+pub struct GetActorFavouritesOutput<'a> {
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    #[serde(borrow)]
+    pub cursor: std::option::Option<jacquard_common::CowStr<'a>>,
+    #[serde(borrow)]
+    pub feed: Vec<crate::net_gifdex::feed::PostFeedView<'a>>,
+}
+
+#[jacquard_derive::open_union]
+#[derive(
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    thiserror::Error,
+    miette::Diagnostic,
+    jacquard_derive::IntoStatic
+)]
+#[serde(tag = "error", content = "message")]
+#[serde(bound(deserialize = "'de: 'a"))]
+pub enum GetActorFavouritesError<'a> {
+    /// The requested actor does not exist or has not been indexed yet.
+    #[serde(rename = "ActorNotFound")]
+    ActorNotFound(std::option::Option<jacquard_common::CowStr<'a>>),
+}
+
+impl std::fmt::Display for GetActorFavouritesError<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ActorNotFound(msg) => {
+                write!(f, "ActorNotFound")?;
+                if let Some(msg) = msg {
+                    write!(f, ": {}", msg)?;
+                }
+                Ok(())
+            }
+            Self::Unknown(err) => write!(f, "Unknown error: {:?}", err),
+        }
+    }
+}
+
+/// Response type for
+///net.gifdex.feed.getActorFavourites
+pub struct GetActorFavouritesResponse;
+impl jacquard_common::xrpc::XrpcResp for GetActorFavouritesResponse {
+    const NSID: &'static str = "net.gifdex.feed.getActorFavourites";
+    const ENCODING: &'static str = "application/json";
+    type Output<'de> = GetActorFavouritesOutput<'de>;
+    type Err<'de> = GetActorFavouritesError<'de>;
+}
+
+impl<'a> jacquard_common::xrpc::XrpcRequest for GetActorFavourites<'a> {
+    const NSID: &'static str = "net.gifdex.feed.getActorFavourites";
+    const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
+    type Response = GetActorFavouritesResponse;
+}
+
+/// Endpoint type for
+///net.gifdex.feed.getActorFavourites
+pub struct GetActorFavouritesRequest;
+impl jacquard_common::xrpc::XrpcEndpoint for GetActorFavouritesRequest {
+    const PATH: &'static str = "/xrpc/net.gifdex.feed.getActorFavourites";
+    const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
+    type Request<'de> = GetActorFavourites<'de>;
+    type Response = GetActorFavouritesResponse;
+}
\ No newline at end of file
@@ -0,0 +1,232 @@
+// @generated by jacquard-lexicon. DO NOT EDIT.
+//
+// Lexicon: net.gifdex.feed.getFeedSkeleton
+//
+// This file was automatically generated from Lexicon schemas.
+// Any manual changes will be overwritten on the next regeneration.
+
+#[derive(
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    jacquard_derive::IntoStatic
+)]
+#[serde(rename_all = "camelCase")]
+pub struct GetFeedSkeleton<'a> {
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    #[serde(borrow)]
+    pub cursor: std::option::Option<jacquard_common::CowStr<'a>>,
+    ///Feed algorithm to return the skeleton of. Either `trending` or `latest`.
+    #[serde(borrow)]
+    pub feed: jacquard_common::CowStr<'a>,
+    ///(min: 1, max: 100)
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    pub limit: std::option::Option<i64>,
+}
+
+pub mod get_feed_skeleton_state {
+
+    pub use crate::builder_types::{Set, Unset, IsSet, IsUnset};
+    #[allow(unused)]
+    use ::core::marker::PhantomData;
+    mod sealed {
+        pub trait Sealed {}
+    }
+    /// State trait tracking which required fields have been set
+    pub trait State: sealed::Sealed {
+        type Feed;
+    }
+    /// Empty state - all required fields are unset
+    pub struct Empty(());
+    impl sealed::Sealed for Empty {}
+    impl State for Empty {
+        type Feed = Unset;
+    }
+    ///State transition - sets the `feed` field to Set
+    pub struct SetFeed<S: State = Empty>(PhantomData<fn() -> S>);
+    impl<S: State> sealed::Sealed for SetFeed<S> {}
+    impl<S: State> State for SetFeed<S> {
+        type Feed = Set<members::feed>;
+    }
+    /// Marker types for field names
+    #[allow(non_camel_case_types)]
+    pub mod members {
+        ///Marker type for the `feed` field
+        pub struct feed(());
+    }
+}
+
+/// Builder for constructing an instance of this type
+pub struct GetFeedSkeletonBuilder<'a, S: get_feed_skeleton_state::State> {
+    _phantom_state: ::core::marker::PhantomData<fn() -> S>,
+    __unsafe_private_named: (
+        ::core::option::Option<jacquard_common::CowStr<'a>>,
+        ::core::option::Option<jacquard_common::CowStr<'a>>,
+        ::core::option::Option<i64>,
+    ),
+    _phantom: ::core::marker::PhantomData<&'a ()>,
+}
+
+impl<'a> GetFeedSkeleton<'a> {
+    /// Create a new builder for this type
+    pub fn new() -> GetFeedSkeletonBuilder<'a, get_feed_skeleton_state::Empty> {
+        GetFeedSkeletonBuilder::new()
+    }
+}
+
+impl<'a> GetFeedSkeletonBuilder<'a, get_feed_skeleton_state::Empty> {
+    /// Create a new builder with all fields unset
+    pub fn new() -> Self {
+        GetFeedSkeletonBuilder {
+            _phantom_state: ::core::marker::PhantomData,
+            __unsafe_private_named: (None, None, None),
+            _phantom: ::core::marker::PhantomData,
+        }
+    }
+}
+
+impl<'a, S: get_feed_skeleton_state::State> GetFeedSkeletonBuilder<'a, S> {
+    /// Set the `cursor` field (optional)
+    pub fn cursor(
+        mut self,
+        value: impl Into<Option<jacquard_common::CowStr<'a>>>,
+    ) -> Self {
+        self.__unsafe_private_named.0 = value.into();
+        self
+    }
+    /// Set the `cursor` field to an Option value (optional)
+    pub fn maybe_cursor(mut self, value: Option<jacquard_common::CowStr<'a>>) -> Self {
+        self.__unsafe_private_named.0 = value;
+        self
+    }
+}
+
+impl<'a, S> GetFeedSkeletonBuilder<'a, S>
+where
+    S: get_feed_skeleton_state::State,
+    S::Feed: get_feed_skeleton_state::IsUnset,
+{
+    /// Set the `feed` field (required)
+    pub fn feed(
+        mut self,
+        value: impl Into<jacquard_common::CowStr<'a>>,
+    ) -> GetFeedSkeletonBuilder<'a, get_feed_skeleton_state::SetFeed<S>> {
+        self.__unsafe_private_named.1 = ::core::option::Option::Some(value.into());
+        GetFeedSkeletonBuilder {
+            _phantom_state: ::core::marker::PhantomData,
+            __unsafe_private_named: self.__unsafe_private_named,
+            _phantom: ::core::marker::PhantomData,
+        }
+    }
+}
+
+impl<'a, S: get_feed_skeleton_state::State> GetFeedSkeletonBuilder<'a, S> {
+    /// Set the `limit` field (optional)
+    pub fn limit(mut self, value: impl Into<Option<i64>>) -> Self {
+        self.__unsafe_private_named.2 = value.into();
+        self
+    }
+    /// Set the `limit` field to an Option value (optional)
+    pub fn maybe_limit(mut self, value: Option<i64>) -> Self {
+        self.__unsafe_private_named.2 = value;
+        self
+    }
+}
+
+impl<'a, S> GetFeedSkeletonBuilder<'a, S>
+where
+    S: get_feed_skeleton_state::State,
+    S::Feed: get_feed_skeleton_state::IsSet,
+{
+    /// Build the final struct
+    pub fn build(self) -> GetFeedSkeleton<'a> {
+        GetFeedSkeleton {
+            cursor: self.__unsafe_private_named.0,
+            feed: self.__unsafe_private_named.1.unwrap(),
+            limit: self.__unsafe_private_named.2,
+        }
+    }
+}
+
+#[jacquard_derive::lexicon]
+#[derive(
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    jacquard_derive::IntoStatic
+)]
+#[serde(rename_all = "camelCase")]
+pub struct GetFeedSkeletonOutput<'a> {
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    #[serde(borrow)]
+    pub cursor: std::option::Option<jacquard_common::CowStr<'a>>,
+    #[serde(borrow)]
+    pub feed: Vec<crate::net_gifdex::feed::SkeletonFeedPost<'a>>,
+}
+
+#[jacquard_derive::open_union]
+#[derive(
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    thiserror::Error,
+    miette::Diagnostic,
+    jacquard_derive::IntoStatic
+)]
+#[serde(tag = "error", content = "message")]
+#[serde(bound(deserialize = "'de: 'a"))]
+pub enum GetFeedSkeletonError<'a> {
+    /// The requested feed algorithm is not recognised.
+    #[serde(rename = "UnknownFeed")]
+    UnknownFeed(std::option::Option<jacquard_common::CowStr<'a>>),
+}
+
+impl std::fmt::Display for GetFeedSkeletonError<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownFeed(msg) => {
+                write!(f, "UnknownFeed")?;
+                if let Some(msg) = msg {
+                    write!(f, ": {}", msg)?;
+                }
+                Ok(())
+            }
+            Self::Unknown(err) => write!(f, "Unknown error: {:?}", err),
+        }
+    }
+}
+
+/// Response type for
+///net.gifdex.feed.getFeedSkeleton
+pub struct GetFeedSkeletonResponse;
+impl jacquard_common::xrpc::XrpcResp for GetFeedSkeletonResponse {
+    const NSID: &'static str = "net.gifdex.feed.getFeedSkeleton";
+    const ENCODING: &'static str = "application/json";
+    type Output<'de> = GetFeedSkeletonOutput<'de>;
+    type Err<'de> = GetFeedSkeletonError<'de>;
+}
+
+impl<'a> jacquard_common::xrpc::XrpcRequest for GetFeedSkeleton<'a> {
+    const NSID: &'static str = "net.gifdex.feed.getFeedSkeleton";
+    const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
+    type Response = GetFeedSkeletonResponse;
+}
+
+/// Endpoint type for
+///net.gifdex.feed.getFeedSkeleton
+pub struct GetFeedSkeletonRequest;
+impl jacquard_common::xrpc::XrpcEndpoint for GetFeedSkeletonRequest {
+    const PATH: &'static str = "/xrpc/net.gifdex.feed.getFeedSkeleton";
+    const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
+    type Request<'de> = GetFeedSkeleton<'de>;
+    type Response = GetFeedSkeletonResponse;
+}
@@ -0,0 +1,267 @@
+// @generated by jacquard-lexicon. DO NOT EDIT.
+//
+// Lexicon: net.gifdex.feed.getPostFavourites
+//
+// This file was automatically generated from Lexicon schemas.
+// Any manual changes will be overwritten on the next regeneration.
+
+#[derive(
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    jacquard_derive::IntoStatic
+)]
+#[serde(rename_all = "camelCase")]
+pub struct GetPostFavourites<'a> {
+    #[serde(borrow)]
+    pub actor: jacquard_common::types::string::Did<'a>,
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    #[serde(borrow)]
+    pub cursor: std::option::Option<jacquard_common::CowStr<'a>>,
+    ///(min: 1, max: 100)
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    pub limit: std::option::Option<i64>,
+    #[serde(borrow)]
+    pub rkey: jacquard_common::CowStr<'a>,
+}
+
+pub mod get_post_favourites_state {
+
+    pub use crate::builder_types::{Set, Unset, IsSet, IsUnset};
+    #[allow(unused)]
+    use ::core::marker::PhantomData;
+    mod sealed {
+        pub trait Sealed {}
+    }
+    /// State trait tracking which required fields have been set
+    pub trait State: sealed::Sealed {
+        type Actor;
+        type Rkey;
+    }
+    /// Empty state - all required fields are unset
+    pub struct Empty(());
+    impl sealed::Sealed for Empty {}
+    impl State for Empty {
+        type Actor = Unset;
+        type Rkey = Unset;
+    }
+    ///State transition - sets the `actor` field to Set
+    pub struct SetActor<S: State = Empty>(PhantomData<fn() -> S>);
+    impl<S: State> sealed::Sealed for SetActor<S> {}
+    impl<S: State> State for SetActor<S> {
+        type Actor = Set<members::actor>;
+        type Rkey = S::Rkey;
+    }
+    ///State transition - sets the `rkey` field to Set
+    pub struct SetRkey<S: State = Empty>(PhantomData<fn() -> S>);
+    impl<S: State> sealed::Sealed for SetRkey<S> {}
+    impl<S: State> State for SetRkey<S> {
+        type Actor = S::Actor;
+        type Rkey = Set<members::rkey>;
+    }
+    /// Marker types for field names
+    #[allow(non_camel_case_types)]
+    pub mod members {
+        ///Marker type for the `actor` field
+        pub struct actor(());
+        ///Marker type for the `rkey` field
+        pub struct rkey(());
+    }
+}
+
+/// Builder for constructing an instance of this type
+pub struct GetPostFavouritesBuilder<'a, S: get_post_favourites_state::State> {
+    _phantom_state: ::core::marker::PhantomData<fn() -> S>,
+    __unsafe_private_named: (
+        ::core::option::Option<jacquard_common::types::string::Did<'a>>,
+        ::core::option::Option<jacquard_common::CowStr<'a>>,
+        ::core::option::Option<i64>,
+        ::core::option::Option<jacquard_common::CowStr<'a>>,
+    ),
+    _phantom: ::core::marker::PhantomData<&'a ()>,
+}
+
+impl<'a> GetPostFavourites<'a> {
+    /// Create a new builder for this type
+    pub fn new() -> GetPostFavouritesBuilder<'a, get_post_favourites_state::Empty> {
+        GetPostFavouritesBuilder::new()
+    }
+}
+
+impl<'a> GetPostFavouritesBuilder<'a, get_post_favourites_state::Empty> {
+    /// Create a new builder with all fields unset
+    pub fn new() -> Self {
+        GetPostFavouritesBuilder {
+            _phantom_state: ::core::marker::PhantomData,
+            __unsafe_private_named: (None, None, None, None),
+            _phantom: ::core::marker::PhantomData,
+        }
+    }
+}
+
+impl<'a, S> GetPostFavouritesBuilder<'a, S>
+where
+    S: get_post_favourites_state::State,
+    S::Actor: get_post_favourites_state::IsUnset,
+{
+    /// Set the `actor` field (required)
+    pub fn actor(
+        mut self,
+        value: impl Into<jacquard_common::types::string::Did<'a>>,
+    ) -> GetPostFavouritesBuilder<'a, get_post_favourites_state::SetActor<S>> {
+        self.__unsafe_private_named.0 = ::core::option::Option::Some(value.into());
+        GetPostFavouritesBuilder {
+            _phantom_state: ::core::marker::PhantomData,
+            __unsafe_private_named: self.__unsafe_private_named,
+            _phantom: ::core::marker::PhantomData,
+        }
+    }
+}
+
+impl<'a, S: get_post_favourites_state::State> GetPostFavouritesBuilder<'a, S> {
+    /// Set the `cursor` field (optional)
+    pub fn cursor(
+        mut self,
+        value: impl Into<Option<jacquard_common::CowStr<'a>>>,
+    ) -> Self {
+        self.__unsafe_private_named.1 = value.into();
+        self
+    }
+    /// Set the `cursor` field to an Option value (optional)
+    pub fn maybe_cursor(mut self, value: Option<jacquard_common::CowStr<'a>>) -> Self {
+        self.__unsafe_private_named.1 = value;
+        self
+    }
+}
+
+impl<'a, S: get_post_favourites_state::State> GetPostFavouritesBuilder<'a, S> {
+    /// Set the `limit` field (optional)
+    pub fn limit(mut self, value: impl Into<Option<i64>>) -> Self {
+        self.__unsafe_private_named.2 = value.into();
+        self
+    }
+    /// Set the `limit` field to an Option value (optional)
+    pub fn maybe_limit(mut self, value: Option<i64>) -> Self {
+        self.__unsafe_private_named.2 = value;
+        self
+    }
+}
+
+impl<'a, S> GetPostFavouritesBuilder<'a, S>
+where
+    S: get_post_favourites_state::State,
+    S::Rkey: get_post_favourites_state::IsUnset,
+{
+    /// Set the `rkey` field (required)
+    pub fn rkey(
+        mut self,
+        value: impl Into<jacquard_common::CowStr<'a>>,
+    ) -> GetPostFavouritesBuilder<'a, get_post_favourites_state::SetRkey<S>> {
+        self.__unsafe_private_named.3 = ::core::option::Option::Some(value.into());
+        GetPostFavouritesBuilder {
+            _phantom_state: ::core::marker::PhantomData,
+            __unsafe_private_named: self.__unsafe_private_named,
+            _phantom: ::core::marker::PhantomData,
+        }
+    }
+}
+
+impl<'a, S> GetPostFavouritesBuilder<'a, S>
+where
+    S: get_post_favourites_state::State,
+    S::Actor: get_post_favourites_state::IsSet,
+    S::Rkey: get_post_favourites_state::IsSet,
+{
+    /// Build the final struct
+    pub fn build(self) -> GetPostFavourites<'a> {
+        GetPostFavourites {
+            actor: self.__unsafe_private_named.0.unwrap(),
+            cursor: self.__unsafe_private_named.1,
+            limit: self.__unsafe_private_named.2,
+            rkey: self.__unsafe_private_named.3.unwrap(),
+        }
+    }
+}
+
+#[jacquard_derive::lexicon]
+#[derive(
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    jacquard_derive::IntoStatic
+)]
+#[serde(rename_all = "camelCase")]
+pub struct GetPostFavouritesOutput<'a> {
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    #[serde(borrow)]
+    pub cursor: std::option::Option<jacquard_common::CowStr<'a>>,
+    #[serde(borrow)]
+    pub favourited_by: Vec<crate::net_gifdex::actor::ProfileViewBasic<'a>>,
+}
+
+#[jacquard_derive::open_union]
+#[derive(
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    thiserror::Error,
+    miette::Diagnostic,
+    jacquard_derive::IntoStatic
+)]
+#[serde(tag = "error", content = "message")]
+#[serde(bound(deserialize = "'de: 'a"))]
+pub enum GetPostFavouritesError<'a> {
+    /// The requested post does not exist or has not been indexed yet.
+    #[serde(rename = "PostNotFound")]
+    PostNotFound(std::option::Option<jacquard_common::CowStr<'a>>),
+}
+
+impl std::fmt::Display for GetPostFavouritesError<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::PostNotFound(msg) => {
+                write!(f, "PostNotFound")?;
+                if let Some(msg) = msg {
+                    write!(f, ": {}", msg)?;
+                }
+                Ok(())
+            }
+            Self::Unknown(err) => write!(f, "Unknown error: {:?}", err),
+        }
+    }
+}
+
+/// Response type for
+///net.gifdex.feed.getPostFavourites
+pub struct GetPostFavouritesResponse;
+impl jacquard_common::xrpc::XrpcResp for GetPostFavouritesResponse {
+    const NSID: &'static str = "net.gifdex.feed.getPostFavourites";
+    const ENCODING: &'static str = "application/json";
+    type Output<'de> = GetPostFavouritesOutput<'de>;
+    type Err<'de> = GetPostFavouritesError<'de>;
+}
+
+impl<'a> jacquard_common::xrpc::XrpcRequest for GetPostFavourites<'a> {
+    const NSID: &'static str = "net.gifdex.feed.getPostFavourites";
+    const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
+    type Response = GetPostFavouritesResponse;
+}
+
+/// Endpoint type for
+///net.gifdex.feed.getPostFavourites
+pub struct GetPostFavouritesRequest;
+impl jacquard_common::xrpc::XrpcEndpoint for GetPostFavouritesRequest {
+    const PATH: &'static str = "/xrpc/net.gifdex.feed.getPostFavourites";
+    const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
+    type Request<'de> = GetPostFavourites<'de>;
+    type Response = GetPostFavouritesResponse;
+}
@@ -0,0 +1,154 @@
+// @generated by jacquard-lexicon. DO NOT EDIT.
+//
+// Lexicon: net.gifdex.feed.getPosts
+//
+// This file was automatically generated from Lexicon schemas.
+// Any manual changes will be overwritten on the next regeneration.
+
+#[derive(
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    jacquard_derive::IntoStatic
+)]
+#[serde(rename_all = "camelCase")]
+pub struct GetPosts<'a> {
+    #[serde(borrow)]
+    pub uris: Vec<jacquard_common::types::string::AtUri<'a>>,
+}
+
+pub mod get_posts_state {
+
+    pub use crate::builder_types::{Set, Unset, IsSet, IsUnset};
+    #[allow(unused)]
+    use ::core::marker::PhantomData;
+    mod sealed {
+        pub trait Sealed {}
+    }
+    /// State trait tracking which required fields have been set
+    pub trait State: sealed::Sealed {
+        type Uris;
+    }
+    /// Empty state - all required fields are unset
+    pub struct Empty(());
+    impl sealed::Sealed for Empty {}
+    impl State for Empty {
+        type Uris = Unset;
+    }
+    ///State transition - sets the `uris` field to Set
+    pub struct SetUris<S: State = Empty>(PhantomData<fn() -> S>);
+    impl<S: State> sealed::Sealed for SetUris<S> {}
+    impl<S: State> State for SetUris<S> {
+        type Uris = Set<members::uris>;
+    }
+    /// Marker types for field names
+    #[allow(non_camel_case_types)]
+    pub mod members {
+        ///Marker type for the `uris` field
+        pub struct uris(());
+    }
+}
+
+/// Builder for constructing an instance of this type
+pub struct GetPostsBuilder<'a, S: get_posts_state::State> {
+    _phantom_state: ::core::marker::PhantomData<fn() -> S>,
+    __unsafe_private_named: (
+        ::core::option::Option<Vec<jacquard_common::types::string::AtUri<'a>>>,
+    ),
+    _phantom: ::core::marker::PhantomData<&'a ()>,
+}
+
+impl<'a> GetPosts<'a> {
+    /// Create a new builder for this type
+    pub fn new() -> GetPostsBuilder<'a, get_posts_state::Empty> {
+        GetPostsBuilder::new()
+    }
+}
+
+impl<'a> GetPostsBuilder<'a, get_posts_state::Empty> {
+    /// Create a new builder with all fields unset
+    pub fn new() -> Self {
+        GetPostsBuilder {
+            _phantom_state: ::core::marker::PhantomData,
+            __unsafe_private_named: (None,),
+            _phantom: ::core::marker::PhantomData,
+        }
+    }
+}
+
+impl<'a, S> GetPostsBuilder<'a, S>
+where
+    S: get_posts_state::State,
+    S::Uris: get_posts_state::IsUnset,
+{
+    /// Set the `uris` field (required)
+    pub fn uris(
+        mut self,
+        value: impl Into<Vec<jacquard_common::types::string::AtUri<'a>>>,
+    ) -> GetPostsBuilder<'a, get_posts_state::SetUris<S>> {
+        self.__unsafe_private_named.0 = ::core::option::Option::Some(value.into());
+        GetPostsBuilder {
+            _phantom_state: ::core::marker::PhantomData,
+            __unsafe_private_named: self.__unsafe_private_named,
+            _phantom: ::core::marker::PhantomData,
+        }
+    }
+}
+
+impl<'a, S> GetPostsBuilder<'a, S>
+where
+    S: get_posts_state::State,
+    S::Uris: get_posts_state::IsSet,
+{
+    /// Build the final struct
+    pub fn build(self) -> GetPosts<'a> {
+        GetPosts {
+            uris: self.__unsafe_private_named.0.unwrap(),
+        }
+    }
+}
+
+#[jacquard_derive::lexicon]
+#[derive(
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    jacquard_derive::IntoStatic
+)]
+#[serde(rename_all = "camelCase")]
+pub struct GetPostsOutput<'a> {
+    #[serde(borrow)]
+    pub posts: Vec<crate::net_gifdex::feed::PostFeedView<'a>>,
+}
+
+/// Response type for
+///net.gifdex.feed.getPosts
+pub struct GetPostsResponse;
+impl jacquard_common::xrpc::XrpcResp for GetPostsResponse {
+    const NSID: &'static str = "net.gifdex.feed.getPosts";
+    const ENCODING: &'static str = "application/json";
+    type Output<'de> = GetPostsOutput<'de>;
+    type Err<'de> = jacquard_common::xrpc::GenericError<'de>;
+}
+
+impl<'a> jacquard_common::xrpc::XrpcRequest for GetPosts<'a> {
+    const NSID: &'static str = "net.gifdex.feed.getPosts";
+    const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
+    type Response = GetPostsResponse;
+}
+
+/// Endpoint type for
+///net.gifdex.feed.getPosts
+pub struct GetPostsRequest;
+impl jacquard_common::xrpc::XrpcEndpoint for GetPostsRequest {
+    const PATH: &'static str = "/xrpc/net.gifdex.feed.getPosts";
+    const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
+    type Request<'de> = GetPosts<'de>;
+    type Response = GetPostsResponse;
+}
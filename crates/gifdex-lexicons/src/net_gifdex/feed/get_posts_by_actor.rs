@@ -17,12 +17,16 @@
 #[serde(rename_all = "camelCase")]
 pub struct GetPostsByActor<'a> {
     #[serde(borrow)]
-    pub actor: jacquard_common::types::string::Did<'a>,
+    pub actor: jacquard_common::types::ident::AtIdentifier<'a>,
     #[serde(skip_serializing_if = "std::option::Option::is_none")]
-    pub cursor: std::option::Option<i64>,
+    #[serde(borrow)]
+    pub cursor: std::option::Option<jacquard_common::CowStr<'a>>,
     ///(min: 1, max: 100)
     #[serde(skip_serializing_if = "std::option::Option::is_none")]
     pub limit: std::option::Option<i64>,
+    ///Return results in ascending (oldest-first) order of creation.
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    pub reverse: std::option::Option<bool>,
 }
 
 pub mod get_posts_by_actor_state {
@@ -61,9 +65,10 @@ pub mod get_posts_by_actor_state {
 pub struct GetPostsByActorBuilder<'a, S: get_posts_by_actor_state::State> {
     _phantom_state: ::core::marker::PhantomData<fn() -> S>,
     __unsafe_private_named: (
-        ::core::option::Option<jacquard_common::types::string::Did<'a>>,
-        ::core::option::Option<i64>,
+        ::core::option::Option<jacquard_common::types::ident::AtIdentifier<'a>>,
+        ::core::option::Option<jacquard_common::CowStr<'a>>,
         ::core::option::Option<i64>,
+        ::core::option::Option<bool>,
     ),
     _phantom: ::core::marker::PhantomData<&'a ()>,
 }
@@ -80,7 +85,7 @@ impl<'a> GetPostsByActorBuilder<'a, get_posts_by_actor_state::Empty> {
     pub fn new() -> Self {
         GetPostsByActorBuilder {
             _phantom_state: ::core::marker::PhantomData,
-            __unsafe_private_named: (None, None, None),
+            __unsafe_private_named: (None, None, None, None),
             _phantom: ::core::marker::PhantomData,
         }
     }
@@ -94,7 +99,7 @@ where
     /// Set the `actor` field (required)
     pub fn actor(
         mut self,
-        value: impl Into<jacquard_common::types::string::Did<'a>>,
+        value: impl Into<jacquard_common::types::ident::AtIdentifier<'a>>,
     ) -> GetPostsByActorBuilder<'a, get_posts_by_actor_state::SetActor<S>> {
         self.__unsafe_private_named.0 = ::core::option::Option::Some(value.into());
         GetPostsByActorBuilder {
@@ -107,12 +112,15 @@ where
 
 impl<'a, S: get_posts_by_actor_state::State> GetPostsByActorBuilder<'a, S> {
     /// Set the `cursor` field (optional)
-    pub fn cursor(mut self, value: impl Into<Option<i64>>) -> Self {
+    pub fn cursor(
+        mut self,
+        value: impl Into<Option<jacquard_common::CowStr<'a>>>,
+    ) -> Self {
         self.__unsafe_private_named.1 = value.into();
         self
     }
     /// Set the `cursor` field to an Option value (optional)
-    pub fn maybe_cursor(mut self, value: Option<i64>) -> Self {
+    pub fn maybe_cursor(mut self, value: Option<jacquard_common::CowStr<'a>>) -> Self {
         self.__unsafe_private_named.1 = value;
         self
     }
@@ -131,6 +139,19 @@ impl<'a, S: get_posts_by_actor_state::State> GetPostsByActorBuilder<'a, S> {
     }
 }
 
+impl<'a, S: get_posts_by_actor_state::State> GetPostsByActorBuilder<'a, S> {
+    /// Set the `reverse` field (optional)
+    pub fn reverse(mut self, value: impl Into<Option<bool>>) -> Self {
+        self.__unsafe_private_named.3 = value.into();
+        self
+    }
+    /// Set the `reverse` field to an Option value (optional)
+    pub fn maybe_reverse(mut self, value: Option<bool>) -> Self {
+        self.__unsafe_private_named.3 = value;
+        self
+    }
+}
+
 impl<'a, S> GetPostsByActorBuilder<'a, S>
 where
     S: get_posts_by_actor_state::State,
@@ -142,6 +163,7 @@ where
             actor: self.__unsafe_private_named.0.unwrap(),
             cursor: self.__unsafe_private_named.1,
             limit: self.__unsafe_private_named.2,
+            reverse: self.__unsafe_private_named.3,
         }
     }
 }
@@ -159,7 +181,8 @@ where
 #[serde(rename_all = "camelCase")]
 pub struct GetPostsByActorOutput<'a> {
     #[serde(skip_serializing_if = "std::option::Option::is_none")]
-    pub cursor: std::option::Option<i64>,
+    #[serde(borrow)]
+    pub cursor: std::option::Option<jacquard_common::CowStr<'a>>,
     #[serde(borrow)]
     pub feed: Vec<crate::net_gifdex::feed::PostFeedView<'a>>,
 }
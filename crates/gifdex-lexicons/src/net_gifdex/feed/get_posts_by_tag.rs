@@ -0,0 +1,238 @@
+// @generated by jacquard-lexicon. DO NOT EDIT.
+//
+// Lexicon: net.gifdex.feed.getPostsByTag
+//
+// This file was automatically generated from Lexicon schemas.
+// Any manual changes will be overwritten on the next regeneration.
+
+#[derive(
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    jacquard_derive::IntoStatic
+)]
+#[serde(rename_all = "camelCase")]
+pub struct GetPostsByTag<'a> {
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    #[serde(borrow)]
+    pub cursor: std::option::Option<jacquard_common::CowStr<'a>>,
+    ///Also include posts that declare no languages when filtering by language.
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    pub include_unspecified_languages: std::option::Option<bool>,
+    ///If specified, only posts tagged with at least one of these languages are returned.
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    pub languages: std::option::Option<Vec<jacquard_common::types::string::Language>>,
+    ///(min: 1, max: 100)
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    pub limit: std::option::Option<i64>,
+    #[serde(borrow)]
+    pub tag: jacquard_common::CowStr<'a>,
+}
+
+pub mod get_posts_by_tag_state {
+
+    pub use crate::builder_types::{Set, Unset, IsSet, IsUnset};
+    #[allow(unused)]
+    use ::core::marker::PhantomData;
+    mod sealed {
+        pub trait Sealed {}
+    }
+    /// State trait tracking which required fields have been set
+    pub trait State: sealed::Sealed {
+        type Tag;
+    }
+    /// Empty state - all required fields are unset
+    pub struct Empty(());
+    impl sealed::Sealed for Empty {}
+    impl State for Empty {
+        type Tag = Unset;
+    }
+    ///State transition - sets the `tag` field to Set
+    pub struct SetTag<S: State = Empty>(PhantomData<fn() -> S>);
+    impl<S: State> sealed::Sealed for SetTag<S> {}
+    impl<S: State> State for SetTag<S> {
+        type Tag = Set<members::tag>;
+    }
+    /// Marker types for field names
+    #[allow(non_camel_case_types)]
+    pub mod members {
+        ///Marker type for the `tag` field
+        pub struct tag(());
+    }
+}
+
+/// Builder for constructing an instance of this type
+pub struct GetPostsByTagBuilder<'a, S: get_posts_by_tag_state::State> {
+    _phantom_state: ::core::marker::PhantomData<fn() -> S>,
+    __unsafe_private_named: (
+        ::core::option::Option<jacquard_common::CowStr<'a>>,
+        ::core::option::Option<bool>,
+        ::core::option::Option<Vec<jacquard_common::types::string::Language>>,
+        ::core::option::Option<i64>,
+        ::core::option::Option<jacquard_common::CowStr<'a>>,
+    ),
+    _phantom: ::core::marker::PhantomData<&'a ()>,
+}
+
+impl<'a> GetPostsByTag<'a> {
+    /// Create a new builder for this type
+    pub fn new() -> GetPostsByTagBuilder<'a, get_posts_by_tag_state::Empty> {
+        GetPostsByTagBuilder::new()
+    }
+}
+
+impl<'a> GetPostsByTagBuilder<'a, get_posts_by_tag_state::Empty> {
+    /// Create a new builder with all fields unset
+    pub fn new() -> Self {
+        GetPostsByTagBuilder {
+            _phantom_state: ::core::marker::PhantomData,
+            __unsafe_private_named: (None, None, None, None, None),
+            _phantom: ::core::marker::PhantomData,
+        }
+    }
+}
+
+impl<'a, S: get_posts_by_tag_state::State> GetPostsByTagBuilder<'a, S> {
+    /// Set the `cursor` field (optional)
+    pub fn cursor(
+        mut self,
+        value: impl Into<Option<jacquard_common::CowStr<'a>>>,
+    ) -> Self {
+        self.__unsafe_private_named.0 = value.into();
+        self
+    }
+    /// Set the `cursor` field to an Option value (optional)
+    pub fn maybe_cursor(mut self, value: Option<jacquard_common::CowStr<'a>>) -> Self {
+        self.__unsafe_private_named.0 = value;
+        self
+    }
+}
+
+impl<'a, S: get_posts_by_tag_state::State> GetPostsByTagBuilder<'a, S> {
+    /// Set the `limit` field (optional)
+    pub fn limit(mut self, value: impl Into<Option<i64>>) -> Self {
+        self.__unsafe_private_named.3 = value.into();
+        self
+    }
+    /// Set the `limit` field to an Option value (optional)
+    pub fn maybe_limit(mut self, value: Option<i64>) -> Self {
+        self.__unsafe_private_named.3 = value;
+        self
+    }
+}
+impl<'a, S: get_posts_by_tag_state::State> GetPostsByTagBuilder<'a, S> {
+    /// Set the `include_unspecified_languages` field (optional)
+    pub fn include_unspecified_languages(mut self, value: impl Into<Option<bool>>) -> Self {
+        self.__unsafe_private_named.1 = value.into();
+        self
+    }
+    /// Set the `include_unspecified_languages` field to an Option value (optional)
+    pub fn maybe_include_unspecified_languages(mut self, value: Option<bool>) -> Self {
+        self.__unsafe_private_named.1 = value;
+        self
+    }
+}
+
+impl<'a, S: get_posts_by_tag_state::State> GetPostsByTagBuilder<'a, S> {
+    /// Set the `languages` field (optional)
+    pub fn languages(
+        mut self,
+        value: impl Into<Option<Vec<jacquard_common::types::string::Language>>>,
+    ) -> Self {
+        self.__unsafe_private_named.2 = value.into();
+        self
+    }
+    /// Set the `languages` field to an Option value (optional)
+    pub fn maybe_languages(
+        mut self,
+        value: Option<Vec<jacquard_common::types::string::Language>>,
+    ) -> Self {
+        self.__unsafe_private_named.2 = value;
+        self
+    }
+}
+
+
+impl<'a, S> GetPostsByTagBuilder<'a, S>
+where
+    S: get_posts_by_tag_state::State,
+    S::Tag: get_posts_by_tag_state::IsUnset,
+{
+    /// Set the `tag` field (required)
+    pub fn tag(
+        mut self,
+        value: impl Into<jacquard_common::CowStr<'a>>,
+    ) -> GetPostsByTagBuilder<'a, get_posts_by_tag_state::SetTag<S>> {
+        self.__unsafe_private_named.4 = ::core::option::Option::Some(value.into());
+        GetPostsByTagBuilder {
+            _phantom_state: ::core::marker::PhantomData,
+            __unsafe_private_named: self.__unsafe_private_named,
+            _phantom: ::core::marker::PhantomData,
+        }
+    }
+}
+
+impl<'a, S> GetPostsByTagBuilder<'a, S>
+where
+    S: get_posts_by_tag_state::State,
+    S::Tag: get_posts_by_tag_state::IsSet,
+{
+    /// Build the final struct
+    pub fn build(self) -> GetPostsByTag<'a> {
+        GetPostsByTag {
+            cursor: self.__unsafe_private_named.0,
+            include_unspecified_languages: self.__unsafe_private_named.1,
+            languages: self.__unsafe_private_named.2,
+            limit: self.__unsafe_private_named.3,
+            tag: self.__unsafe_private_named.4.unwrap(),
+        }
+    }
+}
+
+#[jacquard_derive::lexicon]
+#[derive(
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    jacquard_derive::IntoStatic
+)]
+#[serde(rename_all = "camelCase")]
+pub struct GetPostsByTagOutput<'a> {
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    #[serde(borrow)]
+    pub cursor: std::option::Option<jacquard_common::CowStr<'a>>,
+    #[serde(borrow)]
+    pub feed: Vec<crate::net_gifdex::feed::PostFeedView<'a>>,
+}
+
+/// Response type for
+///net.gifdex.feed.getPostsByTag
+pub struct GetPostsByTagResponse;
+impl jacquard_common::xrpc::XrpcResp for GetPostsByTagResponse {
+    const NSID: &'static str = "net.gifdex.feed.getPostsByTag";
+    const ENCODING: &'static str = "application/json";
+    type Output<'de> = GetPostsByTagOutput<'de>;
+    type Err<'de> = jacquard_common::xrpc::GenericError<'de>;
+}
+
+impl<'a> jacquard_common::xrpc::XrpcRequest for GetPostsByTag<'a> {
+    const NSID: &'static str = "net.gifdex.feed.getPostsByTag";
+    const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
+    type Response = GetPostsByTagResponse;
+}
+
+/// Endpoint type for
+///net.gifdex.feed.getPostsByTag
+pub struct GetPostsByTagRequest;
+impl jacquard_common::xrpc::XrpcEndpoint for GetPostsByTagRequest {
+    const PATH: &'static str = "/xrpc/net.gifdex.feed.getPostsByTag";
+    const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
+    type Request<'de> = GetPostsByTag<'de>;
+    type Response = GetPostsByTagResponse;
+}
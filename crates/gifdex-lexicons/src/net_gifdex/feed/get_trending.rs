@@ -0,0 +1,216 @@
+// @generated by jacquard-lexicon. DO NOT EDIT.
+//
+// Lexicon: net.gifdex.feed.getTrending
+//
+// This file was automatically generated from Lexicon schemas.
+// Any manual changes will be overwritten on the next regeneration.
+
+#[derive(
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    jacquard_derive::IntoStatic
+)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTrending<'a> {
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    #[serde(borrow)]
+    pub cursor: std::option::Option<jacquard_common::CowStr<'a>>,
+    ///Also include posts that declare no languages when filtering by language.
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    pub include_unspecified_languages: std::option::Option<bool>,
+    ///If specified, only posts tagged with at least one of these languages are returned.
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    pub languages: std::option::Option<Vec<jacquard_common::types::string::Language>>,
+    ///(min: 1, max: 100)
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    pub limit: std::option::Option<i64>,
+    ///Number of hours of favourites to rank over. (min: 1, max: 168)
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    pub window: std::option::Option<i64>,
+}
+
+pub mod get_trending_state {
+
+    pub use crate::builder_types::{Set, Unset, IsSet, IsUnset};
+    #[allow(unused)]
+    use ::core::marker::PhantomData;
+    mod sealed {
+        pub trait Sealed {}
+    }
+    /// State trait tracking which required fields have been set
+    pub trait State: sealed::Sealed {}
+    /// Empty state - all required fields are unset
+    pub struct Empty(());
+    impl sealed::Sealed for Empty {}
+    impl State for Empty {}
+}
+
+/// Builder for constructing an instance of this type
+pub struct GetTrendingBuilder<'a, S: get_trending_state::State> {
+    _phantom_state: ::core::marker::PhantomData<fn() -> S>,
+    __unsafe_private_named: (
+        ::core::option::Option<jacquard_common::CowStr<'a>>,
+        ::core::option::Option<bool>,
+        ::core::option::Option<Vec<jacquard_common::types::string::Language>>,
+        ::core::option::Option<i64>,
+        ::core::option::Option<i64>,
+    ),
+    _phantom: ::core::marker::PhantomData<&'a ()>,
+}
+
+impl<'a> GetTrending<'a> {
+    /// Create a new builder for this type
+    pub fn new() -> GetTrendingBuilder<'a, get_trending_state::Empty> {
+        GetTrendingBuilder::new()
+    }
+}
+
+impl<'a> GetTrendingBuilder<'a, get_trending_state::Empty> {
+    /// Create a new builder with all fields unset
+    pub fn new() -> Self {
+        GetTrendingBuilder {
+            _phantom_state: ::core::marker::PhantomData,
+            __unsafe_private_named: (None, None, None, None, None),
+            _phantom: ::core::marker::PhantomData,
+        }
+    }
+}
+
+impl<'a, S: get_trending_state::State> GetTrendingBuilder<'a, S> {
+    /// Set the `cursor` field (optional)
+    pub fn cursor(
+        mut self,
+        value: impl Into<Option<jacquard_common::CowStr<'a>>>,
+    ) -> Self {
+        self.__unsafe_private_named.0 = value.into();
+        self
+    }
+    /// Set the `cursor` field to an Option value (optional)
+    pub fn maybe_cursor(mut self, value: Option<jacquard_common::CowStr<'a>>) -> Self {
+        self.__unsafe_private_named.0 = value;
+        self
+    }
+}
+
+impl<'a, S: get_trending_state::State> GetTrendingBuilder<'a, S> {
+    /// Set the `limit` field (optional)
+    pub fn limit(mut self, value: impl Into<Option<i64>>) -> Self {
+        self.__unsafe_private_named.3 = value.into();
+        self
+    }
+    /// Set the `limit` field to an Option value (optional)
+    pub fn maybe_limit(mut self, value: Option<i64>) -> Self {
+        self.__unsafe_private_named.3 = value;
+        self
+    }
+}
+impl<'a, S: get_trending_state::State> GetTrendingBuilder<'a, S> {
+    /// Set the `include_unspecified_languages` field (optional)
+    pub fn include_unspecified_languages(mut self, value: impl Into<Option<bool>>) -> Self {
+        self.__unsafe_private_named.1 = value.into();
+        self
+    }
+    /// Set the `include_unspecified_languages` field to an Option value (optional)
+    pub fn maybe_include_unspecified_languages(mut self, value: Option<bool>) -> Self {
+        self.__unsafe_private_named.1 = value;
+        self
+    }
+}
+
+impl<'a, S: get_trending_state::State> GetTrendingBuilder<'a, S> {
+    /// Set the `languages` field (optional)
+    pub fn languages(
+        mut self,
+        value: impl Into<Option<Vec<jacquard_common::types::string::Language>>>,
+    ) -> Self {
+        self.__unsafe_private_named.2 = value.into();
+        self
+    }
+    /// Set the `languages` field to an Option value (optional)
+    pub fn maybe_languages(
+        mut self,
+        value: Option<Vec<jacquard_common::types::string::Language>>,
+    ) -> Self {
+        self.__unsafe_private_named.2 = value;
+        self
+    }
+}
+
+
+impl<'a, S: get_trending_state::State> GetTrendingBuilder<'a, S> {
+    /// Set the `window` field (optional)
+    pub fn window(mut self, value: impl Into<Option<i64>>) -> Self {
+        self.__unsafe_private_named.4 = value.into();
+        self
+    }
+    /// Set the `window` field to an Option value (optional)
+    pub fn maybe_window(mut self, value: Option<i64>) -> Self {
+        self.__unsafe_private_named.4 = value;
+        self
+    }
+}
+
+impl<'a, S> GetTrendingBuilder<'a, S>
+where
+    S: get_trending_state::State,
+{
+    /// Build the final struct
+    pub fn build(self) -> GetTrending<'a> {
+        GetTrending {
+            cursor: self.__unsafe_private_named.0,
+            include_unspecified_languages: self.__unsafe_private_named.1,
+            languages: self.__unsafe_private_named.2,
+            limit: self.__unsafe_private_named.3,
+            window: self.__unsafe_private_named.4,
+        }
+    }
+}
+
+#[jacquard_derive::lexicon]
+#[derive(
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    jacquard_derive::IntoStatic
+)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTrendingOutput<'a> {
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    #[serde(borrow)]
+    pub cursor: std::option::Option<jacquard_common::CowStr<'a>>,
+    #[serde(borrow)]
+    pub feed: Vec<crate::net_gifdex::feed::PostFeedView<'a>>,
+}
+
+/// Response type for
+///net.gifdex.feed.getTrending
+pub struct GetTrendingResponse;
+impl jacquard_common::xrpc::XrpcResp for GetTrendingResponse {
+    const NSID: &'static str = "net.gifdex.feed.getTrending";
+    const ENCODING: &'static str = "application/json";
+    type Output<'de> = GetTrendingOutput<'de>;
+    type Err<'de> = jacquard_common::xrpc::GenericError<'de>;
+}
+
+impl<'a> jacquard_common::xrpc::XrpcRequest for GetTrending<'a> {
+    const NSID: &'static str = "net.gifdex.feed.getTrending";
+    const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
+    type Response = GetTrendingResponse;
+}
+
+/// Endpoint type for
+///net.gifdex.feed.getTrending
+pub struct GetTrendingRequest;
+impl jacquard_common::xrpc::XrpcEndpoint for GetTrendingRequest {
+    const PATH: &'static str = "/xrpc/net.gifdex.feed.getTrending";
+    const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
+    type Request<'de> = GetTrending<'de>;
+    type Response = GetTrendingResponse;
+}
@@ -0,0 +1,273 @@
+// @generated by jacquard-lexicon. DO NOT EDIT.
+//
+// Lexicon: net.gifdex.feed.searchPosts
+//
+// This file was automatically generated from Lexicon schemas.
+// Any manual changes will be overwritten on the next regeneration.
+
+#[derive(
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    jacquard_derive::IntoStatic
+)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchPosts<'a> {
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    #[serde(borrow)]
+    pub cursor: std::option::Option<jacquard_common::CowStr<'a>>,
+    ///Also include posts that declare no languages when filtering by language.
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    pub include_unspecified_languages: std::option::Option<bool>,
+    ///If specified, only posts tagged with at least one of these languages are returned.
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    pub languages: std::option::Option<Vec<jacquard_common::types::string::Language>>,
+    ///(min: 1, max: 100)
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    pub limit: std::option::Option<i64>,
+    #[serde(borrow)]
+    pub q: jacquard_common::CowStr<'a>,
+}
+
+pub mod search_posts_state {
+
+    pub use crate::builder_types::{Set, Unset, IsSet, IsUnset};
+    #[allow(unused)]
+    use ::core::marker::PhantomData;
+    mod sealed {
+        pub trait Sealed {}
+    }
+    /// State trait tracking which required fields have been set
+    pub trait State: sealed::Sealed {
+        type Q;
+    }
+    /// Empty state - all required fields are unset
+    pub struct Empty(());
+    impl sealed::Sealed for Empty {}
+    impl State for Empty {
+        type Q = Unset;
+    }
+    ///State transition - sets the `q` field to Set
+    pub struct SetQ<S: State = Empty>(PhantomData<fn() -> S>);
+    impl<S: State> sealed::Sealed for SetQ<S> {}
+    impl<S: State> State for SetQ<S> {
+        type Q = Set<members::q>;
+    }
+    /// Marker types for field names
+    #[allow(non_camel_case_types)]
+    pub mod members {
+        ///Marker type for the `q` field
+        pub struct q(());
+    }
+}
+
+/// Builder for constructing an instance of this type
+pub struct SearchPostsBuilder<'a, S: search_posts_state::State> {
+    _phantom_state: ::core::marker::PhantomData<fn() -> S>,
+    __unsafe_private_named: (
+        ::core::option::Option<jacquard_common::CowStr<'a>>,
+        ::core::option::Option<bool>,
+        ::core::option::Option<Vec<jacquard_common::types::string::Language>>,
+        ::core::option::Option<i64>,
+        ::core::option::Option<jacquard_common::CowStr<'a>>,
+    ),
+    _phantom: ::core::marker::PhantomData<&'a ()>,
+}
+
+impl<'a> SearchPosts<'a> {
+    /// Create a new builder for this type
+    pub fn new() -> SearchPostsBuilder<'a, search_posts_state::Empty> {
+        SearchPostsBuilder::new()
+    }
+}
+
+impl<'a> SearchPostsBuilder<'a, search_posts_state::Empty> {
+    /// Create a new builder with all fields unset
+    pub fn new() -> Self {
+        SearchPostsBuilder {
+            _phantom_state: ::core::marker::PhantomData,
+            __unsafe_private_named: (None, None, None, None, None),
+            _phantom: ::core::marker::PhantomData,
+        }
+    }
+}
+
+impl<'a, S: search_posts_state::State> SearchPostsBuilder<'a, S> {
+    /// Set the `cursor` field (optional)
+    pub fn cursor(
+        mut self,
+        value: impl Into<Option<jacquard_common::CowStr<'a>>>,
+    ) -> Self {
+        self.__unsafe_private_named.0 = value.into();
+        self
+    }
+    /// Set the `cursor` field to an Option value (optional)
+    pub fn maybe_cursor(mut self, value: Option<jacquard_common::CowStr<'a>>) -> Self {
+        self.__unsafe_private_named.0 = value;
+        self
+    }
+}
+
+impl<'a, S: search_posts_state::State> SearchPostsBuilder<'a, S> {
+    /// Set the `limit` field (optional)
+    pub fn limit(mut self, value: impl Into<Option<i64>>) -> Self {
+        self.__unsafe_private_named.3 = value.into();
+        self
+    }
+    /// Set the `limit` field to an Option value (optional)
+    pub fn maybe_limit(mut self, value: Option<i64>) -> Self {
+        self.__unsafe_private_named.3 = value;
+        self
+    }
+}
+impl<'a, S: search_posts_state::State> SearchPostsBuilder<'a, S> {
+    /// Set the `include_unspecified_languages` field (optional)
+    pub fn include_unspecified_languages(mut self, value: impl Into<Option<bool>>) -> Self {
+        self.__unsafe_private_named.1 = value.into();
+        self
+    }
+    /// Set the `include_unspecified_languages` field to an Option value (optional)
+    pub fn maybe_include_unspecified_languages(mut self, value: Option<bool>) -> Self {
+        self.__unsafe_private_named.1 = value;
+        self
+    }
+}
+
+impl<'a, S: search_posts_state::State> SearchPostsBuilder<'a, S> {
+    /// Set the `languages` field (optional)
+    pub fn languages(
+        mut self,
+        value: impl Into<Option<Vec<jacquard_common::types::string::Language>>>,
+    ) -> Self {
+        self.__unsafe_private_named.2 = value.into();
+        self
+    }
+    /// Set the `languages` field to an Option value (optional)
+    pub fn maybe_languages(
+        mut self,
+        value: Option<Vec<jacquard_common::types::string::Language>>,
+    ) -> Self {
+        self.__unsafe_private_named.2 = value;
+        self
+    }
+}
+
+
+impl<'a, S> SearchPostsBuilder<'a, S>
+where
+    S: search_posts_state::State,
+    S::Q: search_posts_state::IsUnset,
+{
+    /// Set the `q` field (required)
+    pub fn q(
+        mut self,
+        value: impl Into<jacquard_common::CowStr<'a>>,
+    ) -> SearchPostsBuilder<'a, search_posts_state::SetQ<S>> {
+        self.__unsafe_private_named.4 = ::core::option::Option::Some(value.into());
+        SearchPostsBuilder {
+            _phantom_state: ::core::marker::PhantomData,
+            __unsafe_private_named: self.__unsafe_private_named,
+            _phantom: ::core::marker::PhantomData,
+        }
+    }
+}
+
+impl<'a, S> SearchPostsBuilder<'a, S>
+where
+    S: search_posts_state::State,
+    S::Q: search_posts_state::IsSet,
+{
+    /// Build the final struct
+    pub fn build(self) -> SearchPosts<'a> {
+        SearchPosts {
+            cursor: self.__unsafe_private_named.0,
+            include_unspecified_languages: self.__unsafe_private_named.1,
+            languages: self.__unsafe_private_named.2,
+            limit: self.__unsafe_private_named.3,
+            q: self.__unsafe_private_named.4.unwrap(),
+        }
+    }
+}
+
+#[jacquard_derive::lexicon]
+#[derive(
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    jacquard_derive::IntoStatic
+)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchPostsOutput<'a> {
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    #[serde(borrow)]
+    pub cursor: std::option::Option<jacquard_common::CowStr<'a>>,
+    #[serde(borrow)]
+    pub feed: Vec<crate::net_gifdex::feed::PostFeedView<'a>>,
+}
+
+#[jacquard_derive::open_union]
+#[derive(
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    thiserror::Error,
+    miette::Diagnostic,
+    jacquard_derive::IntoStatic
+)]
+#[serde(tag = "error", content = "message")]
+#[serde(bound(deserialize = "'de: 'a"))]
+pub enum SearchPostsError<'a> {
+    /// The search query is empty or otherwise malformed.
+    #[serde(rename = "InvalidQuery")]
+    InvalidQuery(std::option::Option<jacquard_common::CowStr<'a>>),
+}
+
+impl std::fmt::Display for SearchPostsError<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidQuery(msg) => {
+                write!(f, "InvalidQuery")?;
+                if let Some(msg) = msg {
+                    write!(f, ": {}", msg)?;
+                }
+                Ok(())
+            }
+            Self::Unknown(err) => write!(f, "Unknown error: {:?}", err),
+        }
+    }
+}
+
+/// Response type for
+///net.gifdex.feed.searchPosts
+pub struct SearchPostsResponse;
+impl jacquard_common::xrpc::XrpcResp for SearchPostsResponse {
+    const NSID: &'static str = "net.gifdex.feed.searchPosts";
+    const ENCODING: &'static str = "application/json";
+    type Output<'de> = SearchPostsOutput<'de>;
+    type Err<'de> = SearchPostsError<'de>;
+}
+
+impl<'a> jacquard_common::xrpc::XrpcRequest for SearchPosts<'a> {
+    const NSID: &'static str = "net.gifdex.feed.searchPosts";
+    const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
+    type Response = SearchPostsResponse;
+}
+
+/// Endpoint type for
+///net.gifdex.feed.searchPosts
+pub struct SearchPostsRequest;
+impl jacquard_common::xrpc::XrpcEndpoint for SearchPostsRequest {
+    const PATH: &'static str = "/xrpc/net.gifdex.feed.searchPosts";
+    const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Query;
+    type Request<'de> = SearchPosts<'de>;
+    type Response = SearchPostsResponse;
+}
@@ -0,0 +1,127 @@
+//! Shared Prometheus instrumentation for the Gifdex HTTP services.
+//!
+//! Each service creates an [`HttpMetrics`], applies [`track_http`] as a
+//! middleware over its routes and merges [`metrics_router`] into its router
+//! to expose the scrape endpoint. Service-specific collectors can be
+//! registered on the shared registry via [`HttpMetrics::registry`].
+
+use axum::{
+    Router,
+    extract::{MatchedPath, Request, State},
+    http::header,
+    middleware::Next,
+    response::Response,
+    routing::get,
+};
+use prometheus::{Encoder, HistogramOpts, HistogramVec, IntCounterVec, Opts, Registry, TextEncoder};
+use std::{sync::Arc, time::Instant};
+
+pub struct HttpMetrics {
+    registry: Registry,
+    requests: IntCounterVec,
+    duration: HistogramVec,
+}
+
+impl HttpMetrics {
+    pub fn new() -> Self {
+        let registry = Registry::new();
+        let requests = IntCounterVec::new(
+            Opts::new(
+                "http_requests_total",
+                "Number of HTTP requests handled, by route, method and status code.",
+            ),
+            &["route", "method", "status"],
+        )
+        .unwrap();
+        let duration = HistogramVec::new(
+            HistogramOpts::new(
+                "http_request_duration_seconds",
+                "HTTP request latency in seconds, by route.",
+            ),
+            &["route"],
+        )
+        .unwrap();
+        registry
+            .register(Box::new(requests.clone()))
+            .expect("metric names are distinct");
+        registry
+            .register(Box::new(duration.clone()))
+            .expect("metric names are distinct");
+        Self {
+            registry,
+            requests,
+            duration,
+        }
+    }
+
+    /// The registry backing the scrape endpoint, for services to register
+    /// their own collectors on.
+    pub fn registry(&self) -> &Registry {
+        &self.registry
+    }
+
+    /// Render every registered metric in the Prometheus text exposition format.
+    pub fn render(&self) -> String {
+        let mut buffer = Vec::new();
+        let encoder = TextEncoder::new();
+        if let Err(err) = encoder.encode(&self.registry.gather(), &mut buffer) {
+            // Encoding only fails on io errors, which a Vec can't produce - but
+            // a scrape endpoint should never panic regardless.
+            return format!("# encoding error: {err}\n");
+        }
+        String::from_utf8_lossy(&buffer).into_owned()
+    }
+}
+
+impl Default for HttpMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Middleware recording request count, status distribution and latency for
+/// every request. Apply with [`axum::middleware::from_fn_with_state`].
+pub async fn track_http(
+    State(metrics): State<Arc<HttpMetrics>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    // Label with the matched route pattern rather than the raw path so
+    // metric cardinality stays bounded.
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_owned())
+        .unwrap_or_else(|| "unmatched".to_owned());
+    let method = request.method().to_string();
+    let start = Instant::now();
+    let response = next.run(request).await;
+    metrics
+        .requests
+        .with_label_values(&[&route, &method, response.status().as_str()])
+        .inc();
+    metrics
+        .duration
+        .with_label_values(&[&route])
+        .observe(start.elapsed().as_secs_f64());
+    response
+}
+
+/// Build a router serving the Prometheus scrape endpoint at `/metrics`.
+///
+/// Merge this after the service's CORS and instrumentation layers so scrapes
+/// are neither counted nor served with CORS headers.
+pub fn metrics_router(metrics: Arc<HttpMetrics>) -> Router {
+    Router::new().route(
+        "/metrics",
+        get(move || {
+            let metrics = metrics.clone();
+            async move {
+                (
+                    [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+                    metrics.render(),
+                )
+            }
+        }),
+    )
+}
@@ -0,0 +1,2 @@
+-- Blurhash placeholder string computed from the post's media at ingest time.
+ALTER TABLE posts ADD COLUMN blurhash TEXT;
@@ -0,0 +1,12 @@
+-- Full-text search document over a post's title and tags. `array_to_string`
+-- is only STABLE, so it has to be wrapped in an IMMUTABLE function before it
+-- can back a generated column.
+CREATE FUNCTION post_search_document(title TEXT, tags TEXT[]) RETURNS TSVECTOR
+IMMUTABLE
+LANGUAGE SQL
+RETURN to_tsvector('simple', title || ' ' || coalesce(array_to_string(tags, ' '), ''));
+
+ALTER TABLE posts ADD COLUMN search TSVECTOR
+GENERATED ALWAYS AS (post_search_document(title, tags)) STORED;
+
+CREATE INDEX posts_search_idx ON posts USING GIN (search);
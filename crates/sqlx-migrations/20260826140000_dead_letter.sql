@@ -0,0 +1,13 @@
+-- Dead-lettered events
+--
+-- Events that repeatedly fail their handler are parked here and acked so the
+-- tap stream can progress. Rows are inspected and replayed manually.
+CREATE TABLE dead_letter(
+  id BIGSERIAL PRIMARY KEY,
+  did TEXT NOT NULL,
+  collection TEXT NOT NULL,
+  rkey TEXT NOT NULL,
+  payload JSONB,
+  error TEXT NOT NULL,
+  failed_at BIGINT NOT NULL DEFAULT (extract(epoch from now()) * 1000)::BIGINT
+);
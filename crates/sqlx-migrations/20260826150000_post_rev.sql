@@ -0,0 +1,3 @@
+-- Track the repo revision that last wrote each post so out-of-order event
+-- redeliveries can't overwrite fresher state with stale data.
+ALTER TABLE posts ADD COLUMN rev TEXT;
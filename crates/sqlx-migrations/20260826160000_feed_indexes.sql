@@ -0,0 +1,7 @@
+-- The actor feed reads `WHERE did = ? AND created_at < ? ORDER BY created_at
+-- DESC LIMIT n`; a composite index in feed order lets Postgres walk straight
+-- to the page without sorting. Favourite counts join on (post_did, post_rkey),
+-- which no existing index covers - the primary key leads with the favouriting
+-- account's did.
+CREATE INDEX posts_did_created_at_idx ON posts (did, created_at DESC);
+CREATE INDEX post_favourites_post_idx ON post_favourites (post_did, post_rkey);
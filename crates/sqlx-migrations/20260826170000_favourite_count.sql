@@ -0,0 +1,9 @@
+-- Denormalize favourite counts onto posts, maintained by the ingester as
+-- favourite records come and go, so feed reads don't aggregate
+-- post_favourites on every page. Seed the counter from the rows that already
+-- exist.
+ALTER TABLE posts ADD COLUMN favourite_count BIGINT NOT NULL DEFAULT 0;
+UPDATE posts SET favourite_count = (
+  SELECT COUNT(*) FROM post_favourites
+  WHERE post_did = posts.did AND post_rkey = posts.rkey
+);
@@ -0,0 +1,3 @@
+-- Whether the stored handle was cross-checked against the DID document's
+-- alsoKnownAs entries at ingest time. Stays FALSE while verification is off.
+ALTER TABLE accounts ADD COLUMN handle_verified BOOLEAN NOT NULL DEFAULT FALSE;